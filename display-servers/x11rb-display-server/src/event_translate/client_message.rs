@@ -1,17 +1,28 @@
 use leftwm_core::{
     Command, DisplayEvent,
-    models::{WindowChange, WindowHandle},
+    models::{WindowChange, WindowHandle, XyhwChange},
+    utils::modmask_lookup::Button,
 };
 use x11rb::protocol::xproto;
 
-use crate::{X11rbWindowHandle, xwrap::XWrap};
+use crate::{X11rbWindowHandle, xatom::WMStateWindowState, xwrap::XWrap};
 
 use crate::error::Result;
 
+const NET_WM_STATE_REMOVE: u32 = 0;
+const NET_WM_STATE_ADD: u32 = 1;
+const NET_WM_STATE_TOGGLE: u32 = 2;
+
 pub(crate) fn from_event(
     event: &xproto::ClientMessageEvent,
     xw: &XWrap,
 ) -> Result<Option<DisplayEvent<X11rbWindowHandle>>> {
+    // Windows ask for their frame before they are mapped, so before we manage them.
+    if event.type_ == xw.atoms.NetRequestFrameExtents {
+        xw.set_frame_extents(event.window, u32::try_from(xw.border_width)?)?;
+        return Ok(None);
+    }
+
     if !xw.managed_windows.contains(&event.window) && event.window != xw.get_default_root() {
         return Ok(None);
     }
@@ -61,43 +72,152 @@ pub(crate) fn from_event(
     }
 
     if event.type_ == xw.atoms.NetActiveWindow {
-        xw.set_window_urgency(event.window, true)?;
+        return from_active_window(event, xw);
+    }
+
+    if event.type_ == xw.atoms.WMChangeState {
+        return Ok(from_change_state(event));
+    }
+
+    if event.type_ == xw.atoms.NetShowingDesktop {
+        let show = event.data.as_data32()[0] != 0;
+        return Ok(Some(DisplayEvent::SendCommand(Command::ShowDesktop(Some(
+            show,
+        )))));
+    }
+
+    if event.type_ == xw.atoms.NetCloseWindow {
+        xw.kill_window(WindowHandle(X11rbWindowHandle(event.window)))?;
         return Ok(None);
     }
 
-    if event.type_ == xw.atoms.NetWMState {
-        let data = event.data.as_data32();
-
-        if data[1] == xw.atoms.NetWMStateFullscreen || data[2] == xw.atoms.NetWMStateFullscreen {
-            let set_fullscreen = data[0] == 1;
-            let toggle_fullscreen = data[0] == 2;
-            let mut states = xw.get_window_states_atoms(event.window)?;
-
-            //determine what to change the state to
-            let fullscreen = if toggle_fullscreen {
-                !states.contains(&xw.atoms.NetWMStateFullscreen)
-            } else {
-                set_fullscreen
-            };
-
-            //update the list of states
-            if fullscreen {
-                states.push(xw.atoms.NetWMStateFullscreen);
-            } else {
-                states.retain(|x| x != &xw.atoms.NetWMStateFullscreen);
-            }
-            states.sort_unstable();
-            states.dedup();
-            //set the windows state
-            xw.set_window_states_atoms(event.window, &states)?;
+    if event.type_ == xw.atoms.NetRestackWindow {
+        // Only raising is supported, the window stays in its layer.
+        let detail = event.data.as_data32()[2];
+        if detail == u32::from(xproto::StackMode::ABOVE)
+            || detail == u32::from(xproto::StackMode::TOP_IF)
+        {
+            let handle = WindowHandle(X11rbWindowHandle(event.window));
+            return Ok(Some(DisplayEvent::RaiseWindow(handle)));
         }
+        return Ok(None);
+    }
+
+    if event.type_ == xw.atoms.NetMoveResizeWindow {
+        return Ok(Some(from_move_resize_window(event)));
+    }
 
-        // update the window states
-        let mut change = WindowChange::new(WindowHandle(X11rbWindowHandle(event.window)));
-        let states = xw.get_window_states(event.window)?;
-        change.states = Some(states);
-        return Ok(Some(DisplayEvent::WindowChange(change)));
+    if event.type_ == xw.atoms.NetWMMoveResize {
+        return Ok(from_wm_move_resize(event, xw));
+    }
+
+    if event.type_ == xw.atoms.NetWMState {
+        return from_wm_state(event, xw).map(Some);
     }
 
     Ok(None)
 }
+
+fn from_active_window(
+    event: &xproto::ClientMessageEvent,
+    xw: &XWrap,
+) -> Result<Option<DisplayEvent<X11rbWindowHandle>>> {
+    // Taskbars activate minimized windows to restore them.
+    if xw
+        .get_window_states_atoms(event.window)?
+        .contains(&xw.atoms.NetWMStateHidden)
+    {
+        let handle = WindowHandle(X11rbWindowHandle(event.window));
+        return Ok(Some(DisplayEvent::SendCommand(Command::RestoreMinimized(
+            handle,
+        ))));
+    }
+    xw.set_window_urgency(event.window, true)?;
+    Ok(None)
+}
+
+fn from_change_state(
+    event: &xproto::ClientMessageEvent,
+) -> Option<DisplayEvent<X11rbWindowHandle>> {
+    // ICCCM only allows clients to ask for being iconified.
+    if event.data.as_data32()[0] == u32::from(WMStateWindowState::Iconic) {
+        let handle = WindowHandle(X11rbWindowHandle(event.window));
+        return Some(DisplayEvent::SendCommand(Command::MinimizeWindow(Some(
+            handle,
+        ))));
+    }
+    None
+}
+
+fn from_move_resize_window(event: &xproto::ClientMessageEvent) -> DisplayEvent<X11rbWindowHandle> {
+    let data = event.data.as_data32();
+    // Bits 8 to 11 of the flags tell which of x, y, width and height are given. The values are
+    // signed, x and y may be negative.
+    let given = |bit: u32, value: u32| (data[0] & (1 << bit) != 0).then_some(value.cast_signed());
+    let mut change = WindowChange::new(WindowHandle(X11rbWindowHandle(event.window)));
+    change.floating = Some(XyhwChange {
+        x: given(8, data[1]),
+        y: given(9, data[2]),
+        w: given(10, data[3]),
+        h: given(11, data[4]),
+        ..XyhwChange::default()
+    });
+    DisplayEvent::WindowChange(change)
+}
+
+fn from_wm_move_resize(
+    event: &xproto::ClientMessageEvent,
+    xw: &XWrap,
+) -> Option<DisplayEvent<X11rbWindowHandle>> {
+    let data = event.data.as_data32();
+    // Directions 0 to 7 resize from an edge or corner, 8 moves. Like resizing with the mouse key,
+    // every edge and corner resizes the window from its bottom right corner. The keyboard driven
+    // directions and cancelling are not supported.
+    let button = match data[2] {
+        0..=7 => Button::Secondary,
+        8 => Button::Main,
+        _ => return None,
+    };
+    let handle = WindowHandle(X11rbWindowHandle(event.window));
+    // The root coordinates are signed, they are negative left of or above the first output.
+    let (x, y) = (data[0].cast_signed(), data[1].cast_signed());
+    Some(DisplayEvent::MouseCombo(
+        xw.mouse_key_mask.clone(),
+        button,
+        handle,
+        x,
+        y,
+    ))
+}
+
+fn from_wm_state(
+    event: &xproto::ClientMessageEvent,
+    xw: &XWrap,
+) -> Result<DisplayEvent<X11rbWindowHandle>> {
+    let data = event.data.as_data32();
+    let handle = WindowHandle(X11rbWindowHandle(event.window));
+    let mut change = WindowChange::new(handle);
+    let mut states = xw.get_window_states_atoms(event.window)?;
+
+    // `data[0]` tells whether to remove, add or toggle the one or two states that follow.
+    for &atom in data[1..=2].iter().filter(|&&atom| atom != 0) {
+        let set = match data[0] {
+            NET_WM_STATE_REMOVE => false,
+            NET_WM_STATE_ADD => true,
+            NET_WM_STATE_TOGGLE => !states.contains(&atom),
+            _ => continue,
+        };
+        if atom == xw.atoms.NetWMStateDemandsAttention {
+            xw.set_window_urgency(event.window, set)?;
+        }
+        states.retain(|&state| state != atom);
+        if set {
+            states.push(atom);
+        }
+    }
+    xw.set_window_states_atoms(event.window, &states)?;
+
+    change.states = Some(xw.get_window_states(event.window)?);
+    change.urgent = Some(xw.is_window_urgent(event.window)?);
+    Ok(DisplayEvent::WindowChange(change))
+}
@@ -41,23 +41,25 @@ pub(crate) fn from_event(
         x if x == <xproto::AtomEnum as Into<u32>>::into(xproto::AtomEnum::WM_NORMAL_HINTS) => {
             let handle = WindowHandle(X11rbWindowHandle(event.window));
             let mut change = WindowChange::new(handle);
+            change.size_hints = xw.get_size_hints(event.window)?;
 
-            let Some(hint) = xw.get_hint_sizing_as_xyhw(event.window)? else {
-                return Ok(None);
-            };
-            if hint.x.is_none() && hint.y.is_none() && hint.w.is_none() && hint.h.is_none() {
+            if let Some(hint) = xw.get_hint_sizing_as_xyhw(event.window)?
+                && (hint.x.is_some() || hint.y.is_some() || hint.w.is_some() || hint.h.is_some())
+            {
+                let mut xyhw = Xyhw::default();
+                hint.update(&mut xyhw);
+                change.requested = Some(xyhw);
+            }
+            if change.size_hints.is_none() && change.requested.is_none() {
                 return Ok(None);
             }
-
-            let mut xyhw = Xyhw::default();
-            hint.update(&mut xyhw);
-            change.requested = Some(xyhw);
             Ok(Some(DisplayEvent::WindowChange(change)))
         }
 
         x if x == <xproto::AtomEnum as Into<u32>>::into(xproto::AtomEnum::WM_HINTS) => Ok(xw
             .get_wmhints(event.window)?
-            .map(|hints| build_change_hints(event, hints))
+            .map(|hints| build_change_hints(xw, event, hints))
+            .transpose()?
             .map(DisplayEvent::WindowChange)),
 
         x if x == <xproto::AtomEnum as Into<u32>>::into(xproto::AtomEnum::WM_NAME) => {
@@ -82,6 +84,7 @@ pub(crate) fn from_event(
                 let mut change = WindowChange::new(handle);
                 let states = xw.get_window_states(event.window)?;
                 change.states = Some(states);
+                change.urgent = Some(xw.is_window_urgent(event.window)?);
                 return Ok(Some(DisplayEvent::WindowChange(change)));
             }
 
@@ -91,16 +94,18 @@ pub(crate) fn from_event(
 }
 
 fn build_change_hints(
+    xw: &XWrap,
     event: &xproto::PropertyNotifyEvent,
     hints: WmHints,
-) -> WindowChange<X11rbWindowHandle> {
+) -> Result<WindowChange<X11rbWindowHandle>> {
     let handle = WindowHandle(X11rbWindowHandle(event.window));
     let mut change = WindowChange::new(handle);
 
     change.never_focus = hints.input.map(|i| !i);
-    change.urgent = Some(hints.urgent);
+    // `_NET_WM_STATE_DEMANDS_ATTENTION` keeps the window urgent without the hint.
+    change.urgent = Some(hints.urgent || xw.is_window_urgent(event.window)?);
 
-    change
+    Ok(change)
 }
 
 fn update_title(
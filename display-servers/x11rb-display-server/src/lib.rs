@@ -2,10 +2,11 @@
 
 use leftwm_core::{
     Config, DisplayAction, DisplayEvent, DisplayServer, Mode, Window, Workspace,
-    models::{Handle, Screen, TagId, WindowHandle, WindowState},
+    models::{Handle, TagId, WindowHandle, WindowState, Xyhw},
+    utils::screen_tracker::ScreenTracker,
 };
 use serde::{Deserialize, Serialize};
-use x11rb::protocol::xproto;
+use x11rb::protocol::{Event, xproto};
 
 use crate::xwrap::XWrap;
 use error::Result;
@@ -24,6 +25,7 @@ pub struct X11rbDisplayServer {
     xw: XWrap,
     root: xproto::Window,
     initial_events: Vec<DisplayEvent<X11rbWindowHandle>>,
+    screens: ScreenTracker<X11rbWindowHandle>,
 }
 
 impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
@@ -38,8 +40,9 @@ impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
             xw: xwrap,
             root,
             initial_events: Vec::new(),
+            screens: ScreenTracker::new(config),
         };
-        instance.initial_events = instance.initial_events(config);
+        instance.initial_events = instance.initial_events();
 
         instance
     }
@@ -53,6 +56,7 @@ impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
         if let Err(e) = self.xw.load_config(config) {
             tracing::error!("Error when loading config: {}", e);
         }
+        self.screens.load_config(config);
         if let Err(e) = self.xw.update_colors(focused, windows) {
             tracing::error!("Error when updating border colors: {}", e);
         }
@@ -76,9 +80,13 @@ impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
 
     fn get_next_events(&mut self) -> Vec<leftwm_core::DisplayEvent<X11rbWindowHandle>> {
         let mut events = std::mem::take(&mut self.initial_events);
+        let mut screens_changed = false;
 
         loop {
             match self.xw.poll_next_event() {
+                Ok(Some(Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_))) => {
+                    screens_changed = true;
+                }
                 Ok(Some(ev)) => {
                     tracing::trace!("New event received: {:?}", ev);
                     if let Some(ev) = event_translate::translate(&ev, &mut self.xw) {
@@ -93,6 +101,11 @@ impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
             }
         }
 
+        // A single change of monitors comes as a burst of RandR events.
+        if screens_changed {
+            events.append(&mut self.screen_events());
+        }
+
         for event in &events {
             if let DisplayEvent::WindowDestroy(WindowHandle(X11rbWindowHandle(w))) = event
                 && let Err(e) = self.xw.force_unmapped(*w)
@@ -124,6 +137,9 @@ impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
             DisplayAction::ReadyToMoveWindow(h) => from_ready_to_move_window(xw, h),
             DisplayAction::ReadyToResizeWindow(h) => from_ready_to_resize_window(xw, h),
             DisplayAction::SetCurrentTags(t) => from_set_current_tags(xw, t),
+            DisplayAction::SetDesktopNames(l) => from_set_desktop_names(xw, l),
+            DisplayAction::SetWorkArea(a) => from_set_work_area(xw, a),
+            DisplayAction::SetShowingDesktop(s) => from_set_showing_desktop(xw, s),
             DisplayAction::SetWindowTag(h, t) => from_set_window_tag(xw, h, t),
             DisplayAction::ConfigureXlibWindow(w) => from_configure_xlib_window(xw, &w),
 
@@ -173,60 +189,8 @@ impl DisplayServer<X11rbWindowHandle> for X11rbDisplayServer {
 }
 
 impl X11rbDisplayServer {
-    fn initial_events(&self, config: &impl Config) -> Vec<DisplayEvent<X11rbWindowHandle>> {
-        let mut events = vec![];
-        if let Some(workspaces) = config.workspaces() {
-            let screens = match self.xw.get_screens() {
-                Ok(s) => s,
-                Err(e) => {
-                    tracing::error!(error = ?e, "An error occurred when trying to get screens.");
-                    return events;
-                }
-            };
-
-            for (i, wsc) in workspaces.iter().enumerate() {
-                let mut screen = Screen::from(wsc);
-                screen.root = WindowHandle(X11rbWindowHandle(self.root));
-                // If there is a screen corresponding to the given output, create the workspace
-                match screens.iter().find(|i| i.output == wsc.output) {
-                    Some(output_match) => {
-                        if wsc.relative.unwrap_or(false) {
-                            screen.bbox.add(output_match.bbox);
-                        }
-                        screen.id = Some(i + 1);
-                    }
-                    None => continue,
-                }
-                let e = DisplayEvent::ScreenCreate(screen);
-                events.push(e);
-            }
-
-            let auto_derive_workspaces: bool = if config.auto_derive_workspaces() {
-                true
-            } else if events.is_empty() {
-                tracing::warn!(
-                    "No Workspace in Workspace config matches connected screen. Falling back to \"auto_derive_workspaces: true\"."
-                );
-                true
-            } else {
-                false
-            };
-
-            let mut next_id = workspaces.len() + 1;
-
-            // If there is no hardcoded workspace layout, add every screen not mentioned in the config.
-            if auto_derive_workspaces {
-                screens
-                    .iter()
-                    .filter(|screen| !workspaces.iter().any(|wsc| wsc.output == screen.output))
-                    .for_each(|screen| {
-                        let mut s = screen.clone();
-                        s.id = Some(next_id);
-                        next_id += 1;
-                        events.push(DisplayEvent::ScreenCreate(s));
-                    });
-            }
-        }
+    fn initial_events(&mut self) -> Vec<DisplayEvent<X11rbWindowHandle>> {
+        let mut events = self.screen_events();
 
         // Tell manager about existing windows.
         events.append(&mut self.find_all_windows());
@@ -234,6 +198,22 @@ impl X11rbDisplayServer {
         events
     }
 
+    /// Creates, updates or destroys screens to match the connected outputs.
+    fn screen_events(&mut self) -> Vec<DisplayEvent<X11rbWindowHandle>> {
+        match self.xw.get_screens() {
+            Ok(mut outputs) => {
+                for output in &mut outputs {
+                    output.root = WindowHandle(X11rbWindowHandle(self.root));
+                }
+                self.screens.update(&outputs)
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "An error occurred when trying to get screens.");
+                vec![]
+            }
+        }
+    }
+
     fn find_all_windows(&self) -> Vec<DisplayEvent<X11rbWindowHandle>> {
         let mut all: Vec<DisplayEvent<X11rbWindowHandle>> = Vec::new();
         match self.xw.get_all_windows() {
@@ -409,6 +389,36 @@ fn from_set_current_tags(
     Ok(None)
 }
 
+fn from_set_desktop_names(
+    xw: &mut XWrap,
+    labels: Vec<String>,
+) -> Result<Option<DisplayEvent<X11rbWindowHandle>>> {
+    xw.tag_labels = labels;
+    xw.set_desktop_names()?;
+    Ok(None)
+}
+
+fn from_set_work_area(
+    xw: &mut XWrap,
+    area: Xyhw,
+) -> Result<Option<DisplayEvent<X11rbWindowHandle>>> {
+    xw.set_work_area([
+        u32::try_from(area.x())?,
+        u32::try_from(area.y())?,
+        u32::try_from(area.w())?,
+        u32::try_from(area.h())?,
+    ])?;
+    Ok(None)
+}
+
+fn from_set_showing_desktop(
+    xw: &mut XWrap,
+    showing: bool,
+) -> Result<Option<DisplayEvent<X11rbWindowHandle>>> {
+    xw.set_desktop_prop(&[u32::from(showing)], xw.atoms.NetShowingDesktop)?;
+    Ok(None)
+}
+
 fn from_set_window_tag(
     xw: &mut XWrap,
     handle: WindowHandle<X11rbWindowHandle>,
@@ -9,6 +9,7 @@ atom_manager! {
         WMProtocols: b"WM_PROTOCOLS" as &[u8],
         WMDelete: b"WM_DELETE_WINDOW",
         WMState: b"WM_STATE",
+        WMChangeState: b"WM_CHANGE_STATE",
         WMClass: b"WM_CLASS",
         WMTakeFocus: b"WM_TAKE_FOCUS",
         NetActiveWindow: b"_NET_ACTIVE_WINDOW",
@@ -66,6 +67,14 @@ atom_manager! {
         NetWMDesktop: b"_NET_WM_DESKTOP",
         NetWMStrutPartial: b"_NET_WM_STRUT_PARTIAL",
         NetWMStrut: b"_NET_WM_STRUT",
+        NetWorkarea: b"_NET_WORKAREA",
+        NetFrameExtents: b"_NET_FRAME_EXTENTS",
+        NetRequestFrameExtents: b"_NET_REQUEST_FRAME_EXTENTS",
+        NetCloseWindow: b"_NET_CLOSE_WINDOW",
+        NetMoveResizeWindow: b"_NET_MOVERESIZE_WINDOW",
+        NetWMMoveResize: b"_NET_WM_MOVERESIZE",
+        NetShowingDesktop: b"_NET_SHOWING_DESKTOP",
+        NetRestackWindow: b"_NET_RESTACK_WINDOW",
 
         UTF8String: b"UTF8_STRING",
 
@@ -128,6 +137,14 @@ impl AtomCollection {
             self.NetWMDesktop,
             self.NetWMStrutPartial,
             self.NetWMStrut,
+            self.NetWorkarea,
+            self.NetFrameExtents,
+            self.NetRequestFrameExtents,
+            self.NetCloseWindow,
+            self.NetMoveResizeWindow,
+            self.NetWMMoveResize,
+            self.NetShowingDesktop,
+            self.NetRestackWindow,
         ]
     }
 }
@@ -138,6 +155,7 @@ impl AtomCollection {
             x if x == self.WMProtocols => "WM_PROTOCOLS",
             x if x == self.WMDelete => "WM_DELETE_WINDOW",
             x if x == self.WMState => "WM_STATE",
+            x if x == self.WMChangeState => "WM_CHANGE_STATE",
             x if x == self.WMClass => "WM_CLASS",
             x if x == self.WMTakeFocus => "WM_TAKE_FOCUS",
             x if x == self.NetActiveWindow => "_NET_ACTIVE_WINDOW",
@@ -191,6 +209,14 @@ impl AtomCollection {
             x if x == self.NetWMDesktop => "_NET_WM_DESKTOP",
             x if x == self.NetWMStrutPartial => "_NET_WM_STRUT_PARTIAL",
             x if x == self.NetWMStrut => "_NET_WM_STRUT",
+            x if x == self.NetWorkarea => "_NET_WORKAREA",
+            x if x == self.NetFrameExtents => "_NET_FRAME_EXTENTS",
+            x if x == self.NetRequestFrameExtents => "_NET_REQUEST_FRAME_EXTENTS",
+            x if x == self.NetCloseWindow => "_NET_CLOSE_WINDOW",
+            x if x == self.NetMoveResizeWindow => "_NET_MOVERESIZE_WINDOW",
+            x if x == self.NetWMMoveResize => "_NET_WM_MOVERESIZE",
+            x if x == self.NetShowingDesktop => "_NET_SHOWING_DESKTOP",
+            x if x == self.NetRestackWindow => "_NET_RESTACK_WINDOW",
             x if x == self.WMNormalHints => "WM_NORMAL_HINTS",
             x if x == self.WMSizeHints => "WM_SIZE_HINTS",
             x if x == self.UTF8String => "UTF8_STRING",
@@ -216,7 +242,8 @@ impl TryFrom<&u32> for WMStateWindowState {
         match value {
             0 => Ok(Self::Withdrawn),
             1 => Ok(Self::Normal),
-            2 => Ok(Self::Iconic),
+            // Older versions wrote 2 on windows hidden on other tags.
+            2 | 3 => Ok(Self::Iconic),
             _ => Err(InvalidWindowState),
         }
     }
@@ -227,7 +254,7 @@ impl From<WMStateWindowState> for u32 {
         match value {
             WMStateWindowState::Withdrawn => 0,
             WMStateWindowState::Normal => 1,
-            WMStateWindowState::Iconic => 2,
+            WMStateWindowState::Iconic => 3,
         }
     }
 }
@@ -56,6 +56,7 @@ pub struct Colors {
     normal: u32,
     floating: u32,
     active: u32,
+    urgent: u32,
     background: u32,
 }
 
@@ -76,6 +77,8 @@ pub(crate) struct XWrap {
     pub mouse_key_mask: ModMask,
     pub mode_origin: (i32, i32),
     pub window_hiding_strategy: WindowHidingStrategy,
+    pub border_width: i32,
+    work_area: [u32; 4],
 
     #[allow(unused)]
     task_guard: oneshot::Receiver<()>,
@@ -147,6 +150,7 @@ impl XWrap {
             normal: 0,
             floating: 0,
             active: 0,
+            urgent: 0,
             background: 0,
         };
 
@@ -169,6 +173,8 @@ impl XWrap {
             mouse_key_mask: ModMask::Zero,
             mode_origin: (0, 0),
             window_hiding_strategy: WindowHidingStrategy::default(),
+            border_width: 0,
+            work_area: [0; 4],
 
             task_guard,
             task_notify,
@@ -197,9 +203,11 @@ impl XWrap {
             normal: self.get_color(&config.default_border_color())?,
             floating: self.get_color(&config.floating_border_color())?,
             active: self.get_color(&config.focused_border_color())?,
+            urgent: self.get_color(&config.urgent_border_color())?,
             background: self.get_color(&config.background_color())?,
         };
         self.window_hiding_strategy = config.window_hiding_strategy();
+        self.border_width = config.border_width();
         Ok(())
     }
 
@@ -214,10 +222,8 @@ impl XWrap {
             let WindowHandle(X11rbWindowHandle(handle)) = window.handle;
             let color: u32 = if focused == Some(window.handle) {
                 self.colors.active
-            } else if window.floating() {
-                self.colors.floating
             } else {
-                self.colors.normal
+                self.unfocused_border_color(window)
             };
             self.set_window_border_color(handle, color)?;
         }
@@ -225,6 +231,17 @@ impl XWrap {
         Ok(())
     }
 
+    /// The border color of a window which is not focused.
+    fn unfocused_border_color(&self, window: &Window<X11rbWindowHandle>) -> u32 {
+        if window.urgent {
+            self.colors.urgent
+        } else if window.floating() {
+            self.colors.floating
+        } else {
+            self.colors.normal
+        }
+    }
+
     pub fn init(&mut self) -> Result<()> {
         let root = self.root;
 
@@ -236,6 +253,17 @@ impl XWrap {
                 .event_mask(root_event_mask()),
         )?;
 
+        // Get notified when monitors are plugged in, unplugged or rearranged.
+        let randr_mask = randr::NotifyMask::SCREEN_CHANGE
+            | randr::NotifyMask::CRTC_CHANGE
+            | randr::NotifyMask::OUTPUT_CHANGE;
+        if let Err(e) = randr::select_input(&self.conn, root, randr_mask) {
+            tracing::warn!(
+                "RandR is unavailable, monitor changes will go unnoticed: {}",
+                e
+            );
+        }
+
         // EWMH compliance.
         let supported: Vec<xproto::Atom> = self.atoms.net_supported();
         self.replace_property_u32(
@@ -255,6 +283,35 @@ impl XWrap {
 
     /// EWMH support used for bars such as polybar.
     pub fn init_desktops_hints(&self) -> Result<()> {
+        self.set_desktop_names()?;
+
+        // Set a current desktop.
+        self.set_desktop_prop(&[0_u32, x11rb::CURRENT_TIME], self.atoms.NetCurrentDesktop)?;
+
+        // Set the WM NAME.
+        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String)?;
+
+        self.set_desktop_prop_string(
+            "LeftWM",
+            self.atoms.WMClass,
+            xproto::AtomEnum::STRING.into(),
+        )?;
+
+        self.set_desktop_prop_u32(
+            self.root,
+            self.atoms.NetSupportingWmCheck,
+            xproto::AtomEnum::WINDOW.into(),
+        )?;
+
+        // Set a viewport.
+        self.set_desktop_prop(&[0_u32, 0_u32], self.atoms.NetDesktopViewport)?;
+
+        self.set_desktop_prop(&[0], self.atoms.NetShowingDesktop)?;
+        Ok(())
+    }
+
+    /// Sets the number and names of the desktops from the tag labels.
+    pub fn set_desktop_names(&self) -> Result<()> {
         let tag_labels = &self.tag_labels;
         let tag_length = tag_labels.len();
 
@@ -264,9 +321,6 @@ impl XWrap {
             self.atoms.NetNumberOfDesktops,
         )?;
 
-        // Set a current desktop.
-        self.set_desktop_prop(&[0_u32, x11rb::CURRENT_TIME], self.atoms.NetCurrentDesktop)?;
-
         // Set desktop names.
         //
         // Convert the list of tag names string into a valid list of strings for an atom,
@@ -291,26 +345,17 @@ impl XWrap {
             &bytes[..bytes.len() - 1],
         )?;
 
-        // Set the WM NAME.
-        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String)?;
-
-        self.set_desktop_prop_string(
-            "LeftWM",
-            self.atoms.WMClass,
-            xproto::AtomEnum::STRING.into(),
-        )?;
-
-        self.set_desktop_prop_u32(
-            self.root,
-            self.atoms.NetSupportingWmCheck,
-            xproto::AtomEnum::WINDOW.into(),
-        )?;
-
-        // Set a viewport.
-        self.set_desktop_prop(&[0_u32, 0_u32], self.atoms.NetDesktopViewport)?;
+        // There is a work area for every desktop.
+        self.set_desktop_prop(&self.work_area.repeat(tag_length), self.atoms.NetWorkarea)?;
         Ok(())
     }
 
+    /// Sets the work area of all desktops, the part of the screens not covered by docks.
+    pub fn set_work_area(&mut self, area: [u32; 4]) -> Result<()> {
+        self.work_area = area;
+        self.set_desktop_prop(&area.repeat(self.tag_labels.len()), self.atoms.NetWorkarea)
+    }
+
     /// Send a xevent atom for a window to X.
     fn send_xevent_atom(&self, window: xproto::Window, atom: xproto::Atom) -> Result<bool> {
         if self.can_send_xevent_atom(window, atom)? {
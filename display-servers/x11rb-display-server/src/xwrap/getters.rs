@@ -1,7 +1,7 @@
 use std::backtrace::Backtrace;
 
 use leftwm_core::models::{
-    BBox, DockArea, Screen, WindowHandle, WindowState, WindowType, XyhwChange,
+    BBox, DockArea, Screen, SizeHints, WindowHandle, WindowState, WindowType, XyhwChange,
 };
 use x11rb::{
     connection::Connection,
@@ -148,6 +148,18 @@ impl XWrap {
         Ok(None)
     }
 
+    /// Returns the `WM_NORMAL_HINTS` of a window which tiled windows keep to.
+    pub fn get_size_hints(&self, window: xproto::Window) -> Result<Option<SizeHints>> {
+        Ok(self.get_hint_sizing(window)?.map(|size| SizeHints {
+            min: size.min_size,
+            max: size.max_size,
+            base: size.base_size,
+            increment: size.size_increment,
+            min_aspect: size.aspect.map(|(min, _)| (min.numerator, min.denominator)),
+            max_aspect: size.aspect.map(|(_, max)| (max.numerator, max.denominator)),
+        }))
+    }
+
     /// Returns the next `Xevent` of the xserver.
     pub fn poll_next_event(&self) -> Result<Option<x11rb::protocol::Event>> {
         Ok(self.conn.poll_for_event()?)
@@ -348,23 +360,24 @@ impl XWrap {
 
         let mut window_states: Vec<WindowState> = window_states_atoms
             .iter()
-            .map(|a| match a {
-                x if x == &self.atoms.NetWMStateModal => WindowState::Modal,
-                x if x == &self.atoms.NetWMStateSticky => WindowState::Sticky,
+            .filter_map(|a| match a {
+                x if x == &self.atoms.NetWMStateModal => Some(WindowState::Modal),
+                x if x == &self.atoms.NetWMStateSticky => Some(WindowState::Sticky),
                 x if x == &self.atoms.NetWMStateMaximizedVert && !maximized => {
-                    WindowState::MaximizedVert
+                    Some(WindowState::MaximizedVert)
                 }
                 x if x == &self.atoms.NetWMStateMaximizedHorz && !maximized => {
-                    WindowState::MaximizedHorz
+                    Some(WindowState::MaximizedHorz)
                 }
-                x if x == &self.atoms.NetWMStateShaded => WindowState::Shaded,
-                x if x == &self.atoms.NetWMStateSkipTaskbar => WindowState::SkipTaskbar,
-                x if x == &self.atoms.NetWMStateSkipPager => WindowState::SkipPager,
-                x if x == &self.atoms.NetWMStateHidden => WindowState::Hidden,
-                x if x == &self.atoms.NetWMStateFullscreen => WindowState::Fullscreen,
-                x if x == &self.atoms.NetWMStateAbove => WindowState::Above,
-                x if x == &self.atoms.NetWMStateBelow => WindowState::Below,
-                _ => WindowState::Modal,
+                x if x == &self.atoms.NetWMStateShaded => Some(WindowState::Shaded),
+                x if x == &self.atoms.NetWMStateSkipTaskbar => Some(WindowState::SkipTaskbar),
+                x if x == &self.atoms.NetWMStateSkipPager => Some(WindowState::SkipPager),
+                x if x == &self.atoms.NetWMStateHidden => Some(WindowState::Hidden),
+                x if x == &self.atoms.NetWMStateFullscreen => Some(WindowState::Fullscreen),
+                x if x == &self.atoms.NetWMStateAbove => Some(WindowState::Above),
+                x if x == &self.atoms.NetWMStateBelow => Some(WindowState::Below),
+                // Both maximized states were merged into `Maximized`, urgency is not a state.
+                _ => None,
             })
             .collect();
 
@@ -446,6 +459,16 @@ impl XWrap {
         Ok(WmHints::get(&self.conn, window)?.reply()?)
     }
 
+    /// Whether a window asks for attention, with the urgency hint of its `WM_HINTS` or with
+    /// `_NET_WM_STATE_DEMANDS_ATTENTION`.
+    pub fn is_window_urgent(&self, window: xproto::Window) -> Result<bool> {
+        let hinted = self.get_wmhints(window)?.is_some_and(|hints| hints.urgent);
+        Ok(hinted
+            || self
+                .get_window_states_atoms(window)?
+                .contains(&self.atoms.NetWMStateDemandsAttention))
+    }
+
     /// Returns the `WM_STATE` of a window.
     pub fn get_wm_state(
         &self,
@@ -83,6 +83,16 @@ impl XWrap {
         self.replace_property_u32(self.root, atom, xproto::AtomEnum::CARDINAL.into(), data)
     }
 
+    /// Sets the border a window is framed with on each side.
+    pub fn set_frame_extents(&self, window: xproto::Window, border: u32) -> Result<()> {
+        self.replace_property_u32(
+            window,
+            self.atoms.NetFrameExtents,
+            xproto::AtomEnum::CARDINAL.into(),
+            &[border; 4],
+        )
+    }
+
     /// Sets a desktop property with type `u32`.
     pub fn set_desktop_prop_u32(
         &self,
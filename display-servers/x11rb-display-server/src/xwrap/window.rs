@@ -35,6 +35,7 @@ impl XWrap {
         let mut can_resize = actions.contains(&self.atoms.NetWMActionResize);
         let trans = self.get_transient_for(window)?;
         let sizing_hint = self.get_hint_sizing_as_xyhw(window)?;
+        let size_hints = self.get_size_hints(window)?;
         let wm_hint = self.get_wmhints(window)?;
 
         // Build the new window, and fill in info about it.
@@ -76,11 +77,12 @@ impl XWrap {
         }
 
         w.requested = Some(requested);
+        w.size_hints = size_hints;
         w.can_resize = can_resize;
         if let Some(hint) = wm_hint {
             w.never_focus = !hint.input.unwrap_or(true);
-            w.urgent = hint.urgent;
         }
+        w.urgent = self.is_window_urgent(window)?;
         // Is this needed? Made it so it doens't overwrite prior sizing.
         if w.floating() && sizing_hint.is_none() {
             let geo = self.get_window_geometry(window)?;
@@ -199,6 +201,10 @@ impl XWrap {
             };
             self.set_window_config(handle, &changes)?;
             self.configure_window(window)?;
+            self.set_frame_extents(handle, u32::try_from(window.border())?)?;
+            if handle != self.focused_window {
+                self.set_window_border_color(handle, self.unfocused_border_color(window))?;
+            }
         }
         let (state, _) = self.get_wm_state(handle)?;
         // Only change when needed. This prevents task bar icons flashing (especially with steam).
@@ -305,12 +311,7 @@ impl XWrap {
         // Update previous window.
         if let Some(previous) = previous {
             let WindowHandle(X11rbWindowHandle(previous_handle)) = previous.handle;
-            let color = if previous.floating() {
-                self.colors.floating
-            } else {
-                self.colors.normal
-            };
-            self.set_window_border_color(previous_handle, color)?;
+            self.set_window_border_color(previous_handle, self.unfocused_border_color(previous))?;
             // Open up button1 clicking on the previously focused window.
             if self.focus_behaviour.is_clickto() {
                 self.grab_mouse_clicks(previous_handle, false)?;
@@ -319,6 +320,7 @@ impl XWrap {
         self.focused_window = handle;
         self.grab_mouse_clicks(handle, true)?;
         self.set_window_urgency(handle, false)?;
+        self.set_state(window.handle, false, self.atoms.NetWMStateDemandsAttention)?;
         self.set_window_border_color(handle, self.colors.active)?;
         self.focus(handle, window.never_focus)?;
         self.sync()?;
@@ -0,0 +1,421 @@
+//! Runs `X11rbDisplayServer` and the core against a private Xvfb, with simple x11rb clients.
+//!
+//! Every test starts its own Xvfb, so they are ignored by default and run with
+//! `cargo test --workspace --test xvfb -- --ignored`.
+use leftwm_core::config::{TestConfig, WindowHidingStrategy};
+use leftwm_core::layouts;
+use leftwm_core::models::FocusOnActivationBehaviour;
+use leftwm_core::{DisplayServer, Manager};
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{self, ConnectionExt as _};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb_display_server::{X11rbDisplayServer, X11rbWindowHandle};
+
+/// `DISPLAY` is process wide, so only one test may talk to an Xvfb at a time.
+static DISPLAY_LOCK: Mutex<()> = Mutex::new(());
+
+struct Xvfb {
+    child: Child,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Xvfb {
+    /// Starts Xvfb on a free display and points `DISPLAY` at it.
+    fn start() -> Self {
+        let lock = DISPLAY_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut child = Command::new("Xvfb")
+            .args([
+                "-displayfd",
+                "1",
+                "-screen",
+                "0",
+                "1280x800x24",
+                "-nolisten",
+                "tcp",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Unable to start Xvfb");
+
+        // Xvfb writes the display number once it accepts connections.
+        let mut display = String::new();
+        let stdout = child.stdout.take().expect("stdout is piped");
+        BufReader::new(stdout)
+            .read_line(&mut display)
+            .expect("Xvfb did not report its display");
+        // SAFETY: `DISPLAY_LOCK` keeps every other test from touching the environment.
+        unsafe { std::env::set_var("DISPLAY", format!(":{}", display.trim())) };
+
+        Self { child, _lock: lock }
+    }
+}
+
+impl Drop for Xvfb {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+/// The config of the core tests, with the behaviour of a default leftwm config where the tests
+/// depend on it.
+fn config() -> TestConfig {
+    TestConfig {
+        tags: vec!["1".to_owned(), "2".to_owned(), "3".to_owned()],
+        layouts: vec![layouts::EVEN_HORIZONTAL.to_owned()],
+        layout_definitions: leftwm_layouts::layouts::Layouts::default().layouts,
+        workspaces: Some(vec![]),
+        single_window_border: true,
+        focus_on_activation: FocusOnActivationBehaviour::SwitchTo,
+        focus_new_windows: true,
+        window_hiding_strategy: WindowHidingStrategy::Unmap,
+        ..TestConfig::default()
+    }
+}
+
+/// Leftwm running on an Xvfb, plus a client connection to create windows with.
+struct Harness {
+    manager: Manager<X11rbWindowHandle, TestConfig, X11rbDisplayServer>,
+    client: RustConnection,
+    root: xproto::Window,
+    // Dropped last: the display server has to let go of the runtime and the display first.
+    _runtime: tokio::runtime::Runtime,
+    _xvfb: Xvfb,
+}
+
+impl Harness {
+    fn start() -> Self {
+        Self::start_after(|_, _| {})
+    }
+
+    /// Starts leftwm once `setup` has prepared the display with the client connection and the
+    /// root window, eg. with windows left behind by a previous leftwm.
+    fn start_after(setup: impl FnOnce(&RustConnection, xproto::Window)) -> Self {
+        let xvfb = Xvfb::start();
+        let (client, screen) = x11rb::connect(None).expect("Unable to connect to Xvfb");
+        let root = client.setup().roots[screen].root;
+        setup(&client, root);
+        client.flush().unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Unable to build a runtime");
+        let manager = {
+            let _guard = runtime.enter();
+            Manager::new(config())
+        };
+        let mut harness = Self {
+            manager,
+            client,
+            root,
+            _runtime: runtime,
+            _xvfb: xvfb,
+        };
+        harness.settle();
+        harness
+    }
+
+    /// Lets leftwm handle everything the clients did so far.
+    fn settle(&mut self) {
+        self.client.flush().unwrap();
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(20));
+            self.manager.run_until_idle();
+            self.manager.display_server.flush();
+        }
+    }
+
+    /// Creates and maps a window, optionally with a `_NET_WM_WINDOW_TYPE`.
+    fn map_window(&mut self, window_type: Option<&str>) -> xproto::Window {
+        let window = self.client.generate_id().unwrap();
+        self.client
+            .create_window(
+                x11rb::COPY_DEPTH_FROM_PARENT,
+                window,
+                self.root,
+                0,
+                0,
+                100,
+                100,
+                0,
+                xproto::WindowClass::INPUT_OUTPUT,
+                x11rb::COPY_FROM_PARENT,
+                &xproto::CreateWindowAux::new(),
+            )
+            .unwrap();
+        if let Some(window_type) = window_type {
+            let property = self.atom("_NET_WM_WINDOW_TYPE");
+            let value = self.atom(window_type);
+            self.client
+                .change_property32(
+                    xproto::PropMode::REPLACE,
+                    window,
+                    property,
+                    xproto::AtomEnum::ATOM,
+                    &[value],
+                )
+                .unwrap();
+        }
+        self.client.map_window(window).unwrap();
+        self.settle();
+        window
+    }
+
+    fn atom(&self, name: &str) -> xproto::Atom {
+        self.client
+            .intern_atom(false, name.as_bytes())
+            .unwrap()
+            .reply()
+            .unwrap()
+            .atom
+    }
+
+    fn root_property(&self, name: &str) -> Vec<u32> {
+        self.property(self.root, name)
+    }
+
+    fn property(&self, window: xproto::Window, name: &str) -> Vec<u32> {
+        let reply = self
+            .client
+            .get_property(
+                false,
+                window,
+                self.atom(name),
+                xproto::AtomEnum::ANY,
+                0,
+                1024,
+            )
+            .unwrap()
+            .reply()
+            .unwrap();
+        reply.value32().map(Iterator::collect).unwrap_or_default()
+    }
+
+    /// Sends a client message to the window manager, like pagers and taskbars do.
+    fn send_message(&mut self, window: xproto::Window, name: &str, data: [u32; 5]) {
+        let event = xproto::ClientMessageEvent::new(32, window, self.atom(name), data);
+        self.client
+            .send_event(
+                false,
+                self.root,
+                xproto::EventMask::SUBSTRUCTURE_NOTIFY | xproto::EventMask::SUBSTRUCTURE_REDIRECT,
+                event,
+            )
+            .unwrap();
+        self.settle();
+    }
+
+    fn is_viewable(&self, window: xproto::Window) -> bool {
+        let attributes = self
+            .client
+            .get_window_attributes(window)
+            .unwrap()
+            .reply()
+            .unwrap();
+        attributes.map_state == xproto::MapState::VIEWABLE
+    }
+
+    fn geometry(&self, window: xproto::Window) -> (i16, i16, u16, u16) {
+        let geometry = self.client.get_geometry(window).unwrap().reply().unwrap();
+        (geometry.x, geometry.y, geometry.width, geometry.height)
+    }
+
+    /// Root's children, bottom most first.
+    fn stacking_order(&self) -> Vec<xproto::Window> {
+        self.client
+            .query_tree(self.root)
+            .unwrap()
+            .reply()
+            .unwrap()
+            .children
+    }
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn mapped_windows_are_managed_and_tiled() {
+    let mut harness = Harness::start();
+    let first = harness.map_window(None);
+    let second = harness.map_window(None);
+
+    assert!(harness.is_viewable(first));
+    assert!(harness.is_viewable(second));
+    assert_eq!(harness.root_property("_NET_CLIENT_LIST"), [first, second]);
+    assert_eq!(harness.geometry(first), (0, 0, 640, 800));
+    assert_eq!(harness.geometry(second), (640, 0, 640, 800));
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn new_windows_take_the_focus() {
+    let mut harness = Harness::start();
+    harness.map_window(None);
+    let second = harness.map_window(None);
+
+    assert_eq!(harness.root_property("_NET_ACTIVE_WINDOW"), [second]);
+    let focus = harness.client.get_input_focus().unwrap().reply().unwrap();
+    assert_eq!(focus.focus, second);
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn dialogs_float_above_tiled_windows() {
+    let mut harness = Harness::start();
+    let tiled = harness.map_window(None);
+    let dialog = harness.map_window(Some("_NET_WM_WINDOW_TYPE_DIALOG"));
+
+    let order = harness.stacking_order();
+    let position = |window| order.iter().position(|&w| w == window);
+    assert!(position(dialog) > position(tiled));
+    assert_eq!(harness.geometry(dialog).2, 100);
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn desktops_are_announced_and_switched() {
+    let mut harness = Harness::start();
+    assert_eq!(harness.root_property("_NET_NUMBER_OF_DESKTOPS"), [3]);
+    assert_eq!(
+        harness.root_property("_NET_CURRENT_DESKTOP").first(),
+        Some(&0)
+    );
+    assert_eq!(harness.root_property("_NET_SUPPORTING_WM_CHECK").len(), 1);
+    let window = harness.map_window(None);
+
+    // Ask for the second desktop like a pager would.
+    let root = harness.root;
+    harness.send_message(
+        root,
+        "_NET_CURRENT_DESKTOP",
+        [1, x11rb::CURRENT_TIME, 0, 0, 0],
+    );
+
+    assert_eq!(
+        harness.root_property("_NET_CURRENT_DESKTOP").first(),
+        Some(&1)
+    );
+    assert!(!harness.is_viewable(window));
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn destroyed_windows_are_unmanaged() {
+    let mut harness = Harness::start();
+    let kept = harness.map_window(None);
+    let destroyed = harness.map_window(None);
+    harness.client.destroy_window(destroyed).unwrap();
+    harness.settle();
+
+    assert_eq!(harness.root_property("_NET_CLIENT_LIST"), [kept]);
+    assert_eq!(harness.geometry(kept), (0, 0, 1280, 800));
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn work_area_frame_extents_and_showing_the_desktop() {
+    let mut harness = Harness::start();
+    assert_eq!(
+        harness.root_property("_NET_WORKAREA"),
+        [0, 0, 1280, 800].repeat(3)
+    );
+    let window = harness.map_window(None);
+    assert_eq!(harness.property(window, "_NET_FRAME_EXTENTS"), [0; 4]);
+
+    let root = harness.root;
+    harness.send_message(root, "_NET_SHOWING_DESKTOP", [1, 0, 0, 0, 0]);
+    assert_eq!(harness.root_property("_NET_SHOWING_DESKTOP"), [1]);
+    assert!(!harness.is_viewable(window));
+
+    harness.send_message(root, "_NET_SHOWING_DESKTOP", [0, 0, 0, 0, 0]);
+    assert_eq!(harness.root_property("_NET_SHOWING_DESKTOP"), [0]);
+    assert!(harness.is_viewable(window));
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn window_states_are_added_removed_and_toggled() {
+    let mut harness = Harness::start();
+    let window = harness.map_window(None);
+    let above = harness.atom("_NET_WM_STATE_ABOVE");
+    let sticky = harness.atom("_NET_WM_STATE_STICKY");
+
+    // Like `wmctrl -b add,above,sticky`.
+    harness.send_message(window, "_NET_WM_STATE", [1, above, sticky, 1, 0]);
+    let mut states = harness.property(window, "_NET_WM_STATE");
+    states.sort_unstable();
+    let mut expected = [above, sticky];
+    expected.sort_unstable();
+    assert_eq!(states, expected);
+
+    harness.send_message(window, "_NET_WM_STATE", [2, above, 0, 1, 0]);
+    assert_eq!(harness.property(window, "_NET_WM_STATE"), [sticky]);
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn iconified_windows_are_restored_by_activating_them() {
+    let mut harness = Harness::start();
+    let window = harness.map_window(None);
+    let hidden = harness.atom("_NET_WM_STATE_HIDDEN");
+
+    // Like `XIconifyWindow`, which asks for `IconicState`.
+    harness.send_message(window, "WM_CHANGE_STATE", [3, 0, 0, 0, 0]);
+    assert!(!harness.is_viewable(window));
+    assert_eq!(harness.property(window, "_NET_WM_STATE"), [hidden]);
+    assert_eq!(harness.property(window, "WM_STATE").first(), Some(&3));
+
+    harness.send_message(window, "_NET_ACTIVE_WINDOW", [2, 0, 0, 0, 0]);
+    assert!(harness.is_viewable(window));
+    assert!(harness.property(window, "_NET_WM_STATE").is_empty());
+}
+
+#[test]
+#[ignore = "needs Xvfb"]
+fn windows_hidden_by_older_versions_are_managed_on_startup() {
+    let mut hidden = None;
+    let mut harness = Harness::start_after(|client, root| {
+        let window = client.generate_id().unwrap();
+        client
+            .create_window(
+                x11rb::COPY_DEPTH_FROM_PARENT,
+                window,
+                root,
+                0,
+                0,
+                100,
+                100,
+                0,
+                xproto::WindowClass::INPUT_OUTPUT,
+                x11rb::COPY_FROM_PARENT,
+                &xproto::CreateWindowAux::new(),
+            )
+            .unwrap();
+        // Older versions left windows on other tags unmapped with an `IconicState` of 2.
+        let wm_state = client
+            .intern_atom(false, b"WM_STATE")
+            .unwrap()
+            .reply()
+            .unwrap()
+            .atom;
+        client
+            .change_property32(
+                xproto::PropMode::REPLACE,
+                window,
+                wm_state,
+                wm_state,
+                &[2, 0],
+            )
+            .unwrap();
+        hidden = Some(window);
+    });
+    let hidden = hidden.unwrap();
+    harness.settle();
+
+    assert_eq!(harness.root_property("_NET_CLIENT_LIST"), [hidden]);
+}
@@ -1,17 +1,29 @@
 use crate::XlibWindowHandle;
 
 use super::{DisplayEvent, XWrap};
-use leftwm_core::models::WindowHandle;
+use crate::xwrap::ICONIC_STATE;
+use leftwm_core::models::{WindowHandle, XyhwChange};
+use leftwm_core::utils::modmask_lookup::Button;
 use leftwm_core::{Command, models::WindowChange};
 use std::convert::TryFrom;
 use std::os::raw::c_long;
 
 use x11_dl::xlib;
 
+const NET_WM_STATE_REMOVE: c_long = 0;
+const NET_WM_STATE_ADD: c_long = 1;
+const NET_WM_STATE_TOGGLE: c_long = 2;
+
 pub fn from_event(
     xw: &XWrap,
     event: xlib::XClientMessageEvent,
 ) -> Option<DisplayEvent<XlibWindowHandle>> {
+    // Windows ask for their frame before they are mapped, so before we manage them.
+    if event.message_type == xw.atoms.NetRequestFrameExtents {
+        xw.set_frame_extents(event.window, xw.border_width);
+        return None;
+    }
+
     if !xw.managed_windows.contains(&event.window) && event.window != xw.get_default_root() {
         return None;
     }
@@ -59,58 +71,158 @@ pub fn from_event(
         }
     }
     if event.message_type == xw.atoms.NetActiveWindow {
-        xw.set_window_urgency(event.window, true);
-        match xw.focus_on_activation {
-            leftwm_core::models::FocusOnActivationBehaviour::DoNothing => {
-                return None;
-            }
-            leftwm_core::models::FocusOnActivationBehaviour::MarkUrgent => {
-                let handle = WindowHandle(XlibWindowHandle(event.window));
-                let mut change = WindowChange::new(handle);
-                change.urgent = Some(true);
-                return Some(DisplayEvent::WindowChange(change));
-            }
-            leftwm_core::models::FocusOnActivationBehaviour::SwitchTo => {
-                let handle = WindowHandle(XlibWindowHandle(event.window));
-                return Some(DisplayEvent::WindowTakeFocus(handle));
-            }
-        }
+        return from_active_window(xw, &event);
     }
 
-    // if the client is trying to toggle fullscreen without changing the window state, change it too
-    if event.message_type == xw.atoms.NetWMState
-        && (event.data.get_long(1) == xw.atoms.NetWMStateFullscreen as c_long
-            || event.data.get_long(2) == xw.atoms.NetWMStateFullscreen as c_long)
-    {
-        let set_fullscreen = event.data.get_long(0) == 1;
-        let toggle_fullscreen = event.data.get_long(0) == 2;
-        let mut states = xw.get_window_states_atoms(event.window);
-        // determine what to change the state to
-        let fullscreen = if toggle_fullscreen {
-            !states.contains(&xw.atoms.NetWMStateFullscreen)
-        } else {
-            set_fullscreen
-        };
-        // update the list of states
-        if fullscreen {
-            states.push(xw.atoms.NetWMStateFullscreen);
-        } else {
-            states.retain(|x| x != &xw.atoms.NetWMStateFullscreen);
+    if event.message_type == xw.atoms.WMChangeState {
+        return from_change_state(&event);
+    }
+
+    if event.message_type == xw.atoms.NetShowingDesktop {
+        let show = event.data.get_long(0) != 0;
+        return Some(DisplayEvent::SendCommand(Command::ShowDesktop(Some(show))));
+    }
+
+    if event.message_type == xw.atoms.NetCloseWindow {
+        xw.kill_window(&WindowHandle(XlibWindowHandle(event.window)));
+        return None;
+    }
+
+    if event.message_type == xw.atoms.NetRestackWindow {
+        // Only raising is supported, the window stays in its layer.
+        let detail = event.data.get_long(2);
+        if detail == c_long::from(xlib::Above) || detail == c_long::from(xlib::TopIf) {
+            let handle = WindowHandle(XlibWindowHandle(event.window));
+            return Some(DisplayEvent::RaiseWindow(handle));
         }
-        states.sort_unstable();
-        states.dedup();
-        // set the windows state
-        xw.set_window_states_atoms(event.window, &states);
+        return None;
+    }
+
+    if event.message_type == xw.atoms.NetMoveResizeWindow {
+        return Some(from_move_resize_window(&event));
+    }
+
+    if event.message_type == xw.atoms.NetWMMoveResize {
+        return from_wm_move_resize(xw, &event);
     }
 
-    // update the window states
     if event.message_type == xw.atoms.NetWMState {
+        return Some(from_wm_state(xw, &event));
+    }
+
+    None
+}
+
+fn from_active_window(
+    xw: &XWrap,
+    event: &xlib::XClientMessageEvent,
+) -> Option<DisplayEvent<XlibWindowHandle>> {
+    // Taskbars activate minimized windows to restore them.
+    if xw
+        .get_window_states_atoms(event.window)
+        .contains(&xw.atoms.NetWMStateHidden)
+    {
         let handle = WindowHandle(XlibWindowHandle(event.window));
-        let mut change = WindowChange::new(handle);
-        let states = xw.get_window_states(event.window);
-        change.states = Some(states);
-        return Some(DisplayEvent::WindowChange(change));
+        return Some(DisplayEvent::SendCommand(Command::RestoreMinimized(handle)));
     }
+    xw.set_window_urgency(event.window, true);
+    match xw.focus_on_activation {
+        leftwm_core::models::FocusOnActivationBehaviour::DoNothing => None,
+        leftwm_core::models::FocusOnActivationBehaviour::MarkUrgent => {
+            let handle = WindowHandle(XlibWindowHandle(event.window));
+            let mut change = WindowChange::new(handle);
+            change.urgent = Some(true);
+            Some(DisplayEvent::WindowChange(change))
+        }
+        leftwm_core::models::FocusOnActivationBehaviour::SwitchTo => {
+            let handle = WindowHandle(XlibWindowHandle(event.window));
+            Some(DisplayEvent::WindowTakeFocus(handle))
+        }
+    }
+}
 
+fn from_change_state(event: &xlib::XClientMessageEvent) -> Option<DisplayEvent<XlibWindowHandle>> {
+    // ICCCM only allows clients to ask for being iconified.
+    if event.data.get_long(0) == ICONIC_STATE {
+        let handle = WindowHandle(XlibWindowHandle(event.window));
+        return Some(DisplayEvent::SendCommand(Command::MinimizeWindow(Some(
+            handle,
+        ))));
+    }
     None
 }
+
+fn from_move_resize_window(event: &xlib::XClientMessageEvent) -> DisplayEvent<XlibWindowHandle> {
+    let flags = event.data.get_long(0);
+    // Bits 8 to 11 of the flags tell which of x, y, width and height are given.
+    let given = |bit: c_long, value: c_long| {
+        i32::try_from(value)
+            .ok()
+            .filter(|_| flags & (1 << bit) != 0)
+    };
+    let mut change = WindowChange::new(WindowHandle(XlibWindowHandle(event.window)));
+    change.floating = Some(XyhwChange {
+        x: given(8, event.data.get_long(1)),
+        y: given(9, event.data.get_long(2)),
+        w: given(10, event.data.get_long(3)),
+        h: given(11, event.data.get_long(4)),
+        ..XyhwChange::default()
+    });
+    DisplayEvent::WindowChange(change)
+}
+
+fn from_wm_move_resize(
+    xw: &XWrap,
+    event: &xlib::XClientMessageEvent,
+) -> Option<DisplayEvent<XlibWindowHandle>> {
+    // Directions 0 to 7 resize from an edge or corner, 8 moves. Like resizing with the mouse key,
+    // every edge and corner resizes the window from its bottom right corner. The keyboard driven
+    // directions and cancelling are not supported.
+    let button = match event.data.get_long(2) {
+        0..=7 => Button::Secondary,
+        8 => Button::Main,
+        _ => return None,
+    };
+    let handle = WindowHandle(XlibWindowHandle(event.window));
+    let x = i32::try_from(event.data.get_long(0)).ok()?;
+    let y = i32::try_from(event.data.get_long(1)).ok()?;
+    Some(DisplayEvent::MouseCombo(
+        xw.mouse_key_mask.clone(),
+        button,
+        handle,
+        x,
+        y,
+    ))
+}
+
+fn from_wm_state(xw: &XWrap, event: &xlib::XClientMessageEvent) -> DisplayEvent<XlibWindowHandle> {
+    let handle = WindowHandle(XlibWindowHandle(event.window));
+    let mut change = WindowChange::new(handle);
+    let mut states = xw.get_window_states_atoms(event.window);
+
+    // `data[0]` tells whether to remove, add or toggle the one or two states that follow.
+    for atom in [event.data.get_long(1), event.data.get_long(2)] {
+        let atom = atom as xlib::Atom;
+        if atom == 0 {
+            continue;
+        }
+        let set = match event.data.get_long(0) {
+            NET_WM_STATE_REMOVE => false,
+            NET_WM_STATE_ADD => true,
+            NET_WM_STATE_TOGGLE => !states.contains(&atom),
+            _ => continue,
+        };
+        if atom == xw.atoms.NetWMStateDemandsAttention {
+            xw.set_window_urgency(event.window, set);
+        }
+        states.retain(|&state| state != atom);
+        if set {
+            states.push(atom);
+        }
+    }
+    xw.set_window_states_atoms(event.window, &states);
+
+    change.states = Some(xw.get_window_states(event.window));
+    change.urgent = Some(xw.is_window_urgent(event.window));
+    DisplayEvent::WindowChange(change)
+}
@@ -40,7 +40,7 @@ pub fn from_event(
         }
         xlib::XA_WM_HINTS => xw
             .get_wmhints(event.window)
-            .map(|hints| build_change_hints(event, hints))
+            .map(|hints| build_change_hints(xw, event, hints))
             .map(DisplayEvent::WindowChange),
         xlib::XA_WM_NAME => Some(update_title(xw, event.window)),
         _ => {
@@ -61,6 +61,7 @@ pub fn from_event(
                 let mut change = WindowChange::new(handle);
                 let states = xw.get_window_states(event.window);
                 change.states = Some(states);
+                change.urgent = Some(xw.is_window_urgent(event.window));
                 return Some(DisplayEvent::WindowChange(change));
             }
 
@@ -70,6 +71,7 @@ pub fn from_event(
 }
 
 fn build_change_hints(
+    xw: &XWrap,
     event: xlib::XPropertyEvent,
     hints: xlib::XWMHints,
 ) -> WindowChange<XlibWindowHandle> {
@@ -77,7 +79,9 @@ fn build_change_hints(
     let mut change = WindowChange::new(handle);
 
     change.never_focus = Some(hints.flags & xlib::InputHint != 0 && hints.input == 0);
-    change.urgent = Some(hints.flags & xlib::XUrgencyHint != 0);
+    // `_NET_WM_STATE_DEMANDS_ATTENTION` keeps the window urgent without the hint.
+    change.urgent =
+        Some(hints.flags & xlib::XUrgencyHint != 0 || xw.is_window_urgent(event.window));
 
     change
 }
@@ -119,14 +123,14 @@ fn build_change_for_size_hints(
 ) -> Option<WindowChange<XlibWindowHandle>> {
     let handle = WindowHandle(XlibWindowHandle(window));
     let mut change = WindowChange::new(handle);
+    change.size_hints = Some(xw.get_size_hints(window)?);
     let hint = xw.get_hint_sizing_as_xyhw(window)?;
-    if hint.x.is_none() && hint.y.is_none() && hint.w.is_none() && hint.h.is_none() {
-        // junk hint; change change anything
-        return None;
+    // A junk hint only changes the size hints.
+    if hint.x.is_some() || hint.y.is_some() || hint.w.is_some() || hint.h.is_some() {
+        let mut xyhw = Xyhw::default();
+        hint.update(&mut xyhw);
+        change.requested = Some(xyhw);
     }
-    let mut xyhw = Xyhw::default();
-    hint.update(&mut xyhw);
-    change.requested = Some(xyhw);
     Some(change)
 }
 
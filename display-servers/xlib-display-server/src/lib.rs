@@ -19,9 +19,10 @@ use event_translate::XEvent;
 use futures::prelude::*;
 use leftwm_core::config::Config;
 use leftwm_core::models::{
-    Handle, Mode, Screen, TagId, Window, WindowHandle, WindowState, Workspace,
+    Handle, Mode, Screen, TagId, Window, WindowHandle, WindowState, Workspace, Xyhw,
 };
 use leftwm_core::utils;
+use leftwm_core::utils::screen_tracker::ScreenTracker;
 use leftwm_core::{DisplayAction, DisplayEvent, DisplayServer};
 use std::pin::Pin;
 
@@ -35,6 +36,7 @@ pub struct XlibDisplayServer {
     xw: XWrap,
     root: xlib::Window,
     initial_events: Vec<DisplayEvent<XlibWindowHandle>>,
+    screens: ScreenTracker<XlibWindowHandle>,
 }
 
 impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
@@ -45,17 +47,15 @@ impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
         wrap.init(); // setup events masks
 
         let root = wrap.get_default_root();
-        let instance = Self {
+        let mut instance = Self {
             xw: wrap,
             root,
             initial_events: Vec::new(),
+            screens: ScreenTracker::new(config),
         };
-        let initial_events = instance.initial_events(config);
+        instance.initial_events = instance.initial_events();
 
-        Self {
-            initial_events,
-            ..instance
-        }
+        instance
     }
 
     fn reload_config(
@@ -66,6 +66,7 @@ impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
     ) {
         self.xw.load_config(config);
         self.xw.update_colors(focused, windows);
+        self.screens.load_config(config);
     }
 
     fn update_windows(&self, windows: Vec<&Window<XlibWindowHandle>>) {
@@ -83,9 +84,14 @@ impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
     fn get_next_events(&mut self) -> Vec<DisplayEvent<XlibWindowHandle>> {
         let mut events = std::mem::take(&mut self.initial_events);
 
+        let mut screens_changed = false;
         let events_in_queue = self.xw.queue_len();
         for _ in 0..events_in_queue {
-            let xlib_event = self.xw.get_next_event();
+            let mut xlib_event = self.xw.get_next_event();
+            if self.xw.is_screen_change_event(&mut xlib_event) {
+                screens_changed = true;
+                continue;
+            }
             let event = XEvent(&mut self.xw, xlib_event).into();
             if let Some(e) = event {
                 tracing::trace!("DisplayEvent: {:?}", e);
@@ -93,6 +99,11 @@ impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
             }
         }
 
+        // A single change of monitors comes as a burst of RandR events.
+        if screens_changed {
+            events.append(&mut self.screen_events());
+        }
+
         for event in &events {
             if let DisplayEvent::WindowDestroy(WindowHandle(XlibWindowHandle(w))) = event {
                 self.xw.force_unmapped(*w);
@@ -122,6 +133,9 @@ impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
             DisplayAction::ReadyToMoveWindow(h) => from_ready_to_move_window(xw, h),
             DisplayAction::ReadyToResizeWindow(h) => from_ready_to_resize_window(xw, h),
             DisplayAction::SetCurrentTags(t) => from_set_current_tags(xw, t),
+            DisplayAction::SetDesktopNames(l) => from_set_desktop_names(xw, l),
+            DisplayAction::SetWorkArea(a) => from_set_work_area(xw, a),
+            DisplayAction::SetShowingDesktop(s) => from_set_showing_desktop(xw, s),
             DisplayAction::SetWindowTag(h, t) => from_set_window_tag(xw, h, t),
             DisplayAction::ConfigureXlibWindow(w) => from_configure_xlib_window(xw, &w),
 
@@ -159,53 +173,8 @@ impl DisplayServer<XlibWindowHandle> for XlibDisplayServer {
 
 impl XlibDisplayServer {
     /// Return a vec of events for setting up state of WM.
-    fn initial_events(&self, config: &impl Config) -> Vec<DisplayEvent<XlibWindowHandle>> {
-        let mut events = vec![];
-        if let Some(workspaces) = config.workspaces() {
-            let screens = self.xw.get_screens();
-            for (i, wsc) in workspaces.iter().enumerate() {
-                let mut screen = Screen::from(wsc);
-                screen.root = WindowHandle(XlibWindowHandle(self.root));
-                // If there is a screen corresponding to the given output, create the workspace
-                match screens.iter().find(|i| i.output == wsc.output) {
-                    Some(output_match) => {
-                        if wsc.relative.unwrap_or(false) {
-                            screen.bbox.add(output_match.bbox);
-                        }
-                        screen.id = Some(i + 1);
-                    }
-                    None => continue,
-                }
-                let e = DisplayEvent::ScreenCreate(screen);
-                events.push(e);
-            }
-
-            let auto_derive_workspaces: bool = if config.auto_derive_workspaces() {
-                true
-            } else if events.is_empty() {
-                tracing::warn!(
-                    "No Workspace in Workspace config matches connected screen. Falling back to \"auto_derive_workspaces: true\"."
-                );
-                true
-            } else {
-                false
-            };
-
-            let mut next_id = workspaces.len() + 1;
-
-            // If there is no hardcoded workspace layout, add every screen not mentioned in the config.
-            if auto_derive_workspaces {
-                screens
-                    .iter()
-                    .filter(|screen| !workspaces.iter().any(|wsc| wsc.output == screen.output))
-                    .for_each(|screen| {
-                        let mut s = screen.clone();
-                        s.id = Some(next_id);
-                        next_id += 1;
-                        events.push(DisplayEvent::ScreenCreate(s));
-                    });
-            }
-        }
+    fn initial_events(&mut self) -> Vec<DisplayEvent<XlibWindowHandle>> {
+        let mut events = self.screen_events();
 
         // Tell manager about existing windows.
         events.append(&mut self.find_all_windows());
@@ -213,6 +182,15 @@ impl XlibDisplayServer {
         events
     }
 
+    /// Creates, updates or destroys screens to match the connected outputs.
+    fn screen_events(&mut self) -> Vec<DisplayEvent<XlibWindowHandle>> {
+        let mut outputs = self.xw.get_screens();
+        for output in &mut outputs {
+            output.root = WindowHandle(XlibWindowHandle(self.root));
+        }
+        self.screens.update(&outputs)
+    }
+
     fn find_all_windows(&self) -> Vec<DisplayEvent<XlibWindowHandle>> {
         let mut all: Vec<DisplayEvent<XlibWindowHandle>> = Vec::new();
         match self.xw.get_all_windows() {
@@ -384,6 +362,33 @@ fn from_set_current_tags(
     None
 }
 
+fn from_set_desktop_names(
+    xw: &mut XWrap,
+    labels: Vec<String>,
+) -> Option<DisplayEvent<XlibWindowHandle>> {
+    xw.tag_labels = labels;
+    xw.set_desktop_names();
+    None
+}
+
+fn from_set_work_area(xw: &mut XWrap, area: Xyhw) -> Option<DisplayEvent<XlibWindowHandle>> {
+    xw.set_work_area([
+        area.x() as u32,
+        area.y() as u32,
+        area.w() as u32,
+        area.h() as u32,
+    ]);
+    None
+}
+
+fn from_set_showing_desktop(
+    xw: &mut XWrap,
+    showing: bool,
+) -> Option<DisplayEvent<XlibWindowHandle>> {
+    xw.set_desktop_prop(&[u32::from(showing)], xw.atoms.NetShowingDesktop);
+    None
+}
+
 fn from_set_window_tag(
     xw: &mut XWrap,
     handle: WindowHandle<XlibWindowHandle>,
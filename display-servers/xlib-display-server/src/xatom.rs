@@ -10,6 +10,7 @@ pub struct XAtom {
     pub WMProtocols: xlib::Atom,
     pub WMDelete: xlib::Atom,
     pub WMState: xlib::Atom,
+    pub WMChangeState: xlib::Atom,
     pub WMClass: xlib::Atom,
     pub WMTakeFocus: xlib::Atom,
     pub NetActiveWindow: xlib::Atom,
@@ -67,6 +68,14 @@ pub struct XAtom {
     pub NetWMDesktop: xlib::Atom,
     pub NetWMStrutPartial: xlib::Atom, // net version - Reserve Screen Space
     pub NetWMStrut: xlib::Atom,        // old version
+    pub NetWorkarea: xlib::Atom,
+    pub NetFrameExtents: xlib::Atom,
+    pub NetRequestFrameExtents: xlib::Atom,
+    pub NetCloseWindow: xlib::Atom,
+    pub NetMoveResizeWindow: xlib::Atom,
+    pub NetWMMoveResize: xlib::Atom,
+    pub NetShowingDesktop: xlib::Atom,
+    pub NetRestackWindow: xlib::Atom,
 
     pub UTF8String: xlib::Atom,
 }
@@ -125,6 +134,14 @@ impl XAtom {
             self.NetWMDesktop,
             self.NetWMStrutPartial,
             self.NetWMStrut,
+            self.NetWorkarea,
+            self.NetFrameExtents,
+            self.NetRequestFrameExtents,
+            self.NetCloseWindow,
+            self.NetMoveResizeWindow,
+            self.NetWMMoveResize,
+            self.NetShowingDesktop,
+            self.NetRestackWindow,
         ]
     }
 
@@ -133,6 +150,7 @@ impl XAtom {
             a if a == self.WMProtocols => "WM_PROTOCOLS",
             a if a == self.WMDelete => "WM_DELETE_WINDOW",
             a if a == self.WMState => "WM_STATE",
+            a if a == self.WMChangeState => "WM_CHANGE_STATE",
             a if a == self.WMClass => "WM_CLASS",
             a if a == self.WMTakeFocus => "WM_TAKE_FOCUS",
             a if a == self.NetActiveWindow => "_NET_ACTIVE_WINDOW",
@@ -176,6 +194,14 @@ impl XAtom {
             a if a == self.NetWMDesktop => "_NET_WM_DESKTOP",
             a if a == self.NetWMStrutPartial => "_NET_WM_STRUT_PARTIAL",
             a if a == self.NetWMStrut => "_NET_WM_STRUT",
+            a if a == self.NetWorkarea => "_NET_WORKAREA",
+            a if a == self.NetFrameExtents => "_NET_FRAME_EXTENTS",
+            a if a == self.NetRequestFrameExtents => "_NET_REQUEST_FRAME_EXTENTS",
+            a if a == self.NetCloseWindow => "_NET_CLOSE_WINDOW",
+            a if a == self.NetMoveResizeWindow => "_NET_MOVERESIZE_WINDOW",
+            a if a == self.NetWMMoveResize => "_NET_WM_MOVERESIZE",
+            a if a == self.NetShowingDesktop => "_NET_SHOWING_DESKTOP",
+            a if a == self.NetRestackWindow => "_NET_RESTACK_WINDOW",
 
             a if a == self.UTF8String => "UTF8_STRING",
             _ => "(UNKNOWN)",
@@ -187,6 +213,7 @@ impl XAtom {
             WMProtocols: from(xlib, dpy, "WM_PROTOCOLS"),
             WMDelete: from(xlib, dpy, "WM_DELETE_WINDOW"),
             WMState: from(xlib, dpy, "WM_STATE"),
+            WMChangeState: from(xlib, dpy, "WM_CHANGE_STATE"),
             WMClass: from(xlib, dpy, "WM_CLASS"),
             WMTakeFocus: from(xlib, dpy, "WM_TAKE_FOCUS"),
             NetActiveWindow: from(xlib, dpy, "_NET_ACTIVE_WINDOW"),
@@ -244,6 +271,14 @@ impl XAtom {
             NetWMDesktop: from(xlib, dpy, "_NET_WM_DESKTOP"),
             NetWMStrutPartial: from(xlib, dpy, "_NET_WM_STRUT_PARTIAL"),
             NetWMStrut: from(xlib, dpy, "_NET_WM_STRUT"),
+            NetWorkarea: from(xlib, dpy, "_NET_WORKAREA"),
+            NetFrameExtents: from(xlib, dpy, "_NET_FRAME_EXTENTS"),
+            NetRequestFrameExtents: from(xlib, dpy, "_NET_REQUEST_FRAME_EXTENTS"),
+            NetCloseWindow: from(xlib, dpy, "_NET_CLOSE_WINDOW"),
+            NetMoveResizeWindow: from(xlib, dpy, "_NET_MOVERESIZE_WINDOW"),
+            NetWMMoveResize: from(xlib, dpy, "_NET_WM_MOVERESIZE"),
+            NetShowingDesktop: from(xlib, dpy, "_NET_SHOWING_DESKTOP"),
+            NetRestackWindow: from(xlib, dpy, "_NET_RESTACK_WINDOW"),
 
             UTF8String: from(xlib, dpy, "UTF8_STRING"),
         }
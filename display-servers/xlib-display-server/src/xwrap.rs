@@ -22,7 +22,7 @@ use tokio::sync::{Notify, oneshot};
 use tokio::time::Duration;
 
 use x11_dl::xlib;
-use x11_dl::xrandr::Xrandr;
+use x11_dl::xrandr::{self, Xrandr};
 
 mod getters;
 mod mouse;
@@ -32,7 +32,9 @@ mod window;
 type WindowStateConst = c_long;
 pub const WITHDRAWN_STATE: WindowStateConst = 0;
 pub const NORMAL_STATE: WindowStateConst = 1;
-pub const ICONIC_STATE: WindowStateConst = 2;
+pub const ICONIC_STATE: WindowStateConst = 3;
+/// The `IconicState` older versions wrote on windows hidden on other tags.
+const LEGACY_ICONIC_STATE: WindowStateConst = 2;
 const MAX_PROPERTY_VALUE_LEN: c_long = 4096;
 
 pub const ROOT_EVENT_MASK: c_long = xlib::SubstructureRedirectMask
@@ -87,6 +89,7 @@ pub struct Colors {
     normal: c_ulong,
     floating: c_ulong,
     active: c_ulong,
+    urgent: c_ulong,
     background: c_ulong,
 }
 
@@ -118,6 +121,10 @@ pub struct XWrap {
     pub motion_event_limiter: c_ulong,
     pub refresh_rate: c_short,
     pub window_hiding_strategy: WindowHidingStrategy,
+    pub border_width: i32,
+    work_area: [u32; 4],
+    /// First `RandR` event code, if the extension is available.
+    randr_event_base: Option<c_int>,
 }
 
 impl Default for XWrap {
@@ -186,6 +193,7 @@ impl XWrap {
             normal: 0,
             floating: 0,
             active: 0,
+            urgent: 0,
             background: 0,
         };
 
@@ -243,6 +251,9 @@ impl XWrap {
             motion_event_limiter: 0,
             refresh_rate,
             window_hiding_strategy: WindowHidingStrategy::default(),
+            border_width: 0,
+            work_area: [0; 4],
+            randr_event_base: None,
         };
 
         // Check that another WM is not running.
@@ -273,9 +284,11 @@ impl XWrap {
             normal: self.get_color(config.default_border_color()),
             floating: self.get_color(config.floating_border_color()),
             active: self.get_color(config.focused_border_color()),
+            urgent: self.get_color(config.urgent_border_color()),
             background: self.get_color(config.background_color()),
         };
         self.window_hiding_strategy = config.window_hiding_strategy();
+        self.border_width = config.border_width();
     }
 
     /// Initialize the xwrapper.
@@ -300,6 +313,25 @@ impl XWrap {
 
         self.subscribe_to_event(root, ROOT_EVENT_MASK);
 
+        // Get notified when monitors are plugged in, unplugged or rearranged.
+        if let Ok(xrandr) = Xrandr::open() {
+            let (mut event_base, mut error_base) = (0, 0);
+            unsafe {
+                if (xrandr.XRRQueryExtension)(
+                    self.display,
+                    &raw mut event_base,
+                    &raw mut error_base,
+                ) != 0
+                {
+                    let mask = xrandr::RRScreenChangeNotifyMask
+                        | xrandr::RRCrtcChangeNotifyMask
+                        | xrandr::RROutputChangeNotifyMask;
+                    (xrandr.XRRSelectInput)(self.display, root, mask);
+                    self.randr_event_base = Some(event_base);
+                }
+            }
+        }
+
         // EWMH compliance.
         unsafe {
             let supported: Vec<c_long> = self
@@ -321,20 +353,42 @@ impl XWrap {
     }
 
     /// EWMH support used for bars such as polybar.
+    pub fn init_desktops_hints(&self) {
+        self.set_desktop_names();
+        // Set a current desktop.
+        let data = vec![0_u32, xlib::CurrentTime as u32];
+        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
+
+        // Set the WM NAME.
+        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
+
+        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
+
+        self.set_desktop_prop_c_ulong(
+            self.root as c_ulong,
+            self.atoms.NetSupportingWmCheck,
+            xlib::XA_WINDOW,
+        );
+
+        // Set a viewport.
+        let data = vec![0_u32, 0_u32];
+        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
+
+        self.set_desktop_prop(&[0], self.atoms.NetShowingDesktop);
+    }
+
+    /// Sets the number and names of the desktops from the tag labels.
     ///  # Panics
     ///
     ///  Panics if a new Cstring cannot be formed
     // `Xutf8TextListToTextProperty`: https://linux.die.net/man/3/xutf8textlisttotextproperty
     // `XSetTextProperty`: https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XSetTextProperty.html
-    pub fn init_desktops_hints(&self) {
+    pub fn set_desktop_names(&self) {
         let tag_labels = &self.tag_labels;
         let tag_length = tag_labels.len();
         // Set the number of desktop.
         let data = vec![tag_length as u32];
         self.set_desktop_prop(&data, self.atoms.NetNumberOfDesktops);
-        // Set a current desktop.
-        let data = vec![0_u32, xlib::CurrentTime as u32];
-        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
         // Set desktop names.
         let mut text: xlib::XTextProperty = unsafe { std::mem::zeroed() };
         unsafe {
@@ -359,20 +413,14 @@ impl XWrap {
             );
         }
 
-        // Set the WM NAME.
-        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
-
-        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
-
-        self.set_desktop_prop_c_ulong(
-            self.root as c_ulong,
-            self.atoms.NetSupportingWmCheck,
-            xlib::XA_WINDOW,
-        );
+        // There is a work area for every desktop.
+        self.set_desktop_prop(&self.work_area.repeat(tag_length), self.atoms.NetWorkarea);
+    }
 
-        // Set a viewport.
-        let data = vec![0_u32, 0_u32];
-        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
+    /// Sets the work area of all desktops, the part of the screens not covered by docks.
+    pub fn set_work_area(&mut self, area: [u32; 4]) {
+        self.work_area = area;
+        self.set_desktop_prop(&area.repeat(self.tag_labels.len()), self.atoms.NetWorkarea);
     }
 
     /// Send a xevent atom for a window to X.
@@ -429,16 +477,25 @@ impl XWrap {
             let WindowHandle(XlibWindowHandle(handle)) = window.handle;
             let color: c_ulong = if focused == Some(window.handle) {
                 self.colors.active
-            } else if window.floating() {
-                self.colors.floating
             } else {
-                self.colors.normal
+                self.unfocused_border_color(window)
             };
             self.set_window_border_color(handle, color);
         }
         self.set_background_color(self.colors.background);
     }
 
+    /// The border color of a window which is not focused.
+    fn unfocused_border_color(&self, window: &Window<XlibWindowHandle>) -> c_ulong {
+        if window.urgent {
+            self.colors.urgent
+        } else if window.floating() {
+            self.colors.floating
+        } else {
+            self.colors.normal
+        }
+    }
+
     /// Sets the mode within our xwrapper.
     pub fn set_mode(&mut self, mode: Mode<XlibWindowHandle>) {
         match mode {
@@ -503,4 +560,23 @@ impl XWrap {
     pub fn queue_len(&self) -> i32 {
         unsafe { (self.xlib.XPending)(self.display) }
     }
+
+    /// Returns whether `event` reports a change of monitors, and lets xlib know about the new
+    /// size of the screen.
+    // `XRRUpdateConfiguration`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    pub fn is_screen_change_event(&self, event: &mut xlib::XEvent) -> bool {
+        let Some(event_base) = self.randr_event_base else {
+            return false;
+        };
+        match event.get_type() - event_base {
+            xrandr::RRScreenChangeNotify => {
+                if let Ok(xrandr) = Xrandr::open() {
+                    unsafe { (xrandr.XRRUpdateConfiguration)(event) };
+                }
+                true
+            }
+            xrandr::RRNotify => true,
+            _ => false,
+        }
+    }
 }
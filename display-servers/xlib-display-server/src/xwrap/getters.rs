@@ -1,7 +1,10 @@
 //! `XWrap` getters.
-use super::{MAX_PROPERTY_VALUE_LEN, MOUSEMASK, Screen, WindowHandle, XlibError};
+use super::{
+    ICONIC_STATE, LEGACY_ICONIC_STATE, MAX_PROPERTY_VALUE_LEN, MOUSEMASK, Screen, WindowHandle,
+    XlibError,
+};
 use crate::{XWrap, XlibWindowHandle};
-use leftwm_core::models::{BBox, DockArea, WindowState, WindowType, XyhwChange};
+use leftwm_core::models::{BBox, DockArea, SizeHints, WindowState, WindowType, XyhwChange};
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int, c_long, c_uchar, c_uint, c_ulong};
 use std::slice;
@@ -187,6 +190,21 @@ impl XWrap {
         None
     }
 
+    /// Returns the `WM_NORMAL_HINTS` of a window which tiled windows keep to.
+    #[must_use]
+    pub fn get_size_hints(&self, window: xlib::Window) -> Option<SizeHints> {
+        let size = self.get_hint_sizing(window)?;
+        let flagged = |flag, value| (size.flags & flag != 0).then_some(value);
+        Some(SizeHints {
+            min: flagged(xlib::PMinSize, (size.min_width, size.min_height)),
+            max: flagged(xlib::PMaxSize, (size.max_width, size.max_height)),
+            base: flagged(xlib::PBaseSize, (size.base_width, size.base_height)),
+            increment: flagged(xlib::PResizeInc, (size.width_inc, size.height_inc)),
+            min_aspect: flagged(xlib::PAspect, (size.min_aspect.x, size.min_aspect.y)),
+            max_aspect: flagged(xlib::PAspect, (size.max_aspect.x, size.max_aspect.y)),
+        })
+    }
+
     /// Returns the next `Xevent` that matches the mask of the xserver.
     // `XMaskEvent`: https://tronche.com/gui/x/xlib/event-handling/manipulating-event-queue/XMaskEvent.html
     #[must_use]
@@ -472,23 +490,24 @@ impl XWrap {
 
         let mut window_states: Vec<WindowState> = window_states_atoms
             .iter()
-            .map(|a| match a {
-                x if x == &self.atoms.NetWMStateModal => WindowState::Modal,
-                x if x == &self.atoms.NetWMStateSticky => WindowState::Sticky,
+            .filter_map(|a| match a {
+                x if x == &self.atoms.NetWMStateModal => Some(WindowState::Modal),
+                x if x == &self.atoms.NetWMStateSticky => Some(WindowState::Sticky),
                 x if x == &self.atoms.NetWMStateMaximizedVert && !maximized => {
-                    WindowState::MaximizedVert
+                    Some(WindowState::MaximizedVert)
                 }
                 x if x == &self.atoms.NetWMStateMaximizedHorz && !maximized => {
-                    WindowState::MaximizedHorz
+                    Some(WindowState::MaximizedHorz)
                 }
-                x if x == &self.atoms.NetWMStateShaded => WindowState::Shaded,
-                x if x == &self.atoms.NetWMStateSkipTaskbar => WindowState::SkipTaskbar,
-                x if x == &self.atoms.NetWMStateSkipPager => WindowState::SkipPager,
-                x if x == &self.atoms.NetWMStateHidden => WindowState::Hidden,
-                x if x == &self.atoms.NetWMStateFullscreen => WindowState::Fullscreen,
-                x if x == &self.atoms.NetWMStateAbove => WindowState::Above,
-                x if x == &self.atoms.NetWMStateBelow => WindowState::Below,
-                _ => WindowState::Modal,
+                x if x == &self.atoms.NetWMStateShaded => Some(WindowState::Shaded),
+                x if x == &self.atoms.NetWMStateSkipTaskbar => Some(WindowState::SkipTaskbar),
+                x if x == &self.atoms.NetWMStateSkipPager => Some(WindowState::SkipPager),
+                x if x == &self.atoms.NetWMStateHidden => Some(WindowState::Hidden),
+                x if x == &self.atoms.NetWMStateFullscreen => Some(WindowState::Fullscreen),
+                x if x == &self.atoms.NetWMStateAbove => Some(WindowState::Above),
+                x if x == &self.atoms.NetWMStateBelow => Some(WindowState::Below),
+                // Both maximized states were merged into `Maximized`, urgency is not a state.
+                _ => None,
             })
             .collect();
 
@@ -592,7 +611,20 @@ impl XWrap {
         }
     }
 
-    /// Returns the `WM_STATE` of a window.
+    /// Whether a window asks for attention, with the urgency hint of its `WM_HINTS` or with
+    /// `_NET_WM_STATE_DEMANDS_ATTENTION`.
+    #[must_use]
+    pub fn is_window_urgent(&self, window: xlib::Window) -> bool {
+        let hinted = self
+            .get_wmhints(window)
+            .is_some_and(|hints| hints.flags & xlib::XUrgencyHint != 0);
+        hinted
+            || self
+                .get_window_states_atoms(window)
+                .contains(&self.atoms.NetWMStateDemandsAttention)
+    }
+
+    /// Returns the `WM_STATE` of a window, with the legacy `IconicState` of 2 read as 3.
     #[must_use]
     pub fn get_wm_state(&self, window: xlib::Window) -> Option<c_long> {
         let (prop_return, nitems_return) = self
@@ -602,7 +634,12 @@ impl XWrap {
             return None;
         }
         #[allow(clippy::cast_ptr_alignment)]
-        Some(unsafe { *prop_return.cast::<c_long>() })
+        let state = unsafe { *prop_return.cast::<c_long>() };
+        Some(if state == LEGACY_ICONIC_STATE {
+            ICONIC_STATE
+        } else {
+            state
+        })
     }
 
     /// Returns the name of a `XAtom`.
@@ -102,6 +102,12 @@ impl XWrap {
         self.replace_property_long(self.root, atom, xlib::XA_CARDINAL, &x_data);
     }
 
+    /// Sets the border a window is framed with on each side.
+    pub fn set_frame_extents(&self, window: xlib::Window, border: i32) {
+        let data = [c_long::from(border); 4];
+        self.replace_property_long(window, self.atoms.NetFrameExtents, xlib::XA_CARDINAL, &data);
+    }
+
     /// Sets a desktop property with type `c_ulong`.
     pub fn set_desktop_prop_c_ulong(&self, value: c_ulong, atom: c_ulong, r#type: c_ulong) {
         let data = vec![value as c_long];
@@ -33,6 +33,7 @@ impl XWrap {
         let mut can_resize = actions.contains(&self.atoms.NetWMActionResize);
         let trans = self.get_transient_for(window);
         let sizing_hint = self.get_hint_sizing_as_xyhw(window);
+        let size_hints = self.get_size_hints(window);
         let wm_hint = self.get_wmhints(window);
 
         // Build the new window, and fill in info about it.
@@ -78,13 +79,12 @@ impl XWrap {
             hint.update(&mut requested);
         }
         w.requested = Some(requested);
+        w.size_hints = size_hints;
         w.can_resize = can_resize;
         if let Some(hint) = wm_hint {
             w.never_focus = hint.flags & xlib::InputHint != 0 && hint.input == 0;
         }
-        if let Some(hint) = wm_hint {
-            w.urgent = hint.flags & xlib::XUrgencyHint != 0;
-        }
+        w.urgent = self.is_window_urgent(window);
         // Is this needed? Made it so it doens't overwrite prior sizing.
         if w.floating()
             && sizing_hint.is_none()
@@ -199,6 +199,10 @@ impl XWrap {
                 xlib::CWX | xlib::CWY | xlib::CWWidth | xlib::CWHeight | xlib::CWBorderWidth;
             self.set_window_config(handle, changes, u32::from(unlock));
             self.configure_window(window);
+            self.set_frame_extents(handle, window.border());
+            if handle != self.focused_window {
+                self.set_window_border_color(handle, self.unfocused_border_color(window));
+            }
         }
         let Some(state) = self.get_wm_state(handle) else {
             return;
@@ -310,12 +314,7 @@ impl XWrap {
         // Update previous window.
         if let Some(previous) = previous {
             let WindowHandle(XlibWindowHandle(previous_handle)) = previous.handle;
-            let color = if previous.floating() {
-                self.colors.floating
-            } else {
-                self.colors.normal
-            };
-            self.set_window_border_color(previous_handle, color);
+            self.set_window_border_color(previous_handle, self.unfocused_border_color(previous));
             // Open up button1 clicking on the previously focused window.
             if self.focus_behaviour.is_clickto() {
                 self.grab_mouse_clicks(previous_handle, false);
@@ -324,6 +323,7 @@ impl XWrap {
         self.focused_window = handle;
         self.grab_mouse_clicks(handle, true);
         self.set_window_urgency(handle, false);
+        self.set_state(window.handle, false, self.atoms.NetWMStateDemandsAttention);
         self.set_window_border_color(handle, self.colors.active);
         self.focus(handle, window.never_focus);
         self.sync();
@@ -1,5 +1,5 @@
 pub use crate::handlers::command_handler::ReleaseScratchPadOption;
-use crate::models::{Handle, ScratchPadName, TagId, WindowHandle};
+use crate::models::{Criteria, Handle, ScratchPadName, TagId, WindowHandle};
 use leftwm_layouts::geometry::Direction as FocusDirection;
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +32,14 @@ pub enum Command<H: Handle> {
     ToggleMaximized,
     ToggleSticky,
     ToggleAbove,
+    /// Hides all windows to show the desktop, or shows them again. `None` toggles.
+    ShowDesktop(Option<bool>),
+    /// Hides a window, the focused one if `None`, until it is restored.
+    MinimizeWindow(#[serde(bound = "")] Option<WindowHandle<H>>),
+    /// Restores the window which was minimized last.
+    RestoreLastMinimized,
+    /// Restores a minimized window, going to its tag if it is not in view.
+    RestoreMinimized(#[serde(bound = "")] WindowHandle<H>),
     GoToTag {
         tag: TagId,
         swap: bool,
@@ -62,6 +70,8 @@ pub enum Command<H: Handle> {
         swap: bool,
     },
     FocusWindowAt(FocusDirection),
+    /// Focuses the window which has been urgent for the longest time, on any tag.
+    FocusUrgent,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
     SendWindowToTag {
@@ -89,8 +99,59 @@ pub enum Command<H: Handle> {
     IncreaseMainCount(),
     DecreaseMainCount(),
     SetMarginMultiplier(f32),
+    /// Makes the next window split the focused one side by side, in the `Manual` layout.
+    SplitHorizontal,
+    /// Makes the next window split the focused one top to bottom, in the `Manual` layout.
+    SplitVertical,
+    /// Flips the split holding the focused window between side by side and top to bottom.
+    ToggleSplitOrientation,
+    /// Grows the focused window in its split by a percentage, shrinks it if negative.
+    ResizeSplit(i32),
+    /// Focuses the column to the left of the focused window, in the `Scrolling` layout.
+    ScrollLeft,
+    /// Focuses the column to the right of the focused window, in the `Scrolling` layout.
+    ScrollRight,
+    /// Moves the focused window to the bottom of the column to the left of it.
+    ConsumeIntoColumn,
+    /// Moves the focused window out of its column into a new one to the right of it.
+    ExpelFromColumn,
+    /// Sets the width of the column of the focused window to a percentage of the workspace.
+    SetColumnWidth(i32),
     SendWorkspaceToTag(usize, usize),
     CloseAllOtherWindows,
+    AddTag(String),
+    RemoveTag(Option<TagId>),
+    RenameTag {
+        tag: TagId,
+        label: String,
+    },
+    MoveTag {
+        tag: TagId,
+        position: TagId,
+    },
+    ToggleTagView(TagId),
+    ToggleWindowTag(TagId),
+    MarkWindow(String),
+    UnmarkWindow(Option<String>),
+    FocusMark(String),
+    SwapWithMark(String),
+    SendMarkToTag {
+        mark: String,
+        tag: TagId,
+    },
+    GetWindows,
+    GetWorkspaces,
+    GetTags,
+    GetLayouts,
+    GetScratchpads,
+    GetFocused,
+    GetConfig,
+    /// Runs `command` for every window matching `criteria`, as if it was focused.
+    WithCriteria {
+        criteria: Criteria,
+        #[serde(bound = "")]
+        command: Box<Command<H>>,
+    },
     Other(String),
 }
 
@@ -1,4 +1,8 @@
 mod insert_behavior;
+mod output_profile;
+#[cfg(any(test, feature = "test-utils"))]
+mod test_config;
+mod window_count_layout;
 mod window_hiding_strategy;
 mod workspace_config;
 
@@ -8,8 +12,14 @@ pub use crate::models::ScratchPad;
 pub use crate::models::{FocusBehaviour, Gutter, Margins, Size};
 use crate::models::{FocusOnActivationBehaviour, Handle, Manager, Window, WindowType};
 use crate::state::State;
+use crate::utils::command_socket::CommandError;
 pub use insert_behavior::InsertBehavior;
 use leftwm_layouts::Layout;
+pub use output_profile::OutputProfile;
+use std::path::PathBuf;
+#[cfg(any(test, feature = "test-utils"))]
+pub use test_config::TestConfig;
+pub use window_count_layout::WindowCountLayout;
 pub use window_hiding_strategy::WindowHidingStrategy;
 pub use workspace_config::Workspace;
 
@@ -18,6 +28,9 @@ pub trait Config {
 
     fn workspaces(&self) -> Option<Vec<Workspace>>;
 
+    /// Workspaces to use instead of `workspaces` while the outputs of a profile are connected.
+    fn output_profiles(&self) -> Vec<OutputProfile>;
+
     fn focus_behaviour(&self) -> FocusBehaviour;
 
     fn focus_on_activation(&self) -> FocusOnActivationBehaviour;
@@ -32,16 +45,26 @@ pub trait Config {
 
     fn layout_mode(&self) -> LayoutMode;
 
+    /// Layouts switched to by the number of tiled windows, the first one matching is used.
+    fn window_count_layouts(&self) -> Vec<WindowCountLayout> {
+        vec![]
+    }
+
     fn insert_behavior(&self) -> InsertBehavior;
 
     fn single_window_border(&self) -> bool;
 
     fn focus_new_windows(&self) -> bool;
 
+    /// Handles commands not known to the core, returns whether the display needs a refresh.
+    ///
+    /// # Errors
+    ///
+    /// The error is sent back to the client that issued the command.
     fn command_handler<H: Handle, SERVER>(
         command: &str,
         manager: &mut Manager<H, Self, SERVER>,
-    ) -> bool
+    ) -> Result<bool, CommandError>
     where
         SERVER: DisplayServer<H>,
         Self: Sized;
@@ -56,6 +79,8 @@ pub trait Config {
     fn default_border_color(&self) -> String;
     fn floating_border_color(&self) -> String;
     fn focused_border_color(&self) -> String;
+    /// Border color of windows asking for attention.
+    fn urgent_border_color(&self) -> String;
     fn background_color(&self) -> String;
     fn on_new_window_cmd(&self) -> Option<String>;
     fn get_list_of_gutters(&self) -> Vec<Gutter>;
@@ -66,6 +91,21 @@ pub trait Config {
     fn create_follows_cursor(&self) -> bool;
     fn reposition_cursor_on_resize(&self) -> bool;
     fn window_hiding_strategy(&self) -> WindowHidingStrategy;
+    /// Whether new windows swallow the terminal they were launched from, unless a window rule
+    /// says otherwise. Off unless implemented.
+    fn swallow_terminals(&self) -> bool {
+        false
+    }
+
+    /// Where to record the display events and commands to, if at all.
+    fn record_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// The config as returned to `GetConfig` queries, `null` unless implemented.
+    fn as_json(&self) -> serde_json::Value {
+        serde_json::Value::Null
+    }
 
     /// Attempt to write current state to a file.
     ///
@@ -98,168 +138,21 @@ pub trait Config {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    pub(crate) use super::TestConfig;
     use super::*;
     use crate::models::MockHandle;
     use crate::models::Screen;
     use crate::models::Window;
     use crate::models::WindowHandle;
 
-    #[allow(clippy::module_name_repetitions)]
-    #[derive(Default)]
-    pub struct TestConfig {
-        pub tags: Vec<String>,
-        pub layouts: Vec<String>,
-        pub layout_definitions: Vec<Layout>,
-        pub workspaces: Option<Vec<Workspace>>,
-        pub insert_behavior: InsertBehavior,
-        pub border_width: i32,
-        pub single_window_border: bool,
-    }
-
-    impl Config for TestConfig {
-        fn create_list_of_tag_labels(&self) -> Vec<String> {
-            self.tags.clone()
-        }
-        fn workspaces(&self) -> Option<Vec<Workspace>> {
-            self.workspaces.clone()
-        }
-        fn focus_behaviour(&self) -> FocusBehaviour {
-            FocusBehaviour::ClickTo
-        }
-        fn focus_on_activation(&self) -> FocusOnActivationBehaviour {
-            FocusOnActivationBehaviour::MarkUrgent
-        }
-        fn mousekey(&self) -> Vec<String> {
-            vec!["Mod4".to_owned()]
-        }
-        fn create_list_of_scratchpads(&self) -> Vec<ScratchPad> {
-            vec![]
-        }
-        fn layouts(&self) -> Vec<String> {
-            self.layouts.clone()
-        }
-        fn layout_definitions(&self) -> Vec<Layout> {
-            self.layout_definitions.clone()
-        }
-        fn layout_mode(&self) -> LayoutMode {
-            LayoutMode::Workspace
-        }
-
-        fn insert_behavior(&self) -> InsertBehavior {
-            self.insert_behavior
-        }
-
-        fn single_window_border(&self) -> bool {
-            self.single_window_border
-        }
-
-        fn focus_new_windows(&self) -> bool {
-            false
-        }
-        fn command_handler<H: Handle, SERVER>(
-            command: &str,
-            manager: &mut Manager<H, Self, SERVER>,
-        ) -> bool
-        where
-            SERVER: DisplayServer<H>,
-        {
-            match command {
-                "GoToTag2" => manager.command_handler(&crate::Command::GoToTag {
-                    tag: 2,
-                    swap: false,
-                }),
-                _ => unimplemented!("custom command handler: {:?}", command),
-            }
-        }
-        fn always_float(&self) -> bool {
-            false
-        }
-        fn default_width(&self) -> i32 {
-            1000
-        }
-        fn default_height(&self) -> i32 {
-            800
-        }
-        fn border_width(&self) -> i32 {
-            self.border_width
-        }
-        fn margin(&self) -> Margins {
-            Margins::new(0)
-        }
-        fn workspace_margin(&self) -> Option<Margins> {
-            None
-        }
-        fn gutter(&self) -> Option<Vec<Gutter>> {
-            unimplemented!()
-        }
-        fn default_border_color(&self) -> String {
-            unimplemented!()
-        }
-        fn floating_border_color(&self) -> String {
-            unimplemented!()
-        }
-        fn focused_border_color(&self) -> String {
-            unimplemented!()
-        }
-        fn background_color(&self) -> String {
-            unimplemented!()
-        }
-        fn on_new_window_cmd(&self) -> Option<String> {
-            None
-        }
-        fn get_list_of_gutters(&self) -> Vec<Gutter> {
-            Default::default()
-        }
-        fn disable_tile_drag(&self) -> bool {
-            false
-        }
-        fn disable_window_snap(&self) -> bool {
-            false
-        }
-        fn save_state<H: Handle>(&self, _state: &State<H>) {
-            unimplemented!()
-        }
-        fn load_state<H: Handle>(&self, _state: &mut State<H>) {
-            unimplemented!()
-        }
-        fn setup_predefined_window<H: Handle>(
-            &self,
-            _: &mut State<H>,
-            window: &mut Window<H>,
-        ) -> bool {
-            if window.res_class == Some("ShouldGoToTag2".to_string()) {
-                window.tag = Some(2);
-                true
-            } else {
-                false
-            }
-        }
-        fn sloppy_mouse_follows_focus(&self) -> bool {
-            true
-        }
-
-        fn auto_derive_workspaces(&self) -> bool {
-            true
-        }
-
-        fn reposition_cursor_on_resize(&self) -> bool {
-            true
-        }
-
-        fn create_follows_cursor(&self) -> bool {
-            false
-        }
-
-        fn window_hiding_strategy(&self) -> WindowHidingStrategy {
-            Default::default()
-        }
-    }
-
     #[test]
     fn ensure_command_handler_trait_boundary() {
         let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
         manager.screen_create_handler(Screen::default());
-        assert!(TestConfig::command_handler("GoToTag2", &mut manager));
+        assert_eq!(
+            Ok(true),
+            TestConfig::command_handler("GoToTag2", &mut manager)
+        );
         assert_eq!(manager.state.focus_manager.tag_history, &[2, 1]);
     }
 
@@ -0,0 +1,22 @@
+use super::Workspace;
+use serde::{Deserialize, Serialize};
+
+/// A set of workspaces used while exactly the given outputs are connected, eg. a `laptop`
+/// profile for `eDP-1` alone and a `docked` profile for `eDP-1`, `DP-1` and `DP-2`.
+#[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputProfile {
+    pub name: String,
+    pub outputs: Vec<String>,
+    pub workspaces: Vec<Workspace>,
+}
+
+impl OutputProfile {
+    /// Whether `connected` are exactly the outputs of this profile, in any order.
+    #[must_use]
+    pub fn matches(&self, connected: &[&str]) -> bool {
+        connected
+            .iter()
+            .all(|c| self.outputs.iter().any(|o| o == c))
+            && self.outputs.iter().all(|o| connected.contains(&o.as_str()))
+    }
+}
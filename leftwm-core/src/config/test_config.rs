@@ -0,0 +1,181 @@
+//! A [`Config`] for tests, also available to the tests of other crates with the `test-utils`
+//! feature.
+use super::{
+    Config, FocusBehaviour, Gutter, InsertBehavior, Margins, OutputProfile, ScratchPad,
+    WindowCountLayout, WindowHidingStrategy, Workspace,
+};
+use crate::display_servers::DisplayServer;
+use crate::layouts::LayoutMode;
+use crate::models::{FocusOnActivationBehaviour, Handle, Manager, Window};
+use crate::state::State;
+use crate::utils::command_socket::CommandError;
+use leftwm_layouts::Layout;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Default)]
+pub struct TestConfig {
+    pub tags: Vec<String>,
+    pub layouts: Vec<String>,
+    pub layout_definitions: Vec<Layout>,
+    pub workspaces: Option<Vec<Workspace>>,
+    pub output_profiles: Vec<OutputProfile>,
+    pub window_count_layouts: Vec<WindowCountLayout>,
+    pub insert_behavior: InsertBehavior,
+    pub border_width: i32,
+    pub single_window_border: bool,
+    pub swallow_terminals: bool,
+    pub focus_on_activation: FocusOnActivationBehaviour,
+    pub focus_new_windows: bool,
+    pub window_hiding_strategy: WindowHidingStrategy,
+}
+
+impl Config for TestConfig {
+    fn create_list_of_tag_labels(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+    fn workspaces(&self) -> Option<Vec<Workspace>> {
+        self.workspaces.clone()
+    }
+    fn output_profiles(&self) -> Vec<OutputProfile> {
+        self.output_profiles.clone()
+    }
+    fn focus_behaviour(&self) -> FocusBehaviour {
+        FocusBehaviour::ClickTo
+    }
+    fn focus_on_activation(&self) -> FocusOnActivationBehaviour {
+        self.focus_on_activation
+    }
+    fn mousekey(&self) -> Vec<String> {
+        vec!["Mod4".to_owned()]
+    }
+    fn create_list_of_scratchpads(&self) -> Vec<ScratchPad> {
+        vec![]
+    }
+    fn layouts(&self) -> Vec<String> {
+        self.layouts.clone()
+    }
+    fn layout_definitions(&self) -> Vec<Layout> {
+        self.layout_definitions.clone()
+    }
+    fn layout_mode(&self) -> LayoutMode {
+        LayoutMode::Workspace
+    }
+    fn window_count_layouts(&self) -> Vec<WindowCountLayout> {
+        self.window_count_layouts.clone()
+    }
+
+    fn insert_behavior(&self) -> InsertBehavior {
+        self.insert_behavior
+    }
+
+    fn single_window_border(&self) -> bool {
+        self.single_window_border
+    }
+
+    fn focus_new_windows(&self) -> bool {
+        self.focus_new_windows
+    }
+    fn command_handler<H: Handle, SERVER>(
+        command: &str,
+        manager: &mut Manager<H, Self, SERVER>,
+    ) -> Result<bool, CommandError>
+    where
+        SERVER: DisplayServer<H>,
+    {
+        match command {
+            "GoToTag2" => Ok(manager.command_handler(&crate::Command::GoToTag {
+                tag: 2,
+                swap: false,
+            })),
+            _ => unimplemented!("custom command handler: {:?}", command),
+        }
+    }
+    fn always_float(&self) -> bool {
+        false
+    }
+    fn default_width(&self) -> i32 {
+        1000
+    }
+    fn default_height(&self) -> i32 {
+        800
+    }
+    fn border_width(&self) -> i32 {
+        self.border_width
+    }
+    fn margin(&self) -> Margins {
+        Margins::new(0)
+    }
+    fn workspace_margin(&self) -> Option<Margins> {
+        None
+    }
+    fn gutter(&self) -> Option<Vec<Gutter>> {
+        unimplemented!()
+    }
+    fn default_border_color(&self) -> String {
+        "#000000".to_owned()
+    }
+    fn floating_border_color(&self) -> String {
+        "#000000".to_owned()
+    }
+    fn focused_border_color(&self) -> String {
+        "#ffffff".to_owned()
+    }
+    fn urgent_border_color(&self) -> String {
+        "#ffff00".to_owned()
+    }
+    fn background_color(&self) -> String {
+        "#000000".to_owned()
+    }
+    fn on_new_window_cmd(&self) -> Option<String> {
+        None
+    }
+    fn get_list_of_gutters(&self) -> Vec<Gutter> {
+        Default::default()
+    }
+    fn disable_tile_drag(&self) -> bool {
+        false
+    }
+    fn disable_window_snap(&self) -> bool {
+        false
+    }
+    fn save_state<H: Handle>(&self, _state: &State<H>) {
+        unimplemented!()
+    }
+    fn load_state<H: Handle>(&self, _state: &mut State<H>) {
+        unimplemented!()
+    }
+    fn setup_predefined_window<H: Handle>(&self, _: &mut State<H>, window: &mut Window<H>) -> bool {
+        match window.res_class.as_deref() {
+            Some("ShouldGoToTag2") => window.tag = Some(2),
+            Some("ShouldSwallow") => window.swallow = Some(true),
+            Some("ShouldNotSwallow") => window.swallow = Some(false),
+            _ => return false,
+        }
+        true
+    }
+    fn sloppy_mouse_follows_focus(&self) -> bool {
+        true
+    }
+
+    fn auto_derive_workspaces(&self) -> bool {
+        true
+    }
+
+    fn reposition_cursor_on_resize(&self) -> bool {
+        true
+    }
+
+    fn create_follows_cursor(&self) -> bool {
+        false
+    }
+
+    fn window_hiding_strategy(&self) -> WindowHidingStrategy {
+        self.window_hiding_strategy
+    }
+    fn swallow_terminals(&self) -> bool {
+        self.swallow_terminals
+    }
+    fn as_json(&self) -> serde_json::Value {
+        serde_json::json!({ "tags": self.tags, "layouts": self.layouts })
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Picks `layout` while a workspace or tag tiles between `min` and `max` windows, eg. `Monocle`
+/// for a single window and `Grid` from 5 windows on.
+#[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WindowCountLayout {
+    pub min: usize,
+    /// Without a maximum, the layout is picked for any number of windows from `min` on.
+    #[serde(default)]
+    pub max: Option<usize>,
+    pub layout: String,
+}
+
+impl WindowCountLayout {
+    /// Whether the layout is to be picked for `count` tiled windows.
+    #[must_use]
+    pub fn matches(&self, count: usize) -> bool {
+        self.min <= count && self.max.is_none_or(|max| count <= max)
+    }
+}
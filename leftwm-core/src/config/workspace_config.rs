@@ -1,3 +1,4 @@
+use super::WindowCountLayout;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq)]
@@ -12,4 +13,11 @@ pub struct Workspace {
     /// The default layout from the config; introduced in 0.5.4
     #[serde(default)]
     pub default_layout: Option<String>,
+    /// Replaces the global `window_count_layouts` on this workspace.
+    #[serde(default)]
+    pub window_count_layouts: Option<Vec<WindowCountLayout>>,
+    /// The tag (1-indexed) the workspace shows when it is created or its output profile is
+    /// activated.
+    #[serde(default)]
+    pub default_tag: Option<usize>,
 }
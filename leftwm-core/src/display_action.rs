@@ -3,6 +3,7 @@ use crate::models::TagId;
 use crate::models::Window;
 use crate::models::WindowHandle;
 use crate::models::WindowState;
+use crate::models::Xyhw;
 use crate::utils::modmask_lookup::Button;
 use serde::{Deserialize, Serialize};
 
@@ -73,6 +74,15 @@ pub enum DisplayAction<H: Handle> {
     /// Used to let the WM know of the current displayed tag changes.
     SetCurrentTags(Option<TagId>),
 
+    /// Used to let the WM know the labels of all normal tags, after tags were added or removed.
+    SetDesktopNames(Vec<String>),
+
+    /// Used to let the WM know the part of the screens not covered by docks.
+    SetWorkArea(Xyhw),
+
+    /// Used to let the WM know whether all windows are hidden to show the desktop.
+    SetShowingDesktop(bool),
+
     /// Used to let the WM know of the tag for a given window.
     #[serde(bound = "")]
     SetWindowTag(WindowHandle<H>, Option<TagId>),
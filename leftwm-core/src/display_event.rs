@@ -1,9 +1,13 @@
-use super::{Button, ModMask, models::Screen, models::Window, models::WindowHandle};
+use super::{
+    Button, ModMask, models::Screen, models::Window, models::WindowHandle, models::WorkspaceId,
+};
 use crate::Command;
 use crate::models::{Handle, WindowChange};
+use serde::{Deserialize, Serialize};
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(bound = "")]
 pub enum DisplayEvent<H: Handle> {
     Movement(WindowHandle<H>, i32, i32),
     MouseCombo(ModMask, Button, WindowHandle<H>, i32, i32),
@@ -16,7 +20,11 @@ pub enum DisplayEvent<H: Handle> {
     MoveFocusTo(i32, i32),            // Focus the nearest window to this point.
     MoveWindow(WindowHandle<H>, i32, i32),
     ResizeWindow(WindowHandle<H>, i32, i32),
+    RaiseWindow(WindowHandle<H>), // Put the window in front of the ones of the same importance.
+    OutputProfileChanged(Option<String>), // The name of the profile, if any matches.
     ScreenCreate(Screen<H>),
+    ScreenUpdate(Screen<H>),
+    ScreenDestroy(WorkspaceId),
     SendCommand(Command<H>),
     ConfigureXlibWindow(WindowHandle<H>), // TODO: check if this has backend specific code
     ChangeToNormalMode,
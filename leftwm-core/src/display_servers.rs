@@ -1,3 +1,4 @@
+mod headless_display_server;
 #[cfg(test)]
 mod mock_display_server;
 
@@ -12,6 +13,7 @@ use crate::models::Workspace;
 use futures::prelude::*;
 use std::pin::Pin;
 
+pub use self::headless_display_server::HeadlessDisplayServer;
 #[cfg(test)]
 pub use self::mock_display_server::MockDisplayServer;
 
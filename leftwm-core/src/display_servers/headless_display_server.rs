@@ -0,0 +1,198 @@
+//! A display server without a display, for driving a [`Manager`] through scripted events.
+//!
+//! [`Manager`]: crate::Manager
+use super::Config;
+use super::DisplayEvent;
+use super::DisplayServer;
+use crate::display_action::DisplayAction;
+use crate::models::{Handle, Window, WindowHandle};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Hands out the events pushed to it and records everything the manager asks it to do.
+///
+/// Run the events with [`Manager::run_until_idle`], then check the windows as they would have
+/// been drawn with [`Self::windows`] and the focus with [`Self::focused`].
+///
+/// [`Manager::run_until_idle`]: crate::Manager::run_until_idle
+#[derive(Debug)]
+pub struct HeadlessDisplayServer<H: Handle> {
+    events: VecDeque<DisplayEvent<H>>,
+    actions: Vec<DisplayAction<H>>,
+    windows: RefCell<Vec<Window<H>>>,
+    focused: Option<WindowHandle<H>>,
+}
+
+impl<H: Handle> HeadlessDisplayServer<H> {
+    /// Queues an event, eg. a `ScreenCreate`, `WindowCreate` or `SendCommand`.
+    pub fn push_event(&mut self, event: DisplayEvent<H>) {
+        self.events.push_back(event);
+    }
+
+    /// Queues several events, in order.
+    pub fn push_events(&mut self, events: impl IntoIterator<Item = DisplayEvent<H>>) {
+        self.events.extend(events);
+    }
+
+    /// Every action executed so far, oldest first.
+    #[must_use]
+    pub fn actions(&self) -> &[DisplayAction<H>] {
+        &self.actions
+    }
+
+    /// Takes the actions executed so far, eg. to only check the ones of the next events.
+    pub fn take_actions(&mut self) -> Vec<DisplayAction<H>> {
+        std::mem::take(&mut self.actions)
+    }
+
+    /// The windows as of the last time they were drawn.
+    #[must_use]
+    pub fn windows(&self) -> Vec<Window<H>> {
+        self.windows.borrow().clone()
+    }
+
+    /// The window with `handle` as of the last time it was drawn.
+    #[must_use]
+    pub fn window(&self, handle: WindowHandle<H>) -> Option<Window<H>> {
+        self.windows
+            .borrow()
+            .iter()
+            .find(|w| w.handle == handle)
+            .cloned()
+    }
+
+    /// The window which was last told to take the focus, unless the focus was removed since.
+    #[must_use]
+    pub fn focused(&self) -> Option<WindowHandle<H>> {
+        self.focused
+    }
+}
+
+impl<H: Handle> DisplayServer<H> for HeadlessDisplayServer<H> {
+    fn new(_: &impl Config) -> Self {
+        Self {
+            events: VecDeque::new(),
+            actions: vec![],
+            windows: RefCell::new(vec![]),
+            focused: None,
+        }
+    }
+
+    fn get_next_events(&mut self) -> Vec<DisplayEvent<H>> {
+        // Like a real display, forget about windows as soon as they are gone.
+        for event in &self.events {
+            if let DisplayEvent::WindowDestroy(handle) = event {
+                self.windows.borrow_mut().retain(|w| w.handle != *handle);
+                if self.focused == Some(*handle) {
+                    self.focused = None;
+                }
+            }
+        }
+        self.events.drain(..).collect()
+    }
+
+    fn reload_config(
+        &mut self,
+        _config: &impl Config,
+        _focused: Option<WindowHandle<H>>,
+        _windows: &[Window<H>],
+    ) {
+    }
+
+    fn update_windows(&self, windows: Vec<&Window<H>>) {
+        let mut drawn = self.windows.borrow_mut();
+        for window in windows {
+            match drawn.iter_mut().find(|w| w.handle == window.handle) {
+                Some(old) => old.clone_from(window),
+                None => drawn.push(window.clone()),
+            }
+        }
+    }
+
+    fn execute_action(&mut self, act: DisplayAction<H>) -> Option<DisplayEvent<H>> {
+        match &act {
+            DisplayAction::WindowTakeFocus { window, .. } => self.focused = Some(window.handle),
+            DisplayAction::Unfocus(..) => self.focused = None,
+            _ => {}
+        }
+        self.actions.push(act);
+        None
+    }
+
+    fn wait_readable(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()>>> {
+        if self.events.is_empty() {
+            Box::pin(std::future::pending())
+        } else {
+            Box::pin(std::future::ready(()))
+        }
+    }
+
+    fn flush(&self) {}
+
+    fn generate_verify_focus_event(&self) -> Option<DisplayEvent<H>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manager;
+    use crate::command::Command;
+    use crate::config::tests::TestConfig;
+    use crate::layouts;
+    use crate::models::{BBox, MockHandle, Screen};
+    use leftwm_layouts::layouts::Layouts;
+
+    fn manager() -> Manager<MockHandle, TestConfig, HeadlessDisplayServer<MockHandle>> {
+        Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            layouts: vec![layouts::EVEN_HORIZONTAL.to_string()],
+            layout_definitions: Layouts::default().layouts,
+            ..TestConfig::default()
+        })
+    }
+
+    fn window(handle: MockHandle) -> DisplayEvent<MockHandle> {
+        DisplayEvent::WindowCreate(Window::new(WindowHandle(handle), None, None), 0, 0)
+    }
+
+    #[test]
+    fn scripted_windows_are_tiled_and_focused() {
+        let mut manager = manager();
+        let bbox = BBox {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 600,
+        };
+        manager.display_server.take_actions();
+        manager.display_server.push_events([
+            DisplayEvent::ScreenCreate(Screen::new(bbox, "HDMI-1".to_owned())),
+            window(1),
+            window(2),
+        ]);
+        manager.run_until_idle();
+
+        let server = &manager.display_server;
+        let xs: Vec<_> = server.windows().iter().map(Window::x).collect();
+        assert_eq!(xs, [0, 500]);
+        assert_eq!(server.focused(), Some(WindowHandle(1)));
+
+        manager.display_server.take_actions();
+        manager.display_server.push_events([
+            DisplayEvent::SendCommand(Command::FocusWindowDown),
+            DisplayEvent::WindowDestroy(WindowHandle(1)),
+        ]);
+        manager.run_until_idle();
+        let server = &manager.display_server;
+        assert!(server.actions().iter().any(|act| matches!(
+            act,
+            DisplayAction::WindowTakeFocus { window, .. } if window.handle == WindowHandle(2)
+        )));
+        assert_eq!(server.windows().len(), 1);
+        let window = server.window(WindowHandle(2)).expect("window 2 is drawn");
+        assert_eq!((window.x(), window.width()), (0, 1000));
+        assert_eq!(server.focused(), Some(WindowHandle(2)));
+    }
+}
@@ -1,10 +1,10 @@
 use crate::models::Handle;
-use crate::{
-    Command, CommandPipe, DisplayEvent, DisplayServer, Manager, Mode, StateSocket, Window,
-};
+use crate::utils::command_socket::PendingCommand;
+use crate::utils::recorder::{Recorded, Recorder};
+use crate::{CommandSocket, DisplayEvent, DisplayServer, Manager, Mode, StateSocket, Window};
 use crate::{child_process::Nanny, config::Config};
 use std::path::{Path, PathBuf};
-use std::sync::{Once, atomic::Ordering};
+use std::sync::atomic::Ordering;
 
 /// Errors which can appear while running the event loop.
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Hash)]
@@ -17,73 +17,160 @@ pub enum Error {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum EventResponse {
+pub(crate) enum EventResponse {
     None,
     DisplayRefreshNeeded,
 }
 
+/// What one turn of the event loop handles.
+enum Turn<H: Handle> {
+    /// Read the events waiting at the display server.
+    ReadEvents,
+    /// Double check which window is focused, see [`Manager::refresh_focus`].
+    VerifyFocus,
+    Command(PendingCommand<H>),
+    /// Handle the events read so far.
+    HandleEvents,
+}
+
 impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     /// Starts the event loop of leftwm
     ///
     /// # Errors
-    /// `EventResponse` if the initialisation of the command pipe or/and the state socket failed.
+    /// `EventResponse` if the initialisation of the command socket or/and the state socket failed.
     pub async fn start_event_loop(mut self) -> Result<(), Error> {
-        let state_socket = get_state_socket().await?;
-        let command_pipe = get_command_pipe().await?;
+        let mut state_socket = get_state_socket().await?;
+        let command_socket = match get_command_socket().await {
+            Ok(command_socket) => command_socket,
+            Err(err) => {
+                state_socket.shutdown().await;
+                return Err(err);
+            }
+        };
 
         self.call_up_scripts();
+        if let Some(path) = self.config.record_path() {
+            match Recorder::create(&path) {
+                Ok(recorder) => self.recorder = Some(recorder),
+                Err(err) => tracing::warn!("Unable to record to {:?}: {}", path, err),
+            }
+        }
         tracing::info!("LeftWM-core booted!");
-        self.event_loop(state_socket, command_pipe).await
+        // The saved state is restored once the first turn set up the screens.
+        self.restore_pending = true;
+        self.event_loop(state_socket, command_socket).await
+    }
+
+    /// Handles the events of the display server and executes the resulting actions until no
+    /// events are left, in the same turns as the event loop but without its sockets and scripts.
+    ///
+    /// Meant for driving a [`HeadlessDisplayServer`](crate::display_servers::HeadlessDisplayServer)
+    /// in tests.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if called from within an async runtime, the turns run on a runtime of their own.
+    pub fn run_until_idle(&mut self) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("a current thread runtime can be built");
+        runtime.block_on(async {
+            let mut event_buffer: Vec<DisplayEvent<H>> = vec![];
+            let mut focus_verified = false;
+            loop {
+                self.turn(Turn::ReadEvents, &mut event_buffer, None).await;
+                if event_buffer.is_empty() && self.verifies_focus() && !focus_verified {
+                    focus_verified = true;
+                    self.turn(Turn::VerifyFocus, &mut event_buffer, None).await;
+                }
+                if event_buffer.is_empty() {
+                    return;
+                }
+                self.turn(Turn::HandleEvents, &mut event_buffer, None).await;
+            }
+        });
     }
 
     async fn event_loop(
         &mut self,
         mut state_socket: StateSocket,
-        mut command_pipe: CommandPipe<H>,
+        mut command_socket: CommandSocket<H>,
     ) -> Result<(), Error> {
-        let after_first_loop: Once = Once::new();
         let mut event_buffer: Vec<DisplayEvent<H>> = vec![];
-        while self.should_keep_running(&mut state_socket).await {
-            self.update_manager_state(&mut state_socket).await;
-            self.display_server.flush();
-
-            let response: EventResponse = tokio::select! {
+        self.update_manager_state(&mut state_socket).await;
+        self.display_server.flush();
+        while self
+            .should_keep_running(&mut state_socket, &mut command_socket)
+            .await
+        {
+            let turn = tokio::select! {
                 () = self.display_server.wait_readable(), if event_buffer.is_empty() => {
-                    self.add_events(&mut event_buffer);
-                    continue;
+                    Turn::ReadEvents
                 }
                 // When a mouse button is pressed or enter/motion notifies are blocked and only appear
                 // once the button is released. This is to double check that we know which window
                 // is currently focused.
-                () = timeout(100), if (self.state.focus_manager.sloppy_mouse_follows_focus &&
-                       self.state.focus_manager.behaviour.is_sloppy() &&
-                       event_buffer.is_empty()) => {
-                        self.refresh_focus(&mut event_buffer);
-                        continue;
-                    }
-                Some::<Command<H>>(cmd) = command_pipe.read_command(), if event_buffer.is_empty() => self.execute_command(&cmd),
-                else => self.execute_display_events(&mut event_buffer),
+                () = timeout(100), if self.verifies_focus() && event_buffer.is_empty() => {
+                    Turn::VerifyFocus
+                }
+                Some::<PendingCommand<H>>(cmd) = command_socket.read_command(), if event_buffer.is_empty() => Turn::Command(cmd),
+                else => Turn::HandleEvents,
             };
+            self.turn(turn, &mut event_buffer, Some(&mut state_socket))
+                .await;
+        }
 
-            match response {
-                EventResponse::None => (),
-                EventResponse::DisplayRefreshNeeded => self.refresh_display(),
-            }
+        Ok(())
+    }
 
-            self.execute_actions(&mut event_buffer);
+    /// Handles `turn` and executes the resulting actions, then publishes the new state to
+    /// `state_socket`.
+    async fn turn(
+        &mut self,
+        turn: Turn<H>,
+        event_buffer: &mut Vec<DisplayEvent<H>>,
+        state_socket: Option<&mut StateSocket>,
+    ) {
+        let handles_input = matches!(turn, Turn::Command(_) | Turn::HandleEvents);
+        let response = match turn {
+            Turn::ReadEvents => self.add_events(event_buffer),
+            Turn::VerifyFocus => self.refresh_focus(event_buffer),
+            Turn::Command(cmd) => self.execute_command(cmd),
+            Turn::HandleEvents => self.execute_display_events(event_buffer),
+        };
+
+        match response {
+            EventResponse::None => (),
+            EventResponse::DisplayRefreshNeeded => self.refresh_display(),
+        }
 
-            // We need to run once through all of the loop to properly initialize the state
-            // before we can restore the previous state
-            after_first_loop.call_once(|| {
-                self.config.load_state(&mut self.state);
-            });
+        self.execute_actions(event_buffer);
 
-            if self.reap_requested.swap(false, Ordering::SeqCst) {
-                self.children.remove_finished_children();
+        // We need to run once through all of the loop to properly initialize the state
+        // before we can restore the previous state
+        if self.restore_pending && handles_input {
+            self.restore_pending = false;
+            self.config.load_state(&mut self.state);
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record_restored(&self.state);
             }
         }
 
-        Ok(())
+        if self.reap_requested.swap(false, Ordering::SeqCst) {
+            self.children.remove_finished_children();
+        }
+
+        if let Some(state_socket) = state_socket {
+            self.update_manager_state(state_socket).await;
+        }
+        self.display_server.flush();
+    }
+
+    /// Whether the focus is double checked while the event loop waits, see
+    /// [`Manager::refresh_focus`].
+    fn verifies_focus(&self) -> bool {
+        let focus_manager = &self.state.focus_manager;
+        focus_manager.sloppy_mouse_follows_focus && focus_manager.behaviour.is_sloppy()
     }
 
     async fn update_manager_state(&self, state_socket: &mut StateSocket) {
@@ -92,9 +179,14 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         }
     }
 
-    async fn should_keep_running(&self, state_socket: &mut StateSocket) -> bool {
+    async fn should_keep_running(
+        &self,
+        state_socket: &mut StateSocket,
+        command_socket: &mut CommandSocket<H>,
+    ) -> bool {
         if self.reload_requested {
             state_socket.shutdown().await;
+            command_socket.shutdown().await;
             false
         } else {
             true
@@ -105,6 +197,9 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         let mut display_needs_refresh = false;
 
         event_buffer.drain(..).for_each(|event: DisplayEvent<H>| {
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record(&Recorded::Event(event.clone()));
+            }
             display_needs_refresh = self.display_event_handler(event) || display_needs_refresh;
         });
 
@@ -119,8 +214,8 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         self.update_windows();
 
         match self.state.mode {
-            // When (resizing / moving) only deal with the single window.
-            Mode::ResizingWindow(h) | Mode::MovingWindow(h) => {
+            // When (resizing / moving) only deal with the single window, unless it resizes the tiles.
+            Mode::ResizingWindow(h) | Mode::MovingWindow(h) if self.state.tile_resize.is_none() => {
                 if let Some(window) = self.state.windows.iter().find(|w| w.handle == h) {
                     self.display_server.update_windows(vec![window]);
                 }
@@ -132,12 +227,22 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         }
     }
 
-    fn execute_command(&mut self, command: &Command<H>) -> EventResponse {
-        if self.command_handler(command) {
+    pub(crate) fn execute_command(&mut self, pending: PendingCommand<H>) -> EventResponse {
+        if let Some(result) = self.query_handler(&pending.command) {
+            pending.reply(result);
+            return EventResponse::None;
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&Recorded::Command(pending.command.clone()));
+        }
+        let result = self.try_command_handler(&pending.command);
+        let response = if result == Ok(true) {
             EventResponse::DisplayRefreshNeeded
         } else {
             EventResponse::None
-        }
+        };
+        pending.reply(result.map(|_| None));
+        response
     }
 
     fn add_events(&mut self, event_buffer: &mut Vec<DisplayEvent<H>>) -> EventResponse {
@@ -194,13 +299,13 @@ async fn get_state_socket() -> Result<StateSocket, Error> {
     Ok(state_socket)
 }
 
-async fn get_command_pipe<H: Handle>() -> Result<CommandPipe<H>, Error> {
-    let file_name = crate::pipe_name();
+async fn get_command_socket<H: Handle>() -> Result<CommandSocket<H>, Error> {
+    let file_name = crate::socket_name();
 
-    let pipe_file =
+    let socket_file =
         place_runtime_file(&file_name).map_err(|_| Error::CreateFile(file_name.clone()))?;
 
-    CommandPipe::new(pipe_file)
+    CommandSocket::listen(socket_file)
         .await
         .map_err(|_| Error::ConnectToFile(file_name))
 }
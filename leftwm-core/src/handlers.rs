@@ -3,7 +3,12 @@ pub mod display_event_handler;
 mod focus_handler;
 mod goto_tag_handler;
 mod mouse_combo_handler;
+mod output_profile_handler;
+mod query_handler;
 mod screen_create_handler;
+mod screen_destroy_handler;
+mod screen_update_handler;
+mod tag_handler;
 mod window_handler;
 mod window_move_handler;
 mod window_resize_handler;
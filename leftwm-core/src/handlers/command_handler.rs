@@ -11,16 +11,17 @@ use super::*;
 use crate::command::FocusDeltaBehavior;
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
-use crate::layouts::{self, MAIN_AND_DECK, MONOCLE};
-use crate::models::{Handle, TagId, WindowState};
+use crate::layouts::{self, ColumnStrip, MAIN_AND_DECK, MONOCLE, Orientation, SplitTree};
+use crate::models::{Criteria, Handle, TagId, WindowState, WorkspaceId};
 use crate::state::State;
+use crate::utils::command_socket::{CommandError, ErrorKind};
 use crate::utils::helpers;
 use crate::utils::helpers::relative_find;
 use crate::{config::Config, models::FocusBehaviour};
 
 impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     /* When adding a command
-     * please update src/utils/command_pipe and leftwm/src/command if:
+     * please update src/utils/command_socket and leftwm/src/command if:
      * - a command is introduced or renamed
      * please also update src/bin/leftwm-check if any of the following apply after your update:
      * - a command now requires a value
@@ -29,28 +30,106 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
      *  */
     /// Processes a command and invokes the associated function.
     pub fn command_handler(&mut self, command: &Command<H>) -> bool {
-        process_internal(self, command).unwrap_or(false)
+        self.try_command_handler(command).unwrap_or(false)
+    }
+
+    /// Processes a command like [`Self::command_handler`], but reports why it failed.
+    ///
+    /// # Errors
+    ///
+    /// Will error if a command not known to the core is rejected by the config.
+    pub fn try_command_handler(&mut self, command: &Command<H>) -> Result<bool, CommandError> {
+        match command {
+            Command::Other(cmd) => C::command_handler(cmd, self),
+            Command::WithCriteria { criteria, command } => self.criteria_handler(criteria, command),
+            _ => Ok(process_internal(self, command, None).unwrap_or(false)),
+        }
+    }
+
+    /// Runs `command` once for every window matching `criteria`, commands acting on the focused
+    /// window act on the matching one instead.
+    ///
+    /// # Errors
+    ///
+    /// Will error if no window matches `criteria`.
+    fn criteria_handler(
+        &mut self,
+        criteria: &Criteria,
+        command: &Command<H>,
+    ) -> Result<bool, CommandError> {
+        let handles: Vec<_> = self
+            .state
+            .windows
+            .iter()
+            .filter(|w| criteria.matches(w, &self.state))
+            .map(|w| w.handle)
+            .collect();
+        if handles.is_empty() {
+            return Err(CommandError::new(
+                ErrorKind::ExecutionFailed,
+                format!("No window matches {criteria}"),
+            ));
+        }
+
+        let mut changed = false;
+        for handle in handles {
+            changed = process_internal(self, command, Some(handle)).unwrap_or(false) || changed;
+        }
+        Ok(changed)
+    }
+}
+
+/// The window a command acts on, `window` if one is given or else the focused window.
+fn target_window<H: Handle>(
+    state: &State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<&Window<H>> {
+    match window {
+        Some(handle) => state.windows.iter().find(|w| w.handle == handle),
+        None => state.focus_manager.window(&state.windows),
     }
 }
 
+/// The tag and the workspace laying out `window`, or the focused ones if no window is given.
+fn target_place<H: Handle>(
+    state: &State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<(TagId, WorkspaceId)> {
+    let Some(handle) = window else {
+        let tag_id = state.focus_manager.tag(0)?;
+        let ws_id = state.focus_manager.workspace(&state.workspaces)?.id;
+        return Some((tag_id, ws_id));
+    };
+    let window = state.windows.iter().find(|w| w.handle == handle)?;
+    let tag_id = window.shown_tag(&state.workspaces).or(window.tag)?;
+    let workspace = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.has_tag(&tag_id))
+        .or_else(|| state.focus_manager.workspace(&state.workspaces))?;
+    Some((tag_id, workspace.id))
+}
+
 macro_rules! move_focus_common_vars {
-    ($func:ident ($state:expr_2021 $(, $arg:expr_2021 )* $(,)? )) => {{
-        let handle = $state.focus_manager.window(&$state.windows)?.handle;
-        let tag_id = $state.focus_manager.tag(0)?;
-        let ws_id = $state.focus_manager.workspace(&$state.workspaces)?.id;
+    ($func:ident ($state:expr_2021, $window:expr_2021 $(, $arg:expr_2021 )* $(,)? )) => {{
+        let handle = target_window($state, $window)?.handle;
+        let (tag_id, ws_id) = target_place($state, $window)?;
         let layout = Some($state.layout_manager.layout(ws_id, tag_id).name.to_owned());
 
+        let workspaces = &$state.workspaces;
         let for_active_workspace =
-            |x: &Window<H>| -> bool { x.tag == Some(tag_id) && x.is_managed() };
+            |x: &Window<H>| -> bool { x.is_on_tag(tag_id, workspaces) && x.is_managed() };
 
         let to_reorder = helpers::vec_extract(&mut $state.windows, for_active_workspace);
         $func($state, handle, layout.as_ref(), to_reorder, $($arg),*)
     }};
 }
 
+#[allow(clippy::too_many_lines)]
 fn process_internal<H: Handle, C: Config, SERVER: DisplayServer<H>>(
     manager: &mut Manager<H, C, SERVER>,
     command: &Command<H>,
+    target: Option<WindowHandle<H>>,
 ) -> Option<bool> {
     let state = &mut manager.state;
     match command {
@@ -69,38 +148,56 @@ fn process_internal<H: Handle, C: Config, SERVER: DisplayServer<H>>(
             scratchpad_handler::cycle_scratchpad_window(manager, scratchpad, Direction::Backward)
         }
 
-        Command::ToggleMaximized => toggle_state(state, WindowState::Maximized),
-        Command::ToggleFullScreen => toggle_state(state, WindowState::Fullscreen),
-        Command::ToggleSticky => toggle_state(state, WindowState::Sticky),
-        Command::ToggleAbove => toggle_state(state, WindowState::Above),
-
-        Command::SendWindowToTag { window, tag } => move_to_tag(*window, *tag, manager),
-        Command::MoveWindowToNextTag { follow } => move_to_tag_relative(manager, *follow, 1),
-        Command::MoveWindowToPreviousTag { follow } => move_to_tag_relative(manager, *follow, -1),
-        Command::MoveWindowToLastWorkspace => move_to_last_workspace(state),
-        Command::MoveWindowToNextWorkspace => move_window_to_workspace_change(manager, 1),
-        Command::MoveWindowToPreviousWorkspace => move_window_to_workspace_change(manager, -1),
-        Command::MoveWindowUp => move_focus_common_vars!(move_window_change(state, -1)),
-        Command::MoveWindowDown => move_focus_common_vars!(move_window_change(state, 1)),
-        Command::MoveWindowTop { swap } => move_focus_common_vars!(move_window_top(state, *swap)),
+        Command::ToggleMaximized => toggle_state(state, target, WindowState::Maximized),
+        Command::ToggleFullScreen => toggle_state(state, target, WindowState::Fullscreen),
+        Command::ToggleSticky => toggle_state(state, target, WindowState::Sticky),
+        Command::ToggleAbove => toggle_state(state, target, WindowState::Above),
+        Command::ShowDesktop(show) => show_desktop(state, *show),
+        Command::MinimizeWindow(window) => minimize_window(manager, window.or(target)),
+        Command::RestoreLastMinimized => {
+            let handle = *state.minimized.last()?;
+            restore_minimized(state, handle)
+        }
+        Command::RestoreMinimized(handle) => restore_minimized(state, *handle),
+        Command::FocusUrgent => focus_urgent(state),
+
+        Command::SendWindowToTag { window, tag } => move_to_tag(window.or(target), *tag, manager),
+        Command::MoveWindowToNextTag { follow } => {
+            move_to_tag_relative(manager, target, *follow, 1)
+        }
+        Command::MoveWindowToPreviousTag { follow } => {
+            move_to_tag_relative(manager, target, *follow, -1)
+        }
+        Command::MoveWindowToLastWorkspace => move_to_last_workspace(state, target),
+        Command::MoveWindowToNextWorkspace => move_window_to_workspace_change(manager, target, 1),
+        Command::MoveWindowToPreviousWorkspace => {
+            move_window_to_workspace_change(manager, target, -1)
+        }
+        Command::MoveWindowUp => move_focus_common_vars!(move_window_change(state, target, -1)),
+        Command::MoveWindowDown => move_focus_common_vars!(move_window_change(state, target, 1)),
+        Command::MoveWindowTop { swap } => {
+            move_focus_common_vars!(move_window_top(state, target, *swap))
+        }
         Command::MoveWindowAt(param) => {
-            move_focus_common_vars!(move_window_direction(state, *param))
+            move_focus_common_vars!(move_window_direction(state, target, *param))
+        }
+        Command::SwapWindowTop { swap } => {
+            move_focus_common_vars!(swap_window_top(state, target, *swap))
         }
-        Command::SwapWindowTop { swap } => move_focus_common_vars!(swap_window_top(state, *swap)),
 
         Command::GoToTag { tag, swap } => goto_tag(state, *tag, *swap),
         Command::ReturnToLastTag => return_to_last_tag(state),
 
-        Command::CloseWindow => close_window(state),
+        Command::CloseWindow => close_window(state, target),
         Command::SwapScreens => swap_tags(state),
         Command::NextLayout => next_layout(state),
         Command::PreviousLayout => previous_layout(state),
 
         Command::SetLayout(layout) => set_layout(layout.as_str(), state),
 
-        Command::FloatingToTile => floating_to_tile(state),
-        Command::TileToFloating => tile_to_floating(state),
-        Command::ToggleFloating => toggle_floating(state),
+        Command::FloatingToTile => floating_to_tile(state, target),
+        Command::TileToFloating => tile_to_floating(state, target),
+        Command::ToggleFloating => toggle_floating(state, target),
 
         Command::FocusNextTag { behavior } => match *behavior {
             FocusDeltaBehavior::Default => focus_tag_change(state, 1),
@@ -113,10 +210,10 @@ fn process_internal<H: Handle, C: Config, SERVER: DisplayServer<H>>(
             FocusDeltaBehavior::IgnoreUsed => focus_previous_empty_tag(state),
         },
         Command::FocusWindow(param) => focus_window(state, param),
-        Command::FocusWindowUp => move_focus_common_vars!(focus_window_change(state, -1)),
-        Command::FocusWindowDown => move_focus_common_vars!(focus_window_change(state, 1)),
-        Command::FocusWindowTop { swap } => focus_window_top(state, *swap),
-        Command::FocusWindowAt(param) => focus_window_direction(state, *param),
+        Command::FocusWindowUp => move_focus_common_vars!(focus_window_change(state, target, -1)),
+        Command::FocusWindowDown => move_focus_common_vars!(focus_window_change(state, target, 1)),
+        Command::FocusWindowTop { swap } => focus_window_top(state, target, *swap),
+        Command::FocusWindowAt(param) => focus_window_direction(state, target, *param),
         Command::FocusWorkspaceNext => focus_workspace_change(state, 1),
         Command::FocusWorkspacePrevious => focus_workspace_change(state, -1),
 
@@ -154,11 +251,64 @@ fn process_internal<H: Handle, C: Config, SERVER: DisplayServer<H>>(
         Command::IncreaseMainCount() => change_main_count(state, 1),
         Command::DecreaseMainCount() => change_main_count(state, -1),
         Command::SetMarginMultiplier(multiplier) => set_margin_multiplier(state, *multiplier),
+        Command::SplitHorizontal => split(state, target, Orientation::Horizontal),
+        Command::SplitVertical => split(state, target, Orientation::Vertical),
+        Command::ToggleSplitOrientation => {
+            let (tree, handle) = focused_split_tree(state, target)?;
+            Some(tree.toggle_orientation(&handle))
+        }
+        Command::ResizeSplit(delta) => {
+            let (tree, handle) = focused_split_tree(state, target)?;
+            Some(tree.resize(&handle, *delta as f32 / 100.0))
+        }
+        Command::ScrollLeft => scroll(state, target, -1),
+        Command::ScrollRight => scroll(state, target, 1),
+        Command::ConsumeIntoColumn => {
+            let (strip, handle) = focused_column_strip(state, target)?;
+            Some(strip.consume(&handle))
+        }
+        Command::ExpelFromColumn => {
+            let (strip, handle) = focused_column_strip(state, target)?;
+            Some(strip.expel(&handle))
+        }
+        Command::SetColumnWidth(width) => {
+            let (strip, handle) = focused_column_strip(state, target)?;
+            Some(strip.set_width(&handle, *width as f32 / 100.0))
+        }
         Command::SendWorkspaceToTag(ws_index, tag_index) => {
             Some(send_workspace_to_tag(state, *ws_index, *tag_index))
         }
-        Command::CloseAllOtherWindows => close_all_other_windows(state),
-        Command::Other(cmd) => Some(C::command_handler(cmd, manager)),
+        Command::CloseAllOtherWindows => close_all_other_windows(state, target),
+
+        Command::AddTag(label) => {
+            state.add_tag(label);
+            Some(false)
+        }
+        Command::RemoveTag(tag) => state.remove_tag(tag.or(state.focus_manager.tag(0))?),
+        Command::RenameTag { tag, label } => state.rename_tag(*tag, label),
+        Command::MoveTag { tag, position } => state.move_tag(*tag, *position),
+        Command::ToggleTagView(tag) => state.toggle_tag_view(*tag),
+        Command::ToggleWindowTag(tag) => state.toggle_window_tag(target, *tag),
+
+        Command::MarkWindow(name) => mark_window(state, target, name),
+        Command::UnmarkWindow(name) => unmark_window(state, target, name.as_deref()),
+        Command::FocusMark(name) => focus_mark(state, name),
+        Command::SwapWithMark(name) => swap_with_mark(state, target, name),
+        Command::SendMarkToTag { mark, tag } => {
+            let handle = *state.marks.get(mark)?;
+            move_to_tag(Some(handle), *tag, manager)
+        }
+        // Queries are answered by `query_handler` and never change anything,
+        // criteria and other commands are handled in `try_command_handler`.
+        Command::GetWindows
+        | Command::GetWorkspaces
+        | Command::GetTags
+        | Command::GetLayouts
+        | Command::GetScratchpads
+        | Command::GetFocused
+        | Command::GetConfig
+        | Command::WithCriteria { .. }
+        | Command::Other(_) => None,
     }
 }
 
@@ -239,19 +389,108 @@ fn focus_previous_used_tag<H: Handle>(state: &mut State<H>) -> Option<bool> {
     state.goto_tag_handler(*previous_used_tag)
 }
 
-fn toggle_state<H: Handle>(state: &mut State<H>, window_state: WindowState) -> Option<bool> {
-    let window = state.focus_manager.window(&state.windows)?;
-    let handle = window.handle;
-    let toggle_to = !window.states.contains(&window_state);
+fn toggle_state<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    window_state: WindowState,
+) -> Option<bool> {
+    let target = target_window(state, window)?;
+    let handle = target.handle;
+    let toggle_to = !target.states.contains(&window_state);
     let act = DisplayAction::SetState(handle, toggle_to, window_state);
     state.actions.push_back(act);
-    state.handle_window_focus(&handle);
+    if window.is_none() {
+        state.handle_window_focus(&handle);
+    }
     match window_state {
         WindowState::Fullscreen | WindowState::Maximized => Some(true),
         _ => Some(false),
     }
 }
 
+fn show_desktop<H: Handle>(state: &mut State<H>, show: Option<bool>) -> Option<bool> {
+    let show = show.unwrap_or(!state.showing_desktop);
+    if show == state.showing_desktop {
+        return Some(false);
+    }
+    if show {
+        state.unfocus_current_window();
+        state.showing_desktop = true;
+        let act = DisplayAction::SetShowingDesktop(true);
+        state.actions.push_back(act);
+    } else {
+        state.stop_showing_desktop();
+        let tag = state.focus_manager.tag(0)?;
+        if let Some(handle) = state.focus_manager.tags_last_window.get(&tag).copied() {
+            state.focus_window(&handle);
+        }
+    }
+    Some(true)
+}
+
+/// Hides a window until it is restored, its focus goes to a neighbour.
+fn minimize_window<H: Handle, C: Config, SERVER: DisplayServer<H>>(
+    manager: &mut Manager<H, C, SERVER>,
+    window: Option<WindowHandle<H>>,
+) -> Option<bool> {
+    let state = &manager.state;
+    let handle = window.or(*state.focus_manager.window_history.front()?)?;
+    let window = state.windows.iter().find(|w| w.handle == handle)?;
+    if !window.is_managed() || window.is_hidden() {
+        return Some(false);
+    }
+    let focused = state
+        .focus_manager
+        .window(&state.windows)
+        .is_some_and(|w| w.handle == handle);
+    let next = if focused {
+        manager.get_next_or_previous_handle(&handle)
+    } else {
+        None
+    };
+
+    let state = &mut manager.state;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
+    window.states.push(WindowState::Hidden);
+    state.track_minimized(&handle);
+    let act = DisplayAction::SetState(handle, true, WindowState::Hidden);
+    state.actions.push_back(act);
+    if focused {
+        match next {
+            Some(next) => state.focus_window(&next),
+            None => state.unfocus_current_window(),
+        }
+    }
+    Some(true)
+}
+
+/// Shows a minimized window again and focuses it.
+fn restore_minimized<H: Handle>(state: &mut State<H>, handle: WindowHandle<H>) -> Option<bool> {
+    let window = state
+        .windows
+        .iter_mut()
+        .find(|w| w.handle == handle && w.is_hidden())?;
+    window.states.retain(|s| s != &WindowState::Hidden);
+    state.track_minimized(&handle);
+    let act = DisplayAction::SetState(handle, false, WindowState::Hidden);
+    state.actions.push_back(act);
+
+    state.stop_showing_desktop();
+    state.reveal_window(&handle);
+    Some(true)
+}
+
+/// Focuses the window which asked for attention first, restoring it if it is minimized.
+fn focus_urgent<H: Handle>(state: &mut State<H>) -> Option<bool> {
+    let handle = *state.urgent.first()?;
+    if state.minimized.contains(&handle) {
+        return restore_minimized(state, handle);
+    }
+    state.stop_showing_desktop();
+    state.reveal_window(&handle);
+    Some(true)
+}
+
 fn move_to_tag<H: Handle, C: Config, SERVER: DisplayServer<H>>(
     window: Option<WindowHandle<H>>,
     tag_id: TagId,
@@ -310,39 +549,41 @@ fn move_to_tag<H: Handle, C: Config, SERVER: DisplayServer<H>>(
 /// Conditionally allow focus to follow the window to the target tag
 fn move_to_tag_relative<H: Handle, C: Config, SERVER: DisplayServer<H>>(
     manager: &mut Manager<H, C, SERVER>,
+    window: Option<WindowHandle<H>>,
     follow: bool,
     delta: i32,
 ) -> Option<bool> {
     // Map indexing from 1..len to 0..(len - 1)
-    let current_tag = manager.state.focus_manager.tag(0).unwrap_or_default() - 1;
+    let current_tag = target_place(&manager.state, window).map_or(0, |(tag, _)| tag) - 1;
     // apply euclidean division reminder to the result of offseting to wrap around tags vector
     // and add 1 to remap back to 1..len indexing
     let tags_len = manager.state.tags.normal().len() as isize;
     let desired_tag = (current_tag as isize + delta as isize).rem_euclid(tags_len) + 1;
     let desired_tag = desired_tag as usize;
 
-    move_to_tag(None, desired_tag, manager);
+    move_to_tag(window, desired_tag, manager);
     if follow {
-        let moved_window = *manager.state.focus_manager.window_history.get(1)?;
+        let moved_window = match window {
+            Some(handle) => handle,
+            None => (*manager.state.focus_manager.window_history.get(1)?)?,
+        };
         manager.state.goto_tag_handler(desired_tag);
-        manager.state.handle_window_focus(&moved_window?);
+        manager.state.handle_window_focus(&moved_window);
     }
     Some(true)
 }
 
 fn move_window_to_workspace_change<H: Handle, C: Config, SERVER: DisplayServer<H>>(
     manager: &mut Manager<H, C, SERVER>,
+    window: Option<WindowHandle<H>>,
     delta: i32,
 ) -> Option<bool> {
-    let current = manager
-        .state
-        .focus_manager
-        .workspace(&manager.state.workspaces)?;
-    let workspace =
-        helpers::relative_find(&manager.state.workspaces, |w| w == current, delta, true)?.clone();
+    let (_, current) = target_place(&manager.state, window)?;
+    let workspaces = &manager.state.workspaces;
+    let workspace = helpers::relative_find(workspaces, |w| w.id == current, delta, true)?;
 
     let tag_id = workspace.tag?;
-    move_to_tag(None, tag_id, manager)
+    move_to_tag(window, tag_id, manager)
 }
 
 fn goto_tag<H: Handle>(
@@ -385,10 +626,15 @@ fn focus_window<H: Handle>(state: &mut State<H>, param: &str) -> Option<bool> {
 }
 
 // TODO: add comment
-fn focus_window_direction<H: Handle>(state: &mut State<H>, dir: FocusDirection) -> Option<bool> {
-    let workspace = state.focus_manager.workspace(&state.workspaces)?.rect();
+fn focus_window_direction<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    dir: FocusDirection,
+) -> Option<bool> {
+    let (_, ws_id) = target_place(state, window)?;
+    let workspace = state.workspaces.iter().find(|ws| ws.id == ws_id)?.rect();
     let mut rects: Vec<Rect> = vec![];
-    let cur_window = state.focus_manager.window(&state.windows)?;
+    let cur_window = target_window(state, window)?;
 
     let mut cur = None;
 
@@ -444,6 +690,14 @@ fn focus_window_by_class<H: Handle>(state: &mut State<H>, window_class: &str) ->
         state.windows.iter().find(|w| is_target(w)).cloned()
     }?;
 
+    focus_or_reveal_window(state, &target_window)
+}
+
+/// Focuses `target_window`, going to its tag first if it is not visible.
+fn focus_or_reveal_window<H: Handle>(
+    state: &mut State<H>,
+    target_window: &Window<H>,
+) -> Option<bool> {
     let handle = target_window.handle;
 
     if target_window.visible() {
@@ -522,8 +776,8 @@ fn swap_tags<H: Handle>(state: &mut State<H>) -> Option<bool> {
 }
 
 // TODO: closing windows breaks focus, see gh-1204
-fn close_window<H: Handle>(state: &mut State<H>) -> Option<bool> {
-    let window = state.focus_manager.window(&state.windows)?;
+fn close_window<H: Handle>(state: &mut State<H>, window: Option<WindowHandle<H>>) -> Option<bool> {
+    let window = target_window(state, window)?;
     if window.is_managed() {
         let act = DisplayAction::KillWindow(window.handle);
         state.actions.push_back(act);
@@ -531,11 +785,15 @@ fn close_window<H: Handle>(state: &mut State<H>) -> Option<bool> {
     None
 }
 
-fn move_to_last_workspace<H: Handle>(state: &mut State<H>) -> Option<bool> {
+fn move_to_last_workspace<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<bool> {
     if state.workspaces.len() >= 2 && state.focus_manager.workspace_history.len() >= 2 {
         let index = *state.focus_manager.workspace_history.get(1)?;
         let wp_tags = state.workspaces.get(index)?.tag;
-        let window = state.focus_manager.window_mut(&mut state.windows)?;
+        let handle = target_window(state, window)?.handle;
+        let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
         window.tag = wp_tags;
         return Some(true);
     }
@@ -613,9 +871,15 @@ fn set_layout<H: Handle>(layout: &str, state: &mut State<H>) -> Option<bool> {
     Some(true)
 }
 
-fn floating_to_tile<H: Handle>(state: &mut State<H>) -> Option<bool> {
-    let workspace = state.focus_manager.workspace(&state.workspaces)?;
-    let window = state.focus_manager.window_mut(&mut state.windows)?;
+fn floating_to_tile<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<bool> {
+    let (_, ws_id) = target_place(state, window)?;
+    let workspace = state.workspaces.iter().find(|ws| ws.id == ws_id)?;
+    let handle = target_window(state, window)?.handle;
+    let focus = window.is_none();
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
     if window.must_float() {
         return None;
     }
@@ -624,18 +888,23 @@ fn floating_to_tile<H: Handle>(state: &mut State<H>) -> Option<bool> {
     if !window.floating() {
         return None;
     }
-    let handle = window.handle;
     if window.snap_to_workspace(workspace) {
         state.sort_windows();
     }
-    state.handle_window_focus(&handle);
+    if focus {
+        state.handle_window_focus(&handle);
+    }
     Some(true)
 }
 
-fn tile_to_floating<H: Handle>(state: &mut State<H>) -> Option<bool> {
+fn tile_to_floating<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<bool> {
     let width = state.default_width;
     let height = state.default_height;
-    let window = state.focus_manager.window_mut(&mut state.windows)?;
+    let handle = target_window(state, window)?.handle;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
 
     if window.floating() {
         return None;
@@ -654,18 +923,19 @@ fn tile_to_floating<H: Handle>(state: &mut State<H>) -> Option<bool> {
     window.start_loc = Some(floating);
     window.set_floating(true);
 
-    let handle = window.handle;
     state.move_to_top(&handle);
 
     Some(true)
 }
 
-fn toggle_floating<H: Handle>(state: &mut State<H>) -> Option<bool> {
-    let window = state.focus_manager.window(&state.windows)?;
-    if window.floating() {
-        floating_to_tile(state)
+fn toggle_floating<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<bool> {
+    if target_window(state, window)?.floating() {
+        floating_to_tile(state, window)
     } else {
-        tile_to_floating(state)
+        tile_to_floating(state, window)
     }
 }
 
@@ -738,7 +1008,12 @@ fn move_window_direction<H: Handle>(
     mut to_reorder: Vec<Window<H>>,
     dir: FocusDirection,
 ) -> Option<bool> {
-    let workspace = state.focus_manager.workspace(&state.workspaces)?.rect();
+    let window = to_reorder.iter().find(|w| w.handle == handle)?;
+    let workspace = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.lays_out(window, &state.workspaces))?
+        .rect();
     let mut rects: Vec<Rect> = vec![];
     let mut cur = None;
 
@@ -835,14 +1110,18 @@ fn focus_window_change<H: Handle>(
     Some(layout == Some(layouts::MONOCLE.to_string()).as_ref())
 }
 
-fn focus_window_top<H: Handle>(state: &mut State<H>, swap: bool) -> Option<bool> {
-    let tag = state.focus_manager.tag(0)?;
-    let cur = state.focus_manager.window(&state.windows).map(|w| w.handle);
+fn focus_window_top<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    swap: bool,
+) -> Option<bool> {
+    let (tag, _) = target_place(state, window)?;
+    let cur = target_window(state, window).map(|w| w.handle);
     let prev = state.focus_manager.tags_last_window.get(&tag).copied();
     let next = state
         .windows
         .iter()
-        .find(|x| x.tag == Some(tag) && !x.floating() && x.is_managed())
+        .find(|x| x.is_on_tag(tag, &state.workspaces) && !x.floating() && x.is_managed())
         .map(|w| w.handle);
 
     match (next, cur, prev) {
@@ -855,10 +1134,78 @@ fn focus_window_top<H: Handle>(state: &mut State<H>, swap: bool) -> Option<bool>
     None
 }
 
-fn close_all_other_windows<H: Handle>(state: &mut State<H>) -> Option<bool> {
-    let current_window: Option<WindowHandle<H>> =
-        state.focus_manager.window(&state.windows).map(|w| w.handle);
-    let current_workspace = state.focus_manager.workspace(&state.workspaces);
+fn mark_window<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    name: &str,
+) -> Option<bool> {
+    let handle = target_window(state, window)?.handle;
+    state.marks.insert(name.to_owned(), handle);
+    Some(false)
+}
+
+/// Removes the mark `name`, or every mark of the focused window.
+fn unmark_window<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    name: Option<&str>,
+) -> Option<bool> {
+    if let Some(name) = name {
+        state.marks.remove(name)?;
+    } else {
+        let handle = target_window(state, window)?.handle;
+        state.marks.retain(|_, h| *h != handle);
+    }
+    Some(false)
+}
+
+fn focus_mark<H: Handle>(state: &mut State<H>, name: &str) -> Option<bool> {
+    let handle = state.marks.get(name)?;
+    let target_window = state.windows.iter().find(|w| &w.handle == handle)?.clone();
+    focus_or_reveal_window(state, &target_window)
+}
+
+/// Swaps the places, including the tags, of the focused and the marked window.
+/// Focus stays at the place of the focused window.
+fn swap_with_mark<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    name: &str,
+) -> Option<bool> {
+    let focused = target_window(state, window)?.handle;
+    let marked = *state.marks.get(name)?;
+    if focused == marked {
+        return Some(false);
+    }
+
+    let focused_index = state.windows.iter().position(|w| w.handle == focused)?;
+    let marked_index = state.windows.iter().position(|w| w.handle == marked)?;
+    let focused_tag = state.windows[focused_index].tag;
+    let marked_tag = state.windows[marked_index].tag;
+    state.windows.swap(focused_index, marked_index);
+
+    if focused_tag != marked_tag {
+        for (index, tag) in [(focused_index, focused_tag), (marked_index, marked_tag)] {
+            let window = &mut state.windows[index];
+            window.untag();
+            if let Some(tag) = tag {
+                window.tag(&tag);
+            }
+            let act = DisplayAction::SetWindowTag(window.handle, tag);
+            state.actions.push_back(act);
+        }
+        state.handle_window_focus(&marked);
+    }
+    Some(true)
+}
+
+fn close_all_other_windows<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<bool> {
+    let current_window: Option<WindowHandle<H>> = target_window(state, window).map(|w| w.handle);
+    let (_, ws_id) = target_place(state, window)?;
+    let current_workspace = state.workspaces.iter().find(|ws| ws.id == ws_id);
 
     for window in &state.windows {
         if window.handle.ne(&current_window?)
@@ -922,6 +1269,62 @@ fn change_main_count<H: Handle>(state: &mut State<H>, factor: i8) -> Option<bool
     Some(true)
 }
 
+/// The split tree of the tag of `window`, or of the focused tag and window, if the tag is
+/// tiled manually.
+fn focused_split_tree<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<(&mut SplitTree<H>, WindowHandle<H>)> {
+    let (tag_id, workspace_id) = target_place(state, window)?;
+    if state.layout_manager.layout(workspace_id, tag_id).name != layouts::MANUAL {
+        return None;
+    }
+    let handle = target_window(state, window)?.handle;
+    Some((state.split_trees.entry(tag_id).or_default(), handle))
+}
+
+/// The column strip of the tag of `window`, or of the focused tag and window, if the tag
+/// scrolls.
+fn focused_column_strip<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+) -> Option<(&mut ColumnStrip<H>, WindowHandle<H>)> {
+    let (tag_id, workspace_id) = target_place(state, window)?;
+    if state.layout_manager.layout(workspace_id, tag_id).name != layouts::SCROLLING {
+        return None;
+    }
+    let handle = target_window(state, window)?.handle;
+    Some((state.column_strips.entry(tag_id).or_default(), handle))
+}
+
+fn scroll<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    shift: i32,
+) -> Option<bool> {
+    let history: Vec<_> = state
+        .focus_manager
+        .window_history
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+    let (strip, handle) = focused_column_strip(state, window)?;
+    let next = strip.neighbour(&handle, shift, history)?;
+    state.handle_window_focus(&next);
+    Some(true)
+}
+
+fn split<H: Handle>(
+    state: &mut State<H>,
+    window: Option<WindowHandle<H>>,
+    orientation: Orientation,
+) -> Option<bool> {
+    let (tree, _) = focused_split_tree(state, window)?;
+    tree.split(orientation);
+    Some(false)
+}
+
 fn set_margin_multiplier<H: Handle>(state: &mut State<H>, margin_multiplier: f32) -> Option<bool> {
     let ws = state.focus_manager.workspace_mut(&mut state.workspaces)?;
     ws.set_margin_multiplier(margin_multiplier);
@@ -1910,4 +2313,359 @@ mod tests {
 
         assert_eq!(manager.state.focus_manager.tag(0).unwrap(), 3);
     }
+
+    #[test]
+    fn criteria_act_on_matching_windows_without_focusing_them() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for (handle, class) in [(1, "firefox"), (2, "alacritty")] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.res_class = Some(class.to_string());
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_window(&WindowHandle(2));
+
+        let command = Command::WithCriteria {
+            criteria: Criteria::parse("class=firefox").unwrap(),
+            command: Box::new(Command::SendWindowToTag {
+                window: None,
+                tag: 2,
+            }),
+        };
+        assert_eq!(Ok(true), manager.try_command_handler(&command));
+
+        let firefox = manager.state.windows.iter().find(|w| w.handle.0 == 1);
+        assert_eq!(firefox.unwrap().tag, Some(2));
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(2)))
+        );
+
+        let command = Command::WithCriteria {
+            criteria: Criteria::parse("class=chromium").unwrap(),
+            command: Box::new(Command::CloseWindow),
+        };
+        assert!(manager.try_command_handler(&command).is_err());
+    }
+
+    #[test]
+    fn criteria_reorder_windows_on_the_tag_of_the_matching_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        for (handle, class) in [(1, "alacritty"), (2, "alacritty"), (3, "firefox")] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.res_class = Some(class.to_string());
+            manager.window_created_handler(window, -1, -1);
+        }
+        for (handle, tag) in [(1, 1), (2, 2), (3, 2)] {
+            manager.command_handler(&Command::SendWindowToTag {
+                window: Some(WindowHandle(handle)),
+                tag,
+            });
+        }
+        manager.state.focus_window(&WindowHandle(1));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+
+        let command = Command::WithCriteria {
+            criteria: Criteria::parse("class=firefox").unwrap(),
+            command: Box::new(Command::MoveWindowTop { swap: false }),
+        };
+        assert_eq!(Ok(true), manager.try_command_handler(&command));
+
+        let tag_2: Vec<_> = manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| w.has_tag(&2))
+            .map(|w| w.handle.0)
+            .collect();
+        assert_eq!(tag_2, vec![3, 2]);
+    }
+
+    #[test]
+    fn focus_mark_reveals_marked_window_until_it_is_destroyed() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=2 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_window(&WindowHandle(1));
+        manager.command_handler(&Command::MarkWindow("build".to_string()));
+        assert!(manager.command_handler(&Command::SendMarkToTag {
+            mark: "build".to_string(),
+            tag: 2,
+        }));
+        manager.state.focus_window(&WindowHandle(2));
+
+        manager.command_handler(&Command::FocusMark("build".to_string()));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(1)))
+        );
+
+        manager.window_destroyed_handler(&WindowHandle(1));
+        assert!(manager.state.marks.is_empty());
+        assert!(!manager.command_handler(&Command::FocusMark("build".to_string())));
+    }
+
+    #[test]
+    fn swap_with_mark_exchanges_places_and_tags() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=3 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_window(&WindowHandle(3));
+        manager.command_handler(&Command::MarkWindow("editor".to_string()));
+        manager.command_handler(&Command::SendWindowToTag {
+            window: Some(WindowHandle(3)),
+            tag: 2,
+        });
+        manager.state.focus_window(&WindowHandle(1));
+
+        assert!(manager.command_handler(&Command::SwapWithMark("editor".to_string())));
+
+        let tags: Vec<_> = manager
+            .state
+            .windows
+            .iter()
+            .map(|w| (w.handle.0, w.tag))
+            .collect();
+        assert_eq!(tags, [(3, Some(1)), (2, Some(1)), (1, Some(2))]);
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(3)))
+        );
+
+        manager.command_handler(&Command::UnmarkWindow(None));
+        assert!(manager.state.marks.is_empty());
+    }
+
+    #[test]
+    fn show_desktop_hides_windows_until_a_tag_is_shown() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=2 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_window(&WindowHandle(2));
+
+        assert!(manager.command_handler(&Command::ShowDesktop(None)));
+        manager.update_windows();
+        assert!(manager.state.windows.iter().all(|w| !w.visible()));
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&None)
+        );
+        assert!(!manager.command_handler(&Command::ShowDesktop(Some(true))));
+
+        assert!(manager.command_handler(&Command::ShowDesktop(None)));
+        manager.update_windows();
+        assert!(manager.state.windows.iter().all(Window::visible));
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(2)))
+        );
+
+        manager.command_handler(&Command::ShowDesktop(Some(true)));
+        manager.state.actions.clear();
+        manager.command_handler(&Command::GoToTag {
+            tag: 2,
+            swap: false,
+        });
+        assert!(!manager.state.showing_desktop);
+        assert!(
+            manager
+                .state
+                .actions
+                .iter()
+                .any(|action| matches!(action, DisplayAction::SetShowingDesktop(false)))
+        );
+    }
+
+    #[test]
+    fn manual_layout_tiles_windows_by_their_splits() {
+        let mut manager = Manager::new_test_with_layouts(
+            vec!["1".to_string()],
+            vec![layouts::MANUAL.to_string()],
+        );
+        let add = |manager: &mut Manager<_, _, _>, handle| {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+            manager.state.focus_window(&WindowHandle(handle));
+            manager.update_windows();
+        };
+        let geometry = |manager: &Manager<_, _, _>, handle| {
+            let window = manager.state.windows.iter().find(|w| w.handle.0 == handle);
+            window.map(|w| (w.x(), w.y(), w.width(), w.height()))
+        };
+
+        add(&mut manager, 1);
+        add(&mut manager, 2);
+        assert!(!manager.command_handler(&Command::SplitVertical));
+        add(&mut manager, 3);
+        assert_eq!(geometry(&manager, 1), Some((0, 0, 400, 600)));
+        assert_eq!(geometry(&manager, 2), Some((400, 0, 400, 300)));
+        assert_eq!(geometry(&manager, 3), Some((400, 300, 400, 300)));
+
+        assert!(manager.command_handler(&Command::ResizeSplit(10)));
+        manager.update_windows();
+        assert_eq!(geometry(&manager, 3), Some((400, 240, 400, 360)));
+
+        assert!(manager.command_handler(&Command::ToggleSplitOrientation));
+        manager.window_destroyed_handler(&WindowHandle(1));
+        manager.update_windows();
+        assert_eq!(geometry(&manager, 2), Some((0, 0, 320, 600)));
+        assert_eq!(geometry(&manager, 3), Some((320, 0, 480, 600)));
+    }
+
+    #[test]
+    fn scrolling_layout_shows_the_focused_column() {
+        let mut manager = Manager::new_test_with_layouts(
+            vec!["1".to_string()],
+            vec![layouts::SCROLLING.to_string()],
+        );
+        for handle in 1..=3 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+            manager.state.focus_window(&WindowHandle(handle));
+            manager.update_windows();
+        }
+        let run = |manager: &mut Manager<_, _, _>, command| {
+            let changed = manager.command_handler(&command);
+            manager.update_windows();
+            changed
+        };
+        let shown = |manager: &Manager<_, _, _>| {
+            let windows = manager.state.windows.iter().filter(|w| w.visible());
+            windows
+                .map(|w| (w.handle.0, w.x(), w.y(), w.width(), w.height()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            shown(&manager),
+            [(2, 0, 0, 400, 600), (3, 400, 0, 400, 600)]
+        );
+
+        assert!(run(&mut manager, Command::ScrollLeft));
+        assert!(run(&mut manager, Command::ScrollLeft));
+        assert!(!run(&mut manager, Command::ScrollLeft));
+        assert_eq!(
+            shown(&manager),
+            [(1, 0, 0, 400, 600), (2, 400, 0, 400, 600)]
+        );
+
+        assert!(!run(&mut manager, Command::ConsumeIntoColumn));
+        assert!(run(&mut manager, Command::ScrollRight));
+        assert!(run(&mut manager, Command::ConsumeIntoColumn));
+        assert!(run(&mut manager, Command::SetColumnWidth(100)));
+        assert_eq!(
+            shown(&manager),
+            [(1, 0, 0, 800, 300), (2, 0, 300, 800, 300)]
+        );
+
+        // The wide column of window 1 is clipped next to the new column of window 2, leaving no
+        // gap.
+        assert!(run(&mut manager, Command::ExpelFromColumn));
+        assert_eq!(
+            shown(&manager),
+            [(1, 0, 0, 400, 600), (2, 400, 0, 400, 600)]
+        );
+    }
+
+    #[test]
+    fn focus_urgent_jumps_to_the_oldest_urgent_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for (tag, handle) in [(1, 1), (2, 2), (2, 3)] {
+            manager.command_handler(&Command::GoToTag { tag, swap: false });
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.command_handler(&Command::GoToTag {
+            tag: 1,
+            swap: false,
+        });
+        for handle in [3, 2] {
+            let mut change = WindowChange::new(WindowHandle(handle));
+            change.urgent = Some(true);
+            manager.window_changed_handler(change);
+        }
+        assert_eq!(manager.state.urgent, [WindowHandle(3), WindowHandle(2)]);
+
+        assert!(manager.command_handler(&Command::FocusUrgent));
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(3)))
+        );
+        assert_eq!(manager.state.urgent, [WindowHandle(2)]);
+        assert!(
+            !manager
+                .state
+                .windows
+                .iter()
+                .any(|w| w.handle.0 == 3 && w.urgent)
+        );
+
+        assert!(manager.command_handler(&Command::FocusUrgent));
+        assert!(manager.state.urgent.is_empty());
+        assert!(!manager.command_handler(&Command::FocusUrgent));
+    }
+
+    #[test]
+    fn minimized_windows_are_restored_last_first() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=3 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_window(&WindowHandle(3));
+
+        assert!(manager.command_handler(&Command::MinimizeWindow(None)));
+        assert!(manager.command_handler(&Command::MinimizeWindow(Some(WindowHandle(1)))));
+        assert!(!manager.command_handler(&Command::MinimizeWindow(Some(WindowHandle(1)))));
+        manager.update_windows();
+        assert_eq!(manager.state.minimized, [WindowHandle(3), WindowHandle(1)]);
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(2)))
+        );
+        let visible: Vec<_> = manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| w.visible())
+            .map(|w| w.handle.0)
+            .collect();
+        assert_eq!(visible, [2]);
+        assert!(manager.state.actions.iter().any(|action| matches!(
+            action,
+            DisplayAction::SetState(WindowHandle(1), true, WindowState::Hidden)
+        )));
+
+        // Restoring a window brings its tag into view.
+        manager.command_handler(&Command::GoToTag {
+            tag: 2,
+            swap: false,
+        });
+        assert!(manager.command_handler(&Command::RestoreLastMinimized));
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+        assert_eq!(manager.state.minimized, [WindowHandle(3)]);
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(1)))
+        );
+
+        assert!(manager.command_handler(&Command::RestoreMinimized(WindowHandle(3))));
+        assert!(!manager.command_handler(&Command::RestoreLastMinimized));
+        manager.update_windows();
+        assert!(manager.state.windows.iter().all(Window::visible));
+    }
 }
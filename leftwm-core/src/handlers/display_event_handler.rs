@@ -10,7 +10,12 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     pub fn display_event_handler(&mut self, event: DisplayEvent<H>) -> bool {
         let state = &mut self.state;
         match event {
+            DisplayEvent::OutputProfileChanged(name) => {
+                self.output_profile_handler(name.as_deref())
+            }
             DisplayEvent::ScreenCreate(s) => self.screen_create_handler(s),
+            DisplayEvent::ScreenUpdate(s) => self.screen_update_handler(s),
+            DisplayEvent::ScreenDestroy(id) => self.screen_destroy_handler(id),
             DisplayEvent::WindowCreate(w, x, y) => self.window_created_handler(w, x, y),
             DisplayEvent::WindowChange(w) => self.window_changed_handler(w),
             DisplayEvent::WindowDestroy(handle) => self.window_destroyed_handler(&handle),
@@ -27,6 +32,7 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             DisplayEvent::Movement(handle, x, y) => from_movement(state, handle, x, y),
             DisplayEvent::MoveWindow(handle, x, y) => from_move_window(self, handle, x, y),
             DisplayEvent::ResizeWindow(handle, x, y) => from_resize_window(self, handle, x, y),
+            DisplayEvent::RaiseWindow(handle) => from_raise_window(state, handle),
             DisplayEvent::ConfigureXlibWindow(handle) => from_configure_xlib_window(state, handle),
         }
     }
@@ -42,6 +48,12 @@ fn from_handle_window_focus<H: Handle>(state: &mut State<H>, handle: WindowHandl
     false
 }
 
+fn from_raise_window<H: Handle>(state: &mut State<H>, handle: WindowHandle<H>) -> bool {
+    // The new stacking order is sent along with `SetWindowOrder`, nothing needs rendering.
+    state.move_to_top(&handle);
+    false
+}
+
 fn from_move_focus_to<H: Handle>(state: &mut State<H>, x: i32, y: i32) -> bool {
     state.focus_window_with_point(x, y);
     false
@@ -55,7 +67,10 @@ fn from_verify_focus_at<H: Handle>(state: &mut State<H>, handle: WindowHandle<H>
 }
 
 fn from_change_to_normal_mode<H: Handle>(state: &mut State<H>) -> bool {
+    let tile_resize = state.tile_resize.take();
     match state.mode {
+        // The tiles were resized, the window itself stays where it is.
+        Mode::ResizingWindow(h) if tile_resize.is_some() => state.focus_window(&h),
         Mode::MovingWindow(h) | Mode::ResizingWindow(h) => {
             // We want to update the windows tag once it is done moving. This means
             // when the window is re-tiled it is on the correct workspace. This also
@@ -132,10 +147,12 @@ fn from_resize_window<H: Handle, C: Config, SERVER: DisplayServer<H>>(
     x: i32,
     y: i32,
 ) -> bool {
-    // Setup for when window first resizes.
+    // Setup for when window first resizes, tiled windows resize the tiles around them.
     if let Mode::ReadyToResize(h) = manager.state.mode {
         manager.state.mode = Mode::ResizingWindow(h);
-        prepare_window(&mut manager.state, h);
+        if !manager.state.start_tile_resize(&h) {
+            prepare_window(&mut manager.state, h);
+        }
     }
     manager.window_resize_handler(&handle, x, y)
 }
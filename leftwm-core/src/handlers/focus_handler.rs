@@ -27,18 +27,43 @@ impl<H: Handle> State<H> {
         if let Some(workspace_id) = self
             .workspaces
             .iter()
-            .find(|ws| ws.is_displaying(&window))
+            .find(|ws| ws.lays_out(&window, &self.workspaces))
             .map(|ws| ws.id)
         {
             _ = self.focus_workspace_work(workspace_id);
         }
 
-        // Make sure the focused window's tag is focused.
-        if let Some(tag) = window.tag {
+        // Make sure the focused window's tag is focused. A workspace viewing several tags
+        // keeps its main tag.
+        let tag = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .filter(|ws| ws.is_displaying(&window))
+            .and_then(|ws| ws.tag)
+            .or(window.tag);
+        if let Some(tag) = tag {
             _ = self.focus_tag_work(tag, true);
         }
     }
 
+    /// Focuses the given window, showing its tag on the focused workspace first if no workspace
+    /// shows it.
+    pub fn reveal_window(&mut self, handle: &WindowHandle<H>) {
+        let tag = self
+            .windows
+            .iter()
+            .find(|w| &w.handle == handle)
+            .and_then(|w| w.tag);
+        if let Some(tag) = tag
+            && !self.workspaces.iter().any(|ws| ws.has_tag(&tag))
+        {
+            // Going to the tag must not focus, and so clear the urgency of, another window first.
+            self.focus_manager.tags_last_window.insert(tag, *handle);
+            self.goto_tag_handler(tag);
+        }
+        self.focus_window(handle);
+    }
+
     /// Focuses the given workspace.
     // NOTE: Should only be called externally from this file.
     pub fn focus_workspace(&mut self, workspace: &Workspace) {
@@ -92,7 +117,7 @@ impl<H: Handle> State<H> {
 
         // Unfocus last window if the target tag is empty
         if let Some(window) = self.focus_manager.window(&self.windows)
-            && window.tag != Some(*tag)
+            && !window.has_tag(tag)
         {
             self.unfocus_current_window();
         }
@@ -183,7 +208,9 @@ impl<H: Handle> State<H> {
 
         if update_workspace && let Some(ws) = self.focus_manager.workspace_mut(&mut self.workspaces)
         {
-            ws.tag = Some(tag);
+            if ws.tag != Some(tag) {
+                ws.show_tag(&tag);
+            }
             self.update_static();
         }
 
@@ -205,6 +232,10 @@ impl<H: Handle> State<H> {
         if !found.is_managed() {
             return None;
         }
+        // Hidden windows have to be shown before they can be focused.
+        if found.is_hidden() {
+            return None;
+        }
         let previous = self.focus_manager.window(&self.windows);
         // No new history if no change.
         if let Some(previous) = previous {
@@ -224,13 +255,20 @@ impl<H: Handle> State<H> {
         // Add this focus change to the history.
         self.focus_manager.window_history.push_front(Some(*handle));
 
+        // The window got the attention it asked for, the display server clears its hints.
+        let previous_window = previous.cloned();
+        let window = self.windows.iter_mut().find(|w| &w.handle == handle)?;
+        window.urgent = false;
+        let window = window.clone();
+        self.track_urgent(handle);
+
         let act = DisplayAction::WindowTakeFocus {
-            window: found.clone(),
-            previous_window: previous.cloned(),
+            window: window.clone(),
+            previous_window,
         };
         self.actions.push_back(act);
 
-        Some(found.clone())
+        Some(window)
     }
 
     fn focus_workspace_work(&mut self, ws_id: usize) -> bool {
@@ -250,7 +288,7 @@ impl<H: Handle> State<H> {
         false
     }
 
-    fn unfocus_current_window(&mut self) {
+    pub(crate) fn unfocus_current_window(&mut self) {
         if let Some(window) = self.focus_manager.window(&self.windows) {
             self.actions.push_back(DisplayAction::Unfocus(
                 Some(window.handle),
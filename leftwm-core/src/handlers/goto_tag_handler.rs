@@ -29,9 +29,16 @@ impl<H: Handle> State<H> {
 
         if let Some(ws) = self.workspaces.iter_mut().find(|ws| ws.tag == new_tag) {
             ws.tag = Some(old_tag);
+            ws.extra_tags.retain(|&t| t != old_tag);
+        }
+        for ws in &mut self.workspaces {
+            ws.extra_tags.retain(|&t| t != tag_id);
         }
 
-        self.focus_manager.workspace_mut(&mut self.workspaces)?.tag = new_tag;
+        self.focus_manager
+            .workspace_mut(&mut self.workspaces)?
+            .show_tag(&tag_id);
+        self.stop_showing_desktop();
         self.focus_tag(&tag_id);
         self.update_static();
 
@@ -0,0 +1,199 @@
+use super::Manager;
+use crate::config::Config;
+use crate::display_servers::DisplayServer;
+use crate::models::Handle;
+use crate::state::{State, default_tags};
+
+impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
+    /// `output_profile_handler` is called when the display server sends a
+    /// `DisplayEvent::OutputProfileChanged(name)` event. This happens before the screens of the
+    /// newly matching output profile are created, or of the `workspaces` config if `name` is
+    /// `None`.
+    ///
+    /// Returns `true` if changes need to be rendered.
+    pub fn output_profile_handler(&mut self, name: Option<&str>) -> bool {
+        let workspaces = match name {
+            Some(name) => {
+                let Some(profile) = self
+                    .config
+                    .output_profiles()
+                    .into_iter()
+                    .find(|profile| profile.name == name)
+                else {
+                    tracing::warn!("There is no output profile named {:?}", name);
+                    return false;
+                };
+                tracing::info!("Applying output profile {:?}", name);
+                profile.workspaces
+            }
+            None => self.config.workspaces().unwrap_or_default(),
+        };
+
+        self.state
+            .layout_manager
+            .load_workspaces(&self.config, &workspaces);
+        self.state.default_tags = default_tags(&workspaces);
+        self.state.show_default_tags();
+        self.state.update_static();
+        true
+    }
+}
+
+impl<H: Handle> State<H> {
+    /// Shows the default tag of each existing workspace, swapping it with the workspace that
+    /// showed it before. Workspaces created later pick their default tag on creation.
+    fn show_default_tags(&mut self) {
+        let focused_tag = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .and_then(|ws| ws.tag);
+        let mut default_tags: Vec<_> = self.default_tags.iter().map(|(&id, &t)| (id, t)).collect();
+        default_tags.sort_unstable();
+        for (workspace_id, tag) in default_tags {
+            if tag < 1 || tag > self.tags.len_normal() {
+                tracing::warn!("Workspace {} has no default tag {}", workspace_id, tag);
+                continue;
+            }
+            let Some(index) = self.workspaces.iter().position(|ws| ws.id == workspace_id) else {
+                continue;
+            };
+            let old_tag = self.workspaces[index].tag;
+            for ws in &mut self.workspaces {
+                ws.extra_tags.retain(|&t| t != tag);
+                if ws.tag == Some(tag) {
+                    ws.tag = old_tag;
+                    ws.extra_tags.retain(|&t| Some(t) != old_tag);
+                }
+            }
+            self.workspaces[index].show_tag(&tag);
+        }
+
+        let tag = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .and_then(|ws| ws.tag);
+        if let Some(tag) = tag
+            && Some(tag) != focused_tag
+        {
+            self.focus_tag(&tag);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Manager;
+    use crate::config::tests::TestConfig;
+    use crate::config::{OutputProfile, Workspace};
+    use crate::layouts;
+    use crate::models::{BBox, Screen};
+    use leftwm_layouts::layouts::Layouts;
+
+    fn output(name: &str, x: i32) -> Screen<crate::models::MockHandle> {
+        let bbox = BBox {
+            x,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        };
+        Screen::new(bbox, name.to_owned())
+    }
+
+    fn workspace(output: &str, default_layout: &str) -> Workspace {
+        Workspace {
+            output: output.to_owned(),
+            relative: Some(true),
+            width: 1920,
+            height: 1080,
+            default_layout: Some(default_layout.to_owned()),
+            ..Workspace::default()
+        }
+    }
+
+    fn config() -> TestConfig {
+        TestConfig {
+            tags: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            layouts: vec![
+                layouts::MONOCLE.to_string(),
+                layouts::EVEN_VERTICAL.to_string(),
+            ],
+            layout_definitions: Layouts::default().layouts,
+            workspaces: Some(vec![workspace("eDP-1", layouts::EVEN_VERTICAL)]),
+            output_profiles: vec![OutputProfile {
+                name: "docked".to_owned(),
+                outputs: vec!["DP-1".to_owned(), "eDP-1".to_owned()],
+                workspaces: vec![
+                    workspace("DP-1", layouts::MONOCLE),
+                    workspace("DP-1", layouts::FIBONACCI),
+                    workspace("eDP-1", layouts::EVEN_VERTICAL),
+                ],
+            }],
+            ..TestConfig::default()
+        }
+    }
+
+    #[test]
+    fn the_workspaces_of_the_matching_profile_are_created() {
+        let mut manager =
+            Manager::new_with_screens(config(), &[output("eDP-1", 0), output("DP-1", 1920)]);
+
+        assert_eq!(3, manager.state.workspaces.len());
+        let layout_manager = &mut manager.state.layout_manager;
+        assert_eq!(layouts::MONOCLE, &layout_manager.layout(1, 1).name);
+        assert_eq!(layouts::FIBONACCI, &layout_manager.layout(2, 1).name);
+        assert_eq!(layouts::EVEN_VERTICAL, &layout_manager.layout(3, 1).name);
+
+        // Back to the `workspaces` config, eg. after undocking.
+        assert!(manager.output_profile_handler(None));
+        let layout_manager = &mut manager.state.layout_manager;
+        assert_eq!(layouts::EVEN_VERTICAL, &layout_manager.layout(1, 1).name);
+        assert_eq!(layouts::MONOCLE, &layout_manager.layout(2, 1).name);
+    }
+
+    #[test]
+    fn the_workspaces_of_a_profile_show_their_default_tag() {
+        let mut config = config();
+        config.output_profiles[0].workspaces[1].default_tag = Some(1);
+        config.output_profiles[0].workspaces[2].default_tag = Some(3);
+        let mut manager = Manager::new_with_screens(config, &[output("eDP-1", 0)]);
+        assert_eq!(Some(1), manager.state.workspaces[0].tag);
+
+        // Docking re-applies the profile before the new screens are created.
+        assert!(manager.output_profile_handler(Some("docked")));
+        for (i, screen) in [output("DP-1", 1920), output("DP-1", 3840)]
+            .into_iter()
+            .enumerate()
+        {
+            let mut screen = screen;
+            screen.id = Some(i + 2);
+            manager.screen_create_handler(screen);
+        }
+
+        let tags: Vec<_> = manager.state.workspaces.iter().map(|ws| ws.tag).collect();
+        assert_eq!(vec![Some(2), Some(1), Some(3)], tags);
+    }
+
+    #[test]
+    fn new_workspaces_keep_the_default_tags_of_the_others_free() {
+        let mut config = config();
+        config.output_profiles[0].workspaces[1].default_tag = Some(1);
+        config.output_profiles[0].workspaces[2].default_tag = Some(3);
+        let manager =
+            Manager::new_with_screens(config, &[output("eDP-1", 0), output("DP-1", 1920)]);
+
+        let tags: Vec<_> = manager.state.workspaces.iter().map(|ws| ws.tag).collect();
+        assert_eq!(vec![Some(2), Some(1), Some(3)], tags);
+    }
+
+    #[test]
+    fn the_workspaces_config_is_used_when_no_profile_matches() {
+        let mut manager = Manager::new_with_screens(config(), &[output("eDP-1", 0)]);
+
+        assert_eq!(1, manager.state.workspaces.len());
+        assert_eq!(
+            layouts::EVEN_VERTICAL,
+            &manager.state.layout_manager.layout(1, 1).name
+        );
+        assert!(!manager.output_profile_handler(Some("unknown")));
+    }
+}
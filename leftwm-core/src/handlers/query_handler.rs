@@ -0,0 +1,218 @@
+use super::{Command, Config, Manager};
+use crate::display_servers::DisplayServer;
+use crate::models::Handle;
+use crate::models::dto::{DisplayScratchPad, DisplayTag, ManagerState};
+use crate::utils::command_socket::{CommandError, CommandResult, ErrorKind};
+use serde::Serialize;
+
+impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
+    /// Answers read-only queries with a JSON payload.
+    ///
+    /// Returns `None` if `command` is not a query.
+    pub fn query_handler(&self, command: &Command<H>) -> Option<CommandResult> {
+        let state = &self.state;
+        let payload = match command {
+            Command::GetWindows => to_payload(ManagerState::from(state).windows),
+            Command::GetWorkspaces => to_payload(ManagerState::from(state).viewports),
+            Command::GetTags => to_payload(self.tags()),
+            Command::GetLayouts => to_payload(
+                state
+                    .layout_manager
+                    .available_layouts()
+                    .iter()
+                    .map(|layout| &layout.name)
+                    .collect::<Vec<_>>(),
+            ),
+            Command::GetScratchpads => to_payload(self.scratchpads()),
+            Command::GetFocused => to_payload(
+                ManagerState::from(state)
+                    .windows
+                    .into_iter()
+                    .find(|w| w.focused),
+            ),
+            Command::GetConfig => Ok(self.config.as_json()),
+            Command::WithCriteria { criteria, command } if **command == Command::GetWindows => {
+                let handles: Vec<_> = state
+                    .windows
+                    .iter()
+                    .filter(|w| criteria.matches(w, state))
+                    .filter_map(|w| serde_json::to_value(w.handle).ok())
+                    .collect();
+                to_payload(
+                    ManagerState::from(state)
+                        .windows
+                        .into_iter()
+                        .filter(|w| handles.contains(&w.handle))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            Command::WithCriteria { criteria, command }
+                if matches!(
+                    **command,
+                    Command::GetWorkspaces
+                        | Command::GetTags
+                        | Command::GetLayouts
+                        | Command::GetScratchpads
+                        | Command::GetFocused
+                        | Command::GetConfig
+                ) =>
+            {
+                Err(CommandError::new(
+                    ErrorKind::InvalidArguments,
+                    format!("Only GetWindows can be limited to the windows matching {criteria}"),
+                ))
+            }
+            _ => return None,
+        };
+        Some(payload.map(Some))
+    }
+
+    fn tags(&self) -> Vec<DisplayTag> {
+        let state = &self.state;
+        state
+            .tags
+            .normal()
+            .iter()
+            .map(|tag| {
+                let windows = state.windows.iter().filter(|w| w.has_tag(&tag.id));
+                DisplayTag {
+                    id: tag.id,
+                    label: tag.label.clone(),
+                    workspace: state
+                        .workspaces
+                        .iter()
+                        .find(|ws| ws.has_tag(&tag.id))
+                        .map(|ws| ws.id),
+                    windows: windows.clone().filter(|w| w.is_managed()).count(),
+                    urgent: windows.clone().any(|w| w.urgent),
+                }
+            })
+            .collect()
+    }
+
+    fn scratchpads(&self) -> Vec<DisplayScratchPad> {
+        let state = &self.state;
+        state
+            .scratchpads
+            .iter()
+            .map(|scratchpad| {
+                let windows: Vec<_> = state
+                    .active_scratchpads
+                    .get(&scratchpad.name)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|pid| state.windows.iter().find(|w| w.pid == Some(*pid)))
+                    .collect();
+                DisplayScratchPad {
+                    name: scratchpad.name.clone().into(),
+                    command: scratchpad.value.clone(),
+                    visible: windows.iter().any(|w| {
+                        state
+                            .workspaces
+                            .iter()
+                            .any(|ws| ws.is_displaying(w) && w.visible())
+                    }),
+                    windows: windows
+                        .iter()
+                        .filter_map(|w| serde_json::to_value(w.handle).ok())
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+fn to_payload(value: impl Serialize) -> Result<serde_json::Value, CommandError> {
+    serde_json::to_value(value)
+        .map_err(|err| CommandError::new(ErrorKind::ExecutionFailed, err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Criteria, MockHandle, Screen, Window, WindowHandle};
+
+    fn payload<C: Config, SERVER: DisplayServer<MockHandle>>(
+        manager: &Manager<MockHandle, C, SERVER>,
+        command: &Command<MockHandle>,
+    ) -> serde_json::Value {
+        manager
+            .query_handler(command)
+            .expect("command is a query")
+            .expect("query succeeds")
+            .expect("query has a payload")
+    }
+
+    #[test]
+    fn non_queries_are_not_answered() {
+        let manager = Manager::new_test(vec!["1".to_string()]);
+        assert!(manager.query_handler(&Command::CloseWindow).is_none());
+    }
+
+    #[test]
+    fn only_get_windows_takes_criteria() {
+        let manager = Manager::new_test(vec!["1".to_string()]);
+        let command = Command::WithCriteria {
+            criteria: Criteria::parse("class=firefox").unwrap(),
+            command: Box::new(Command::GetTags),
+        };
+        let error = manager.query_handler(&command).unwrap().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::InvalidArguments);
+    }
+
+    #[test]
+    fn get_tags_reports_windows_and_workspace() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::<MockHandle>(1), None, None),
+            -1,
+            -1,
+        );
+
+        let tags: Vec<DisplayTag> =
+            serde_json::from_value(payload(&manager, &Command::GetTags)).unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!((tags[0].workspace, tags[0].windows), (Some(1), 1));
+        assert_eq!((tags[1].workspace, tags[1].windows), (None, 0));
+    }
+
+    #[test]
+    fn get_focused_returns_the_focused_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::<MockHandle>(1), None, None),
+            -1,
+            -1,
+        );
+        manager.window_created_handler(
+            Window::new(WindowHandle::<MockHandle>(2), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle(2));
+
+        let focused = payload(&manager, &Command::GetFocused);
+        assert_eq!(focused["handle"], serde_json::json!(2));
+        assert_eq!(focused["focused"], serde_json::json!(true));
+    }
+
+    #[test]
+    fn get_layouts_lists_available_layouts() {
+        let manager = Manager::new_test(vec!["1".to_string()]);
+        let names: Vec<String> =
+            serde_json::from_value(payload(&manager, &Command::GetLayouts)).unwrap();
+        assert!(!names.is_empty());
+        assert_eq!(
+            names,
+            manager
+                .state
+                .layout_manager
+                .available_layouts()
+                .iter()
+                .map(|l| l.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+}
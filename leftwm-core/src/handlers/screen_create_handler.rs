@@ -1,3 +1,4 @@
+use super::window_handler::update_workspace_avoid_list;
 use super::{Manager, Screen, Workspace};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
@@ -5,12 +6,22 @@ use crate::models::Handle;
 
 impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     /// `screen_create_handler` is called when the display server sends a
-    /// `DisplayEvent::ScreenCreate(screen)` event. This happens at initialization.
+    /// `DisplayEvent::ScreenCreate(screen)` event. This happens at initialization and when a
+    /// monitor is plugged in.
     ///
     /// Returns `true` if changes need to be rendered.
-    pub fn screen_create_handler(&mut self, screen: Screen<H>) -> bool {
+    pub fn screen_create_handler(&mut self, mut screen: Screen<H>) -> bool {
         tracing::trace!("Screen create: {:?}", screen);
 
+        // The screen replaces the last workspace, which was kept when its screen was destroyed.
+        if let Some(orphan) = self.state.orphaned_workspace.take() {
+            if screen.id == Some(orphan) {
+                return self.screen_update_handler(screen);
+            }
+            self.screen_create_handler(screen);
+            return self.screen_destroy_handler(orphan);
+        }
+
         let tag_index = self.state.workspaces.len();
         let tag_len = self.state.tags.len_normal();
 
@@ -21,6 +32,8 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             Some(set_id) => set_id,
         };
 
+        screen.id = Some(workspace_id);
+
         let mut new_workspace = Workspace::new(screen.bbox, workspace_id);
         if self.state.workspaces.len() >= tag_len {
             tracing::warn!(
@@ -29,13 +42,46 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         }
         new_workspace.load_config(&self.config);
 
-        // Make sure there are enough tags for this new screen.
-        let next_id = if tag_len > tag_index {
-            tag_index + 1
-        } else {
-            // Add a new tag for the workspace.
-            self.state.tags.add_new_unlabeled()
+        // Make sure there are enough tags for this new screen. A screen plugged in later takes a
+        // tag no other workspace shows, and gets it back if it was merged into another view. The
+        // default tags of other workspaces are only taken if no other tag is left.
+        let shown = |id| self.state.workspaces.iter().any(|ws| ws.tag == Some(id));
+        let reserved = |id| {
+            (self.state.default_tags.iter()).any(|(&ws, &tag)| ws != workspace_id && tag == id)
+        };
+        let default_tag = self
+            .state
+            .default_tags
+            .get(&workspace_id)
+            .copied()
+            .filter(|id| (1..=tag_len).contains(id));
+        let free: Vec<_> = (tag_index + 1..=tag_len)
+            .chain(1..=tag_index)
+            .filter(|&id| !shown(id))
+            .collect();
+        let next_id = match default_tag {
+            Some(tag) if !shown(tag) => tag,
+            _ => match free.iter().find(|&&id| !reserved(id)).or(free.first()) {
+                Some(&id) => id,
+                // Add a new tag for the workspace.
+                None => self.state.tags.add_new_unlabeled(),
+            },
         };
+        // The default tag is taken from the workspace showing it, which gets the free tag instead.
+        let next_id = match default_tag {
+            Some(tag) if tag != next_id => {
+                for workspace in &mut self.state.workspaces {
+                    if workspace.tag == Some(tag) {
+                        workspace.tag = Some(next_id);
+                    }
+                }
+                tag
+            }
+            _ => next_id,
+        };
+        for workspace in &mut self.state.workspaces {
+            workspace.extra_tags.retain(|&id| id != next_id);
+        }
 
         self.state.focus_workspace(&new_workspace); // focus_workspace is called.
         self.state.focus_tag(&next_id);
@@ -43,6 +89,9 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         self.state.workspaces.push(new_workspace.clone());
         self.state.screens.push(screen);
         self.state.focus_workspace(&new_workspace); // focus_workspace is called again.
+
+        // Docks may already be shown on the screen.
+        update_workspace_avoid_list(&mut self.state);
         false
     }
 }
@@ -98,4 +147,20 @@ mod tests {
         assert!(manager.state.workspaces[2].has_tag(&3));
         assert!(manager.state.workspaces[3].has_tag(&4));
     }
+
+    #[test]
+    fn a_screen_plugged_in_later_takes_a_tag_no_workspace_shows() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        manager.state.goto_tag_handler(3);
+        manager.state.workspaces[0].extra_tags.push(2);
+
+        manager.screen_create_handler(Screen::default());
+
+        assert!(manager.state.workspaces[2].has_tag(&2));
+        assert!(!manager.state.workspaces[0].has_tag(&2));
+        assert_eq!(manager.state.tags.len_normal(), 3);
+    }
 }
@@ -0,0 +1,164 @@
+use super::Manager;
+use super::window_handler::update_workspace_avoid_list;
+use crate::config::Config;
+use crate::display_servers::DisplayServer;
+use crate::models::{Handle, WorkspaceId};
+
+impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
+    /// `screen_destroy_handler` is called when the display server sends a
+    /// `DisplayEvent::ScreenDestroy(id)` event. This happens when a monitor is unplugged.
+    ///
+    /// The tags the workspace displayed, and so their windows, are taken over by the focused
+    /// workspace, or the first one if the removed workspace was focused. Tags are never removed.
+    /// The last workspace is kept until the next screen created replaces it.
+    ///
+    /// Returns `true` if changes need to be rendered.
+    pub fn screen_destroy_handler(&mut self, workspace_id: WorkspaceId) -> bool {
+        tracing::trace!("Screen destroy: {}", workspace_id);
+
+        let state = &mut self.state;
+        let Some(index) = state.workspaces.iter().position(|ws| ws.id == workspace_id) else {
+            return false;
+        };
+        if state.workspaces.len() == 1 {
+            tracing::info!("Keeping the last workspace until a screen replaces it.");
+            state.orphaned_workspace = Some(workspace_id);
+            return false;
+        }
+
+        let was_focused = state.focus_manager.workspace_history.front() == Some(&index);
+        let removed = state.workspaces.remove(index);
+        state.screens.retain(|s| s.id != Some(workspace_id));
+
+        // The history holds indices into `workspaces`, which moved down by one.
+        let history = &mut state.focus_manager.workspace_history;
+        history.retain(|&i| i != index);
+        for i in history.iter_mut().filter(|i| **i > index) {
+            *i -= 1;
+        }
+
+        let heir_index = history.front().copied().unwrap_or_default();
+        let heir = &mut state.workspaces[heir_index];
+        for tag in removed.tags() {
+            if !heir.has_tag(&tag) {
+                heir.extra_tags.push(tag);
+            }
+        }
+        if was_focused && let Some(tag) = heir.tag {
+            state.focus_tag(&tag);
+        }
+
+        // Docks of the removed screen no longer take space from any workspace.
+        update_workspace_avoid_list(state);
+        state.update_static();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BBox, MockHandle, Screen, Window, WindowHandle};
+
+    fn screen(x: i32) -> Screen<MockHandle> {
+        let bbox = BBox {
+            x,
+            y: 0,
+            width: 800,
+            height: 600,
+        };
+        Screen::new(bbox, String::new())
+    }
+
+    #[test]
+    fn destroying_the_focused_screen_shows_its_tag_on_another_workspace() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(screen(0));
+        manager.screen_create_handler(screen(800));
+        for (handle, tag) in [(1, 1), (2, 2)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_tag(&2);
+
+        assert!(manager.screen_destroy_handler(2));
+
+        assert_eq!(manager.state.workspaces.len(), 1);
+        assert_eq!(manager.state.screens.len(), 1);
+        let workspace = &manager.state.workspaces[0];
+        assert_eq!(workspace.tags().collect::<Vec<_>>(), [1, 2]);
+        assert!(
+            manager
+                .state
+                .focus_manager
+                .workspace_history
+                .iter()
+                .all(|&i| i == 0)
+        );
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+
+        manager.update_windows();
+        assert!(manager.state.windows.iter().all(Window::visible));
+        assert_eq!(manager.state.tags.len_normal(), 3);
+    }
+
+    #[test]
+    fn destroying_another_screen_keeps_the_focus() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        for x in [0, 800, 1600] {
+            manager.screen_create_handler(screen(x));
+        }
+        let focused = manager.state.workspaces[2].clone();
+        manager.state.focus_workspace(&focused);
+
+        assert!(manager.screen_destroy_handler(1));
+
+        let workspace = manager
+            .state
+            .focus_manager
+            .workspace(&manager.state.workspaces);
+        assert_eq!(workspace.map(|ws| ws.id), Some(3));
+        assert!(workspace.is_some_and(|ws| ws.has_tag(&1) && ws.has_tag(&3)));
+    }
+
+    #[test]
+    fn the_last_screen_is_never_destroyed() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(screen(0));
+
+        assert!(!manager.screen_destroy_handler(1));
+        assert!(!manager.screen_destroy_handler(5));
+        assert_eq!(manager.state.workspaces.len(), 1);
+    }
+
+    #[test]
+    fn the_last_screen_is_destroyed_once_another_replaces_it() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(screen(0));
+        let window = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        manager.window_created_handler(window, -1, -1);
+        assert!(!manager.screen_destroy_handler(1));
+
+        let mut replacement = screen(800);
+        replacement.id = Some(2);
+        assert!(manager.screen_create_handler(replacement));
+
+        let ids: Vec<_> = manager.state.workspaces.iter().map(|ws| ws.id).collect();
+        assert_eq!(ids, [2]);
+        assert_eq!(manager.state.screens.len(), 1);
+        assert!(manager.state.workspaces[0].has_tag(&1));
+        manager.update_windows();
+        assert!(manager.state.windows[0].visible());
+
+        // A screen coming back with the id of the kept workspace takes it over.
+        assert!(!manager.screen_destroy_handler(2));
+        let mut same = screen(0);
+        same.id = Some(2);
+        assert!(manager.screen_create_handler(same));
+        assert_eq!(manager.state.workspaces.len(), 1);
+        assert_eq!(manager.state.workspaces[0].xyhw.x(), 0);
+    }
+}
@@ -0,0 +1,88 @@
+use super::window_handler::update_workspace_avoid_list;
+use super::{Manager, Screen};
+use crate::config::Config;
+use crate::display_servers::DisplayServer;
+use crate::models::Handle;
+
+impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
+    /// `screen_update_handler` is called when the display server sends a
+    /// `DisplayEvent::ScreenUpdate(screen)` event. This happens when a monitor is moved or
+    /// changes resolution.
+    ///
+    /// Returns `true` if changes need to be rendered.
+    pub fn screen_update_handler(&mut self, screen: Screen<H>) -> bool {
+        tracing::trace!("Screen update: {:?}", screen);
+
+        let state = &mut self.state;
+        let Some(workspace) = state
+            .workspaces
+            .iter_mut()
+            .find(|ws| Some(ws.id) == screen.id)
+        else {
+            return false;
+        };
+        workspace.set_bbox(screen.bbox);
+        match state.screens.iter_mut().find(|s| s.id == screen.id) {
+            Some(old) => *old = screen,
+            None => state.screens.push(screen),
+        }
+
+        // Docks may have ended up on another workspace.
+        update_workspace_avoid_list(state);
+        state.update_static();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BBox, MockHandle, Window, WindowHandle, WindowType, XyhwBuilder};
+
+    #[test]
+    fn updating_a_screen_resizes_its_workspace_around_docks() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut dock = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        dock.r#type = WindowType::Dock;
+        dock.strut = Some(
+            XyhwBuilder {
+                x: 0,
+                y: 0,
+                w: 1920,
+                h: 20,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        manager.state.windows.push(dock);
+
+        let bbox = BBox {
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        };
+        let mut screen = Screen::new(bbox, "eDP-1".to_string());
+        screen.id = Some(1);
+        assert!(manager.screen_update_handler(screen));
+
+        let workspace = &manager.state.workspaces[0];
+        assert_eq!((workspace.xyhw.w(), workspace.xyhw.h()), (1920, 1080));
+        assert_eq!(workspace.xyhw_avoided.y(), 20);
+        assert_eq!(workspace.xyhw_avoided.h(), 1060);
+        assert_eq!(manager.state.screens[0].output, "eDP-1");
+    }
+
+    #[test]
+    fn updating_an_unknown_screen_does_nothing() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let screen = Screen {
+            id: Some(7),
+            ..Screen::default()
+        };
+        assert!(!manager.screen_update_handler(screen));
+        assert_eq!(manager.state.screens.len(), 1);
+    }
+}
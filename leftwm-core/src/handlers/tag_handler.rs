@@ -0,0 +1,505 @@
+use crate::display_action::DisplayAction;
+use crate::models::{Handle, TagId, WindowHandle};
+use crate::state::State;
+use std::cmp::Ordering;
+
+impl<H: Handle> State<H> {
+    /// Appends a new normal tag labelled `label`.
+    ///
+    /// Returns the ID of the new tag.
+    pub fn add_tag(&mut self, label: &str) -> TagId {
+        let tag_id = self.tags.add_new_at_runtime(label);
+        self.tags_changed();
+        tag_id
+    }
+
+    /// Removes the normal tag `tag_id`. Its windows are moved to the previous tag, or the next
+    /// one when removing the first tag. A workspace displaying the removed tag displays the tag
+    /// receiving the windows instead, or any tag not displayed yet.
+    ///
+    /// Returns `None` if the tag does not exist or every workspace would not have a tag left.
+    pub fn remove_tag(&mut self, tag_id: TagId) -> Option<bool> {
+        let len = self.tags.len_normal();
+        if tag_id < 1 || tag_id > len || len < 2 || len <= self.workspaces.len() {
+            return None;
+        }
+        let target = if tag_id > 1 { tag_id - 1 } else { 2 };
+
+        for window in self.windows.iter_mut().filter(|w| w.tag == Some(tag_id)) {
+            let extra_tags = std::mem::take(&mut window.extra_tags);
+            window.untag();
+            window.tag(&target);
+            window.extra_tags = extra_tags.into_iter().filter(|&t| t != target).collect();
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.actions.push_back(act);
+        }
+
+        let displayed = |id: TagId| self.workspaces.iter().any(|ws| ws.tag == Some(id));
+        if let Some(index) = self.workspaces.iter().position(|ws| ws.tag == Some(tag_id)) {
+            let replacement = if displayed(target) {
+                (1..=len).find(|&id| id != tag_id && !displayed(id))?
+            } else {
+                target
+            };
+            self.workspaces[index].tag = Some(replacement);
+        }
+
+        self.renumber_tags(|id| match id.cmp(&tag_id) {
+            Ordering::Less => Some(id),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(id - 1),
+        });
+        self.tags.remove(tag_id);
+        self.tags_changed();
+        Some(true)
+    }
+
+    /// Changes the label of the normal tag `tag_id`.
+    ///
+    /// Returns `None` if the tag does not exist.
+    pub fn rename_tag(&mut self, tag_id: TagId, label: &str) -> Option<bool> {
+        if tag_id > self.tags.len_normal() {
+            return None;
+        }
+        label.clone_into(&mut self.tags.get_mut(tag_id)?.label);
+        self.tags_changed();
+        Some(false)
+    }
+
+    /// Moves the normal tag `tag_id` to `position`, shifting the tags in between.
+    /// Windows, workspaces and layouts keep their tag, only its ID changes.
+    ///
+    /// Returns `None` if the tag or position does not exist.
+    pub fn move_tag(&mut self, tag_id: TagId, position: TagId) -> Option<bool> {
+        let len = self.tags.len_normal();
+        if tag_id < 1 || tag_id > len || position < 1 || position > len {
+            return None;
+        }
+
+        self.renumber_tags(|id| {
+            Some(if id == tag_id {
+                position
+            } else if tag_id < id && id <= position {
+                id - 1
+            } else if position <= id && id < tag_id {
+                id + 1
+            } else {
+                id
+            })
+        });
+        self.tags.move_to(tag_id, position)?;
+        self.tags_changed();
+        Some(true)
+    }
+
+    /// Adds the normal tag `tag_id` to the tags the focused workspace displays, or removes it if
+    /// it is displayed already.
+    ///
+    /// Returns `None` if the tag does not exist, is displayed by another workspace or is the only
+    /// tag left in view.
+    pub fn toggle_tag_view(&mut self, tag_id: TagId) -> Option<bool> {
+        if tag_id < 1 || tag_id > self.tags.len_normal() {
+            return None;
+        }
+        let focused_id = self.focus_manager.workspace(&self.workspaces)?.id;
+        if self
+            .workspaces
+            .iter()
+            .any(|ws| ws.id != focused_id && ws.has_tag(&tag_id))
+        {
+            return None;
+        }
+
+        let workspace = self.focus_manager.workspace_mut(&mut self.workspaces)?;
+        let old_tag = workspace.tag;
+        workspace.toggle_tag(tag_id)?;
+        if let Some(new_tag) = workspace.tag.filter(|&t| Some(t) != old_tag) {
+            self.focus_tag(&new_tag);
+        }
+        self.refocus_in_view();
+        self.update_static();
+        Some(true)
+    }
+
+    /// Puts `window`, or the focused window if `None`, on the normal tag `tag_id` too, or takes
+    /// it off that tag if it is there already.
+    ///
+    /// Returns `None` if there is no such window, the tag does not exist or is the only tag of
+    /// the window.
+    pub fn toggle_window_tag(
+        &mut self,
+        window: Option<WindowHandle<H>>,
+        tag_id: TagId,
+    ) -> Option<bool> {
+        if tag_id < 1 || tag_id > self.tags.len_normal() {
+            return None;
+        }
+        let handle = match window {
+            Some(handle) => handle,
+            None => self.focus_manager.window(&self.windows)?.handle,
+        };
+        let window = self.windows.iter_mut().find(|w| w.handle == handle)?;
+        let old_tag = window.tag;
+        window.toggle_tag(tag_id)?;
+        if window.tag != old_tag {
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.actions.push_back(act);
+        }
+        self.refocus_in_view();
+        Some(true)
+    }
+
+    /// Moves the focus away from the focused window if the focused workspace no longer
+    /// displays it.
+    fn refocus_in_view(&mut self) {
+        let Some(workspace) = self.focus_manager.workspace(&self.workspaces) else {
+            return;
+        };
+        if self
+            .focus_manager
+            .window(&self.windows)
+            .is_none_or(|w| workspace.is_displaying(w))
+        {
+            return;
+        }
+        let handle = self
+            .windows
+            .iter()
+            .find(|w| workspace.is_managed(w) && w.can_focus())
+            .map(|w| w.handle);
+        match handle {
+            Some(handle) => self.focus_window(&handle),
+            None => self.unfocus_current_window(),
+        }
+    }
+
+    /// Gives every normal tag the ID returned by `new_id`, `None` for a tag about to be removed.
+    ///
+    /// Has to be called before `self.tags` changes. Hidden tags are left alone.
+    fn renumber_tags(&mut self, new_id: impl Fn(TagId) -> Option<TagId>) {
+        let len = self.tags.len_normal();
+        let new_id = |id: TagId| if id <= len { new_id(id) } else { Some(id) };
+
+        for window in &mut self.windows {
+            let old_tag = window.tag;
+            let mut tags: Vec<TagId> = window.tags().filter_map(new_id).collect();
+            if window.tags().eq(tags.iter().copied()) {
+                continue;
+            }
+            // Retag from scratch, so removed tags do not stay on the window.
+            window.untag();
+            if !tags.is_empty() {
+                window.tag(&tags.remove(0));
+                window.extra_tags = tags;
+            }
+            if window.tag != old_tag {
+                let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+                self.actions.push_back(act);
+            }
+        }
+        for workspace in &mut self.workspaces {
+            workspace.tag = workspace.tag.and_then(new_id);
+            let extra_tags = workspace.extra_tags.iter().copied().filter_map(new_id);
+            workspace.extra_tags = extra_tags.filter(|&id| Some(id) != workspace.tag).collect();
+        }
+        let focus_manager = &mut self.focus_manager;
+        focus_manager.tag_history = std::mem::take(&mut focus_manager.tag_history)
+            .into_iter()
+            .filter_map(new_id)
+            .collect();
+        focus_manager.tags_last_window = std::mem::take(&mut focus_manager.tags_last_window)
+            .into_iter()
+            .filter_map(|(id, handle)| Some((new_id(id)?, handle)))
+            .collect();
+        self.layout_manager.renumber_tags(new_id);
+        self.split_trees = std::mem::take(&mut self.split_trees)
+            .into_iter()
+            .filter_map(|(id, tree)| Some((new_id(id)?, tree)))
+            .collect();
+        self.column_strips = std::mem::take(&mut self.column_strips)
+            .into_iter()
+            .filter_map(|(id, strip)| Some((new_id(id)?, strip)))
+            .collect();
+    }
+
+    /// Lets the display server know about the new tags, and refocuses the current tag in case
+    /// it was removed or its ID changed.
+    fn tags_changed(&mut self) {
+        let labels = self.tags.normal().iter().map(|t| t.label.clone()).collect();
+        self.actions
+            .push_back(DisplayAction::SetDesktopNames(labels));
+
+        let current = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .and_then(|ws| ws.tag);
+        if let Some(tag_id) = current {
+            if self.focus_manager.tag(0) == Some(tag_id) {
+                let act = DisplayAction::SetCurrentTags(Some(tag_id));
+                self.actions.push_back(act);
+            } else {
+                self.focus_tag(&tag_id);
+            }
+        }
+        self.update_static();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{BBox, MockHandle, Screen, Window, WindowHandle};
+    use crate::state::State;
+    use crate::{Command, Manager};
+
+    fn window_tags(state: &State<MockHandle>) -> Vec<(i32, Option<usize>)> {
+        state.windows.iter().map(|w| (w.handle.0, w.tag)).collect()
+    }
+
+    #[test]
+    fn removing_a_tag_migrates_its_windows_and_renumbers_the_rest() {
+        let tags = ["1", "2", "3", "4"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for (handle, tag) in [(1, 2), (2, 3), (3, 4)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.goto_tag_handler(3);
+
+        assert_eq!(manager.state.remove_tag(3), Some(true));
+
+        assert_eq!(
+            window_tags(&manager.state),
+            [(1, Some(2)), (2, Some(2)), (3, Some(3))]
+        );
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        let labels: Vec<_> = manager
+            .state
+            .tags
+            .normal()
+            .iter()
+            .map(|t| &t.label)
+            .collect();
+        assert_eq!(labels, ["1", "2", "4"]);
+    }
+
+    #[test]
+    fn tags_added_at_runtime_are_kept_on_reload() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let window = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        manager.window_created_handler(window, -1, -1);
+        assert_eq!(manager.state.add_tag("added"), 3);
+        manager.state.goto_tag_handler(3);
+        manager.command_handler(&Command::SendWindowToTag {
+            window: Some(WindowHandle(1)),
+            tag: 3,
+        });
+
+        // The config gained a tag meanwhile, the added one follows it.
+        let tags = ["1", "2", "new"].map(String::from).to_vec();
+        let mut reloaded = Manager::new_test(tags);
+        reloaded.screen_create_handler(Screen::default());
+        let window = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        reloaded.window_created_handler(window, -1, -1);
+        reloaded.state.restore_state(&manager.state);
+
+        let labels: Vec<_> = (reloaded.state.tags.normal().iter())
+            .map(|t| &t.label)
+            .collect();
+        assert_eq!(labels, ["1", "2", "new", "added"]);
+        assert_eq!(window_tags(&reloaded.state), [(1, Some(4))]);
+        assert_eq!(reloaded.state.workspaces[0].tag, Some(4));
+        assert_eq!(reloaded.state.focus_manager.tag(0), Some(4));
+    }
+
+    #[test]
+    fn the_last_tag_of_a_workspace_cannot_be_removed() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+
+        assert_eq!(manager.state.remove_tag(1), None);
+        assert_eq!(manager.state.tags.len_normal(), 2);
+    }
+
+    #[test]
+    fn moving_a_tag_keeps_its_windows() {
+        let tags = ["a", "b", "c"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for (handle, tag) in [(1, 1), (2, 3)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+
+        assert_eq!(manager.state.move_tag(3, 1), Some(true));
+
+        assert_eq!(window_tags(&manager.state), [(1, Some(2)), (2, Some(1))]);
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        let labels: Vec<_> = manager
+            .state
+            .tags
+            .normal()
+            .iter()
+            .map(|t| &t.label)
+            .collect();
+        assert_eq!(labels, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn toggling_a_tag_view_tiles_the_windows_of_both_tags() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for (handle, tag) in [(1, 1), (2, 2), (3, 3)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+
+        assert_eq!(manager.state.toggle_tag_view(2), Some(true));
+        manager.update_windows();
+        let visible: Vec<_> = manager.state.windows.iter().map(Window::visible).collect();
+        assert_eq!(visible, [true, true, false]);
+        let (first, second) = (&manager.state.windows[0], &manager.state.windows[1]);
+        assert_ne!(first.normal.x(), second.normal.x());
+
+        // Hiding the main tag hands its place over to the remaining one.
+        assert_eq!(manager.state.toggle_tag_view(1), Some(true));
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        assert!(manager.state.workspaces[0].extra_tags.is_empty());
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.map(|w| w.handle), Some(WindowHandle(2)));
+
+        assert_eq!(manager.state.toggle_tag_view(2), None);
+        assert_eq!(manager.state.toggle_tag_view(4), None);
+    }
+
+    #[test]
+    fn a_window_toggled_onto_another_tag_shows_on_both() {
+        let tags = ["1", "2"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        let window = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        manager.window_created_handler(window, -1, -1);
+
+        assert_eq!(manager.state.toggle_window_tag(None, 2), Some(true));
+        assert_eq!(manager.state.windows[0].tags().collect::<Vec<_>>(), [1, 2]);
+        manager.state.goto_tag_handler(2);
+        manager.update_windows();
+        assert!(manager.state.windows[0].visible());
+
+        // Taking the window off the tag in view moves the focus away from it.
+        assert_eq!(manager.state.toggle_window_tag(None, 2), Some(true));
+        assert_eq!(manager.state.windows[0].tags().collect::<Vec<_>>(), [1]);
+        assert!(
+            manager
+                .state
+                .focus_manager
+                .window(&manager.state.windows)
+                .is_none()
+        );
+        assert_eq!(manager.state.toggle_window_tag(None, 1), None);
+    }
+
+    #[test]
+    fn focus_cycles_through_the_windows_of_a_toggled_in_tag() {
+        let tags = ["1", "2"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for (handle, tag) in [(1, 1), (2, 2), (3, 1)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+        assert_eq!(manager.state.toggle_tag_view(2), Some(true));
+        manager.state.focus_window(&WindowHandle(1));
+
+        let mut focused = vec![];
+        for _ in 0..3 {
+            manager.command_handler(&Command::FocusWindowDown);
+            let window = manager.state.focus_manager.window(&manager.state.windows);
+            focused.extend(window.map(|w| w.handle.0));
+        }
+        assert_eq!(focused, [2, 3, 1]);
+
+        manager.command_handler(&Command::FocusWindowTop { swap: false });
+        let window = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(window.map(|w| w.handle), Some(WindowHandle(1)));
+    }
+
+    #[test]
+    fn windows_move_across_a_toggled_in_tag() {
+        let tags = ["1", "2"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for (handle, tag) in [(1, 1), (2, 2)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+        assert_eq!(manager.state.toggle_tag_view(2), Some(true));
+        manager.state.focus_window(&WindowHandle(1));
+
+        manager.command_handler(&Command::MoveWindowDown);
+        let order: Vec<_> = manager.state.windows.iter().map(|w| w.handle.0).collect();
+        assert_eq!(order, [2, 1]);
+        assert_eq!(manager.state.windows[1].tag, Some(1));
+    }
+
+    #[test]
+    fn a_window_on_two_displayed_tags_is_laid_out_once() {
+        let tags = ["1", "2"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        for x in [0, 1000] {
+            let bbox = BBox {
+                x,
+                y: 0,
+                width: 1000,
+                height: 800,
+            };
+            manager.screen_create_handler(Screen::new(bbox, String::new()));
+        }
+        let mut window = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        window.tag = Some(2);
+        window.extra_tags = vec![1];
+        manager.window_created_handler(window, -1, -1);
+        manager.update_windows();
+
+        // The workspace displaying the main tag of the window lays it out.
+        let (first, second) = (&manager.state.workspaces[0], &manager.state.workspaces[1]);
+        let window = &manager.state.windows[0];
+        assert_eq!((first.tag, second.tag), (Some(1), Some(2)));
+        assert!(!first.lays_out(window, &manager.state.workspaces));
+        assert!(second.lays_out(window, &manager.state.workspaces));
+        assert!(window.x() >= 1000);
+    }
+
+    #[test]
+    fn removed_tags_do_not_stay_on_windows() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for (handle, tag, extra_tag) in [(1, 1, 3), (2, 2, 3)] {
+            let mut window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            window.tag = Some(tag);
+            window.extra_tags = vec![extra_tag];
+            manager.window_created_handler(window, -1, -1);
+        }
+
+        assert_eq!(manager.state.remove_tag(2), Some(true));
+        let tags = |manager: &Manager<_, _, _>| -> Vec<Vec<usize>> {
+            let windows = &manager.state.windows;
+            windows.iter().map(|w| w.tags().collect()).collect()
+        };
+        assert_eq!(tags(&manager), [vec![1, 2], vec![1, 2]]);
+
+        assert_eq!(manager.state.remove_tag(2), Some(true));
+        assert_eq!(tags(&manager), [vec![1], vec![1]]);
+    }
+}
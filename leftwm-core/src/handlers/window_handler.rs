@@ -4,7 +4,9 @@ use crate::config::{Config, InsertBehavior};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::{self, MAIN_AND_VERT_STACK};
-use crate::models::{Handle, WindowHandle, WindowState, Xyhw};
+use crate::models::{
+    FocusOnActivationBehaviour, Handle, WindowHandle, WindowState, Xyhw, XyhwBuilder,
+};
 use crate::state::State;
 use crate::utils::helpers;
 use std::env;
@@ -24,6 +26,10 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         // Setup any predefined hooks.
         self.config
             .setup_predefined_window(&mut self.state, &mut window);
+        // Window rules may ignore windows asking for attention from the start.
+        if window.urgency == Some(FocusOnActivationBehaviour::DoNothing) {
+            window.urgent = false;
+        }
 
         // TODO: this seems very janky.
         let mut is_first = false;
@@ -39,7 +45,11 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             &mut on_same_tag,
         );
         self.config.load_window(&mut window);
-        insert_window(&mut self.state, &mut window, &layout);
+        let swallow = window.swallow.unwrap_or(self.config.swallow_terminals());
+        match find_swallowed_terminal(&self.state, &window, swallow) {
+            Some(index) => swallow_terminal(&mut self.state, &mut window, index),
+            None => insert_window(&mut self.state, &mut window, &layout),
+        }
 
         let follow_mouse = self.state.focus_manager.focus_new_windows
             && self.state.focus_manager.behaviour.is_sloppy()
@@ -47,6 +57,11 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             && window.is_managed()
             && on_same_tag;
 
+        // A new window would be hidden right away while the desktop is shown.
+        if window.is_managed() {
+            self.state.stop_showing_desktop();
+        }
+
         // Let the DS know we are managing this window.
         let act = DisplayAction::AddedWindow(window.handle, window.floating(), follow_mouse);
         self.state.actions.push_back(act);
@@ -57,6 +72,10 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             self.state.actions.push_back(act);
         }
 
+        // Windows mapped iconified, eg. when leftwm restarts, can be restored like minimized ones.
+        self.state.track_minimized(&window.handle);
+        self.state.track_urgent(&window.handle);
+
         // Tell the WM to reevaluate the stacking order, so the new window is put in the correct layer
         self.state.sort_windows();
 
@@ -66,7 +85,12 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         // `is_first` and `on_same_tag` are set by `setup_window`
         // TODO: remove focus_new_windows variable from focus_manager,
         // TODO: use self.config.focus_new_windows() instead
-        if (self.state.focus_manager.focus_new_windows || is_first) && on_same_tag {
+        // A window swallowing the focused terminal takes its focus as well.
+        let terminal_focused = window.swallowed.is_some()
+            && self.state.focus_manager.window_history.front() == Some(&window.swallowed);
+        if ((self.state.focus_manager.focus_new_windows || is_first) && on_same_tag)
+            || terminal_focused
+        {
             self.state.focus_window(&window.handle);
         }
 
@@ -102,10 +126,14 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
                 Some(window) => (window.transient, window.floating(), window.visible()),
                 None => return false,
             };
+        let terminal = restore_swallowed_terminal(&mut self.state, handle);
         self.state
             .focus_manager
             .tags_last_window
             .retain(|_, h| h != handle);
+        self.state.marks.retain(|_, h| h != handle);
+        self.state.minimized.retain(|h| h != handle);
+        self.state.urgent.retain(|h| h != handle);
         self.state.windows.retain(|w| &w.handle != handle);
 
         self.state.handle_single_border(self.config.border_width());
@@ -128,7 +156,7 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
                 find_transient_parent(&self.state.windows, transient).map(|p| p.handle)
             {
                 self.state.focus_window(&parent);
-            } else if let Some(handle) = new_handle {
+            } else if let Some(handle) = terminal.or(new_handle) {
                 self.state.focus_window(&handle);
             } else {
                 let act = DisplayAction::Unfocus(Some(*handle), floating);
@@ -151,12 +179,29 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     /// the `DisplayEvent::WindowChange(change)` event.
     ///
     /// Returns true if changes need to be rendered.
-    pub fn window_changed_handler(&mut self, change: WindowChange<H>) -> bool {
+    pub fn window_changed_handler(&mut self, mut change: WindowChange<H>) -> bool {
         let mut changed = false;
         let mut fullscreen_changed = false;
-        let mut above_changed = false;
+        let mut states_changed = false;
         let mut transient_changed = false;
         let strut_changed = change.strut.is_some();
+        let handle = change.handle;
+        let switch_to = apply_urgency_policy(&self.state, &mut change);
+        // A focused window hiding itself hands the focus to its neighbour.
+        let hides_focused = change
+            .states
+            .as_ref()
+            .is_some_and(|states| states.contains(&WindowState::Hidden))
+            && self
+                .state
+                .focus_manager
+                .window(&self.state.windows)
+                .is_some_and(|w| w.handle == change.handle && !w.is_hidden());
+        let next = if hides_focused {
+            self.get_next_or_previous_handle(&change.handle)
+        } else {
+            None
+        };
         let windows = self.state.windows.clone();
         if let Some(window) = self
             .state
@@ -171,8 +216,9 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             if let Some(states) = &change.states {
                 fullscreen_changed =
                     states.contains(&WindowState::Fullscreen) != window.is_fullscreen();
-                above_changed = states.contains(&WindowState::Above)
-                    != window.states.contains(&WindowState::Above);
+                // Any state may put the window in another layer, eg. `Above` or `Below`.
+                states_changed = states.len() != window.states.len()
+                    || states.iter().any(|state| !window.states.contains(state));
             }
             let container = match find_transient_parent(&windows, window.transient) {
                 Some(parent) => Some(parent.exact_xyhw()),
@@ -205,17 +251,33 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             }
         }
 
-        if fullscreen_changed || above_changed || transient_changed {
+        if states_changed || transient_changed {
             // Reorder windows.
             self.state.sort_windows();
         }
-        if strut_changed {
+        if strut_changed || states_changed {
+            // Sticky windows follow the tag of their workspace.
             self.state.update_static();
         }
+        if states_changed {
+            self.state.track_minimized(&handle);
+        }
+        self.state.track_urgent(&handle);
+        if switch_to {
+            self.state.stop_showing_desktop();
+            self.state.reveal_window(&handle);
+            changed = true;
+        }
+        if hides_focused {
+            match next {
+                Some(next) => self.state.focus_window(&next),
+                None => self.state.unfocus_current_window(),
+            }
+        }
         changed
     }
 
-    /// Find the next or previous window on the currently focused workspace.
+    /// Find the next or previous window on the currently focused workspace, skipping hidden ones.
     /// May return `None` if no other window is present.
     ///
     /// Returns true if changes need to be rendered.
@@ -227,9 +289,13 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         let on_focused_workspace = |x: &Window<H>| -> bool { focused_workspace.is_managed(x) };
         let mut windows_on_workspace =
             helpers::vec_extract(&mut self.state.windows, on_focused_workspace);
-        let is_handle = |x: &Window<H>| -> bool { &x.handle == handle };
-        let new_handle = helpers::relative_find(&windows_on_workspace, is_handle, 1, false)
-            .or_else(|| helpers::relative_find(&windows_on_workspace, is_handle, -1, false))
+        let candidates: Vec<&Window<H>> = windows_on_workspace
+            .iter()
+            .filter(|w| !w.is_hidden() || &w.handle == handle)
+            .collect();
+        let is_handle = |x: &&Window<H>| -> bool { &x.handle == handle };
+        let new_handle = helpers::relative_find(&candidates, is_handle, 1, false)
+            .or_else(|| helpers::relative_find(&candidates, is_handle, -1, false))
             .map(|w| w.handle);
         self.state.windows.append(&mut windows_on_workspace);
         new_handle
@@ -238,6 +304,41 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
 
 // Private helper functions.
 
+/// Handles a window asking for attention as its window rule says, the focused window is never
+/// marked as urgent.
+///
+/// Returns true if the window is to be switched to.
+fn apply_urgency_policy<H: Handle>(state: &State<H>, change: &mut WindowChange<H>) -> bool {
+    if change.urgent != Some(true) {
+        return false;
+    }
+    let Some(window) = state.windows.iter().find(|w| w.handle == change.handle) else {
+        return false;
+    };
+    if state
+        .focus_manager
+        .window(&state.windows)
+        .is_some_and(|w| w.handle == window.handle)
+    {
+        change.urgent = Some(false);
+        return false;
+    }
+    if window.urgent {
+        return false;
+    }
+    match window.urgency.unwrap_or_default() {
+        FocusOnActivationBehaviour::DoNothing => {
+            change.urgent = None;
+            false
+        }
+        FocusOnActivationBehaviour::MarkUrgent => false,
+        FocusOnActivationBehaviour::SwitchTo => {
+            change.urgent = None;
+            true
+        }
+    }
+}
+
 fn find_terminal<H: Handle>(state: &State<H>, pid: Option<u32>) -> Option<&Window<H>> {
     // Get $SHELL, e.g. /bin/zsh
     let shell_path = env::var("SHELL").ok()?;
@@ -269,6 +370,76 @@ fn find_terminal<H: Handle>(state: &State<H>, pid: Option<u32>) -> Option<&Windo
     None
 }
 
+/// Finds the index of the terminal `window` should swallow, if any.
+///
+/// Only tiled windows swallow tiled terminals on the same tag.
+fn find_swallowed_terminal<H: Handle>(
+    state: &State<H>,
+    window: &Window<H>,
+    swallow: bool,
+) -> Option<usize> {
+    if !swallow
+        || window.r#type != WindowType::Normal
+        || window.transient.is_some()
+        || window.floating()
+    {
+        return None;
+    }
+    let terminal = find_terminal(state, window.pid)?;
+    if terminal.tag != window.tag || !terminal.is_managed() || terminal.floating() {
+        return None;
+    }
+    state
+        .windows
+        .iter()
+        .position(|w| w.handle == terminal.handle)
+}
+
+/// Hides the terminal at `index` in the hidden "SWL" tag and puts `window` in its place.
+fn swallow_terminal<H: Handle>(state: &mut State<H>, window: &mut Window<H>, index: usize) {
+    let swallowed_tag = state.tags.get_hidden_by_label("SWL").map(|tag| tag.id);
+    let terminal = &mut state.windows[index];
+    tracing::debug!(
+        "{:?} swallows terminal {:?}",
+        window.handle,
+        terminal.handle
+    );
+
+    window.swallowed = Some(terminal.handle);
+    terminal.untag();
+    if let Some(tag) = swallowed_tag {
+        terminal.tag(&tag);
+    }
+    terminal.set_visible(false);
+    let act = DisplayAction::SetWindowTag(terminal.handle, terminal.tag);
+    state.actions.push_back(act);
+    state.windows.insert(index, window.clone());
+}
+
+/// Shows the terminal swallowed by the window `handle` again, in the place of that window.
+fn restore_swallowed_terminal<H: Handle>(
+    state: &mut State<H>,
+    handle: &WindowHandle<H>,
+) -> Option<WindowHandle<H>> {
+    let window = state.windows.iter().find(|w| &w.handle == handle)?;
+    let (terminal_handle, tag) = (window.swallowed?, window.tag);
+    let terminal_index = state
+        .windows
+        .iter()
+        .position(|w| w.handle == terminal_handle)?;
+    let mut terminal = state.windows.remove(terminal_index);
+
+    terminal.untag();
+    if let Some(tag) = tag {
+        terminal.tag(&tag);
+    }
+    let act = DisplayAction::SetWindowTag(terminal.handle, terminal.tag);
+    state.actions.push_back(act);
+    let index = state.windows.iter().position(|w| &w.handle == handle)?;
+    state.windows.insert(index, terminal);
+    Some(terminal_handle)
+}
+
 fn find_transient_parent<H: Handle>(
     windows: &[Window<H>],
     transient: Option<WindowHandle<H>>,
@@ -290,8 +461,10 @@ fn find_transient_parent<H: Handle>(
 fn insert_window<H: Handle>(state: &mut State<H>, window: &mut Window<H>, layout: &str) {
     let mut was_fullscreen = false;
     if window.r#type == WindowType::Normal {
-        let for_active_workspace =
-            |x: &Window<H>| -> bool { window.tag == x.tag && x.is_managed() };
+        let workspaces = &state.workspaces;
+        let for_active_workspace = |x: &Window<H>| -> bool {
+            window.tag.is_some_and(|tag| x.is_on_tag(tag, workspaces)) && x.is_managed()
+        };
         // Only minimize when the new window is type normal.
         if let Some(fsw) = state
             .windows
@@ -432,14 +605,15 @@ fn setup_window<H: Handle>(
     };
 
     // Setup basic variables.
-    let for_active_workspace = |x: &Window<H>| -> bool { ws.tag == x.tag && x.is_managed() };
+    let for_active_workspace =
+        |x: &Window<H>| -> bool { ws.lays_out(x, &state.workspaces) && x.is_managed() };
     *is_first = !state.windows.iter().any(for_active_workspace);
     // May have been set by a predefined tag.
     if window.tag.is_none() {
         window.tag =
             find_terminal(state, window.pid).map_or_else(|| ws.tag, |terminal| terminal.tag);
     }
-    *on_same_tag = ws.tag == window.tag;
+    *on_same_tag = ws.is_displaying(window);
     layout.clone_from(&state.layout_manager.layout(ws.id, window.tag.unwrap()).name);
 
     // Setup a scratchpad window.
@@ -486,7 +660,7 @@ fn setup_window<H: Handle>(
     }
 }
 
-fn update_workspace_avoid_list<H: Handle>(state: &mut State<H>) {
+pub(super) fn update_workspace_avoid_list<H: Handle>(state: &mut State<H>) {
     let mut avoid = vec![];
     state
         .windows
@@ -509,6 +683,35 @@ fn update_workspace_avoid_list<H: Handle>(state: &mut State<H>) {
         ws.avoid = struts;
         ws.update_avoided_areas();
     }
+
+    // `_NET_WORKAREA` is a single area for all screens. Like other window managers we take the
+    // area spanning all workspaces, less the widest dock along each edge of any workspace.
+    let Some(first) = state.workspaces.first() else {
+        return;
+    };
+    let (mut left, mut top) = (first.xyhw.x(), first.xyhw.y());
+    let (mut right, mut bottom) = (left + first.xyhw.w(), top + first.xyhw.h());
+    let (mut inset_left, mut inset_top, mut inset_right, mut inset_bottom) = (0, 0, 0, 0);
+    for ws in &state.workspaces {
+        let (outer, inner) = (ws.xyhw, ws.xyhw_avoided);
+        left = left.min(outer.x());
+        top = top.min(outer.y());
+        right = right.max(outer.x() + outer.w());
+        bottom = bottom.max(outer.y() + outer.h());
+        inset_left = inset_left.max(inner.x() - outer.x());
+        inset_top = inset_top.max(inner.y() - outer.y());
+        inset_right = inset_right.max(outer.x() + outer.w() - inner.x() - inner.w());
+        inset_bottom = inset_bottom.max(outer.y() + outer.h() - inner.y() - inner.h());
+    }
+    let work_area = XyhwBuilder {
+        x: left + inset_left,
+        y: top + inset_top,
+        w: right - left - inset_left - inset_right,
+        h: bottom - top - inset_top - inset_bottom,
+        ..XyhwBuilder::default()
+    };
+    let act = DisplayAction::SetWorkArea(work_area.into());
+    state.actions.push_back(act);
 }
 
 #[cfg(test)]
@@ -516,7 +719,7 @@ mod tests {
     use super::*;
     use crate::Manager;
     use crate::layouts::MONOCLE;
-    use crate::models::{MockHandle, Screen};
+    use crate::models::{BBox, MockHandle, Screen, XyhwChange};
 
     fn last_window_order(state: &State<MockHandle>) -> Vec<WindowHandle<MockHandle>> {
         state
@@ -853,4 +1056,214 @@ mod tests {
         assert_eq!((manager.state.windows[0]).border(), 0);
         assert_eq!((manager.state.windows[1]).border(), 0);
     }
+
+    #[test]
+    fn swallowed_terminal_is_restored_in_place_of_its_child() {
+        // The terminal is this process, the child is launched from a shell it started.
+        let Ok(shell) = env::var("SHELL") else {
+            return;
+        };
+        let mut shell = std::process::Command::new(shell)
+            .args(["-c", "sleep 10 & echo $!; wait"])
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut child_pid = String::new();
+        std::io::BufRead::read_line(
+            &mut std::io::BufReader::new(shell.stdout.as_mut().unwrap()),
+            &mut child_pid,
+        )
+        .unwrap();
+        let child_pid: u32 = child_pid.trim().parse().unwrap();
+
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.config.swallow_terminals = true;
+        manager.screen_create_handler(Screen::default());
+        let mut terminal = Window::new(
+            WindowHandle::<MockHandle>(1),
+            None,
+            Some(std::process::id()),
+        );
+        terminal.res_class = Some("Alacritty".to_string());
+        manager.window_created_handler(terminal, -1, -1);
+        manager.window_created_handler(Window::new(WindowHandle(2), None, None), -1, -1);
+        manager.state.focus_window(&WindowHandle(1));
+
+        // A window rule keeps this child from swallowing the terminal.
+        let mut child = Window::new(WindowHandle(3), None, Some(child_pid));
+        child.res_class = Some("ShouldNotSwallow".to_string());
+        manager.window_created_handler(child, -1, -1);
+        assert!(manager.state.windows.iter().all(|w| w.tag == Some(1)));
+        manager.window_destroyed_handler(&WindowHandle(3));
+
+        // This one swallows it although `swallow_terminals` is off.
+        manager.config.swallow_terminals = false;
+        manager.state.focus_window(&WindowHandle(1));
+        let mut child = Window::new(WindowHandle(4), None, Some(child_pid));
+        child.res_class = Some("ShouldSwallow".to_string());
+        manager.window_created_handler(child, -1, -1);
+        shell.kill().unwrap();
+        shell.wait().unwrap();
+
+        let swallowed_tag = manager.state.tags.get_hidden_by_label("SWL").unwrap().id;
+        let tags: Vec<_> = manager
+            .state
+            .windows
+            .iter()
+            .map(|w| (w.handle.0, w.tag))
+            .collect();
+        assert_eq!(tags, [(4, Some(1)), (1, Some(swallowed_tag)), (2, Some(1))]);
+
+        manager.window_destroyed_handler(&WindowHandle(4));
+        let tags: Vec<_> = manager
+            .state
+            .windows
+            .iter()
+            .map(|w| (w.handle.0, w.tag))
+            .collect();
+        assert_eq!(tags, [(1, Some(1)), (2, Some(1))]);
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(1)))
+        );
+    }
+
+    #[test]
+    fn work_area_leaves_out_docks_on_any_screen() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        for x in [0, 800] {
+            let bbox = BBox {
+                x,
+                y: 0,
+                width: 800,
+                height: 600,
+            };
+            manager.screen_create_handler(Screen::new(bbox, String::new()));
+        }
+        let mut dock = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        dock.r#type = WindowType::Dock;
+        manager.window_created_handler(dock, -1, -1);
+
+        let mut change = WindowChange::new(WindowHandle(1));
+        change.strut = Some(XyhwChange {
+            x: Some(800),
+            y: Some(0),
+            w: Some(800),
+            h: Some(20),
+            ..XyhwChange::default()
+        });
+        manager.window_changed_handler(change);
+
+        let work_area = manager
+            .state
+            .actions
+            .iter()
+            .rev()
+            .find_map(|action| match action {
+                DisplayAction::SetWorkArea(area) => Some(*area),
+                _ => None,
+            });
+        assert_eq!(
+            work_area.map(|area| (area.x(), area.y(), area.w(), area.h())),
+            Some((0, 20, 1600, 580))
+        );
+    }
+
+    #[test]
+    fn below_windows_are_stacked_under_tiled_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=2 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        let order = last_window_order(&manager.state);
+
+        let mut change = WindowChange::new(order[0]);
+        change.states = Some(vec![WindowState::Below]);
+        manager.window_changed_handler(change);
+
+        assert_eq!(last_window_order(&manager.state), [order[1], order[0]]);
+    }
+
+    #[test]
+    fn hidden_windows_leave_the_layout_and_the_focus() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=2 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.focus_window(&WindowHandle(1));
+
+        let mut change = WindowChange::new(WindowHandle(1));
+        change.states = Some(vec![WindowState::Hidden]);
+        assert!(manager.window_changed_handler(change));
+        manager.update_windows();
+
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(2)))
+        );
+        let visible: Vec<_> = manager
+            .state
+            .windows
+            .iter()
+            .map(|w| (w.handle.0, w.visible()))
+            .collect();
+        assert!(visible.contains(&(1, false)) && visible.contains(&(2, true)));
+        let workspace = &manager.state.workspaces[0];
+        let shown = manager.state.windows.iter().find(|w| w.visible()).unwrap();
+        assert_eq!(shown.normal.w(), workspace.xyhw.w());
+    }
+
+    #[test]
+    fn windows_hiding_themselves_can_be_restored_until_destroyed() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=2 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+
+        for handle in [2, 1] {
+            let mut change = WindowChange::new(WindowHandle(handle));
+            change.states = Some(vec![WindowState::Hidden]);
+            manager.window_changed_handler(change);
+        }
+        assert_eq!(manager.state.minimized, [WindowHandle(2), WindowHandle(1)]);
+
+        let mut change = WindowChange::new(WindowHandle(2));
+        change.states = Some(vec![]);
+        manager.window_changed_handler(change);
+        manager.window_destroyed_handler(&WindowHandle(1));
+        assert!(manager.state.minimized.is_empty());
+    }
+
+    #[test]
+    fn urgency_follows_the_policy_of_the_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for handle in 1..=3 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.windows[1].urgency = Some(FocusOnActivationBehaviour::DoNothing);
+        manager.state.windows[2].urgency = Some(FocusOnActivationBehaviour::SwitchTo);
+        manager.state.windows[2].tag = Some(2);
+        manager.state.focus_window(&WindowHandle(1));
+
+        for handle in [2, 3] {
+            let mut change = WindowChange::new(WindowHandle(handle));
+            change.urgent = Some(true);
+            manager.window_changed_handler(change);
+        }
+        assert!(manager.state.urgent.is_empty());
+        assert!(!manager.state.windows.iter().any(|w| w.urgent));
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        assert_eq!(
+            manager.state.focus_manager.window_history.front(),
+            Some(&Some(WindowHandle(3)))
+        );
+    }
 }
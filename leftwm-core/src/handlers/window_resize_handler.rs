@@ -1,7 +1,9 @@
 use super::{Manager, Window, WindowHandle};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::layouts::{MANUAL, SCROLLING, TileResize, tile_rects};
 use crate::models::Handle;
+use crate::state::State;
 
 impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     pub fn window_resize_handler(
@@ -10,6 +12,18 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
         offset_w: i32,
         offset_h: i32,
     ) -> bool {
+        if let Some(resize) = self.state.tile_resize {
+            let layout = self
+                .state
+                .layout_manager
+                .layout_mut(resize.workspace, resize.tag);
+            for (handle, weight) in resize.drag((offset_w, offset_h), layout) {
+                if let Some(w) = self.state.windows.iter_mut().find(|w| w.handle == handle) {
+                    w.tile_weight = weight;
+                }
+            }
+            return true;
+        }
         if let Some(w) = self.state.windows.iter_mut().find(|w| &w.handle == handle) {
             process_window(w, offset_w, offset_h);
             return true;
@@ -18,6 +32,61 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
     }
 }
 
+impl<H: Handle> State<H> {
+    /// Starts resizing the tiles of the layout around the tiled window `handle` instead of the
+    /// window itself.
+    ///
+    /// Returns false if the window is not tiled or its layout has no boundary to move.
+    pub(crate) fn start_tile_resize(&mut self, handle: &WindowHandle<H>) -> bool {
+        let Some(window) = self.windows.iter().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        if window.floating() || window.is_fullscreen() || window.is_maximized() {
+            return false;
+        }
+        let Some((workspace, tag)) = self
+            .workspaces
+            .iter()
+            .find(|ws| ws.lays_out(window, &self.workspaces))
+            .and_then(|ws| Some((ws, ws.tag?)))
+        else {
+            return false;
+        };
+        let layout = self.layout_manager.layout(workspace.id, tag);
+        if layout.name == MANUAL || layout.name == SCROLLING {
+            return false;
+        }
+
+        // The tiles as `Tag::update_windows` places them, before windows are shrunk to their hints.
+        let windows: Vec<_> = self
+            .windows
+            .iter()
+            .filter(|w| {
+                workspace.lays_out(w, &self.workspaces)
+                    && !w.is_hidden()
+                    && w.is_managed()
+                    && !w.floating()
+            })
+            .collect();
+        let weights: Vec<f32> = windows.iter().map(|w| w.tile_weight).collect();
+        let tiles: Vec<_> = windows
+            .iter()
+            .zip(tile_rects(layout, &workspace.rect(), &weights))
+            .map(|(w, rect)| (w.handle, rect, w.tile_weight))
+            .collect();
+        self.tile_resize = TileResize::new(
+            handle,
+            workspace.id,
+            tag,
+            workspace.rect(),
+            &tiles,
+            layout.main_window_count().unwrap_or(0),
+            layout.main_size(),
+        );
+        self.tile_resize.is_some()
+    }
+}
+
 fn process_window<H: Handle>(window: &mut Window<H>, offset_w: i32, offset_h: i32) {
     window.set_floating(true);
     let mut offset = window.get_floating_offsets().unwrap_or_default();
@@ -27,3 +96,40 @@ fn process_window<H: Handle>(window: &mut Window<H>, offset_w: i32, offset_h: i3
     offset.set_h(start.h() + offset_h);
     window.set_floating_offsets(Some(offset));
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::layouts::MAIN_AND_VERT_STACK;
+    use crate::models::{MockHandle, Mode, Window, WindowHandle};
+    use crate::{DisplayEvent, Manager};
+
+    #[test]
+    fn dragging_a_tiled_window_resizes_the_tiles() {
+        let mut manager = Manager::new_test_with_layouts(
+            vec!["1".to_string()],
+            vec![MAIN_AND_VERT_STACK.to_string()],
+        );
+        for handle in 1..=3 {
+            let window = Window::new(WindowHandle::<MockHandle>(handle), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.update_windows();
+        let geometry = |manager: &Manager<_, _, _>, handle| {
+            let window = manager.state.windows.iter().find(|w| w.handle.0 == handle);
+            window.map(|w| (w.x(), w.y(), w.width(), w.height()))
+        };
+
+        // Drag the last stack window up and left, growing it and the stack.
+        manager.state.mode = Mode::ReadyToResize(WindowHandle(3));
+        manager.display_event_handler(DisplayEvent::ResizeWindow(WindowHandle(3), -80, -150));
+        manager.update_windows();
+        assert_eq!(geometry(&manager, 1), Some((0, 0, 320, 600)));
+        assert_eq!(geometry(&manager, 2), Some((320, 0, 480, 150)));
+        assert_eq!(geometry(&manager, 3), Some((320, 150, 480, 450)));
+
+        manager.display_event_handler(DisplayEvent::ChangeToNormalMode);
+        assert_eq!(manager.state.mode, Mode::Normal);
+        assert!(manager.state.tile_resize.is_none());
+        assert!(manager.state.windows.iter().all(|w| !w.floating()));
+    }
+}
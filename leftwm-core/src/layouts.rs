@@ -1,10 +1,16 @@
+mod column_strip;
 mod layout_manager;
 mod layout_mode;
+mod split_tree;
+mod tile_resize;
 
 use thiserror::Error;
 
+pub use column_strip::ColumnStrip;
 pub use layout_manager::LayoutManager;
 pub use layout_mode::LayoutMode;
+pub use split_tree::{Orientation, SplitTree};
+pub use tile_resize::{Tile, TileResize, tile_rects};
 
 pub const DEFAULT: &str = "Default";
 pub const MONOCLE: &str = "Monocle";
@@ -21,6 +27,12 @@ pub const LEFT_MAIN: &str = "LeftMain";
 pub const CENTER_MAIN: &str = "CenterMain";
 pub const CENTER_MAIN_BALANCED: &str = "CenterMainBalanced";
 pub const CENTER_MAIN_FLUID: &str = "CenterMainFluid";
+/// Tiles the windows of each tag by the [`SplitTree`] of the tag instead of a formula. Does not
+/// need a layout definition.
+pub const MANUAL: &str = "Manual";
+/// Tiles the windows of each tag in the scrolling [`ColumnStrip`] of the tag. Does not need a
+/// layout definition.
+pub const SCROLLING: &str = "Scrolling";
 
 #[derive(Debug, Error)]
 #[error("Could not parse layout: {0}")]
@@ -0,0 +1,270 @@
+//! The column strips of the [`SCROLLING`](super::SCROLLING) layout, which tiles windows
+//! PaperWM-style.
+use crate::models::{Handle, WindowHandle};
+use leftwm_layouts::geometry::{Rect, Split};
+use serde::{Deserialize, Serialize};
+
+/// The width of new columns, as a share of the workspace width.
+const DEFAULT_WIDTH: f32 = 0.5;
+/// The narrowest a column can be set to.
+const MIN_WIDTH: f32 = 0.1;
+/// Leeway for rounding errors when checking if a column is in the viewport.
+const EPSILON: f32 = 0.001;
+
+/// A horizontal strip of columns of windows, which may be wider than the workspace showing it.
+///
+/// The workspace is a viewport onto the strip, scrolled so that the column of the window focused
+/// last is fully shown. Columns partly in the viewport are clipped to it, the others are hidden.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ColumnStrip<H: Handle> {
+    #[serde(bound = "")]
+    columns: Vec<Column<H>>,
+    /// Where the viewport starts, in workspace widths from the start of the strip.
+    offset: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Column<H: Handle> {
+    /// The windows of the column from top to bottom.
+    #[serde(bound = "")]
+    windows: Vec<WindowHandle<H>>,
+    /// The share of the workspace width.
+    width: f32,
+}
+
+impl<H: Handle> Column<H> {
+    fn new(handle: WindowHandle<H>) -> Self {
+        Self {
+            windows: vec![handle],
+            width: DEFAULT_WIDTH,
+        }
+    }
+}
+
+impl<H: Handle> ColumnStrip<H> {
+    /// The windows of the strip, column by column.
+    pub fn windows(&self) -> Vec<WindowHandle<H>> {
+        self.columns
+            .iter()
+            .flat_map(|c| c.windows.iter().copied())
+            .collect()
+    }
+
+    /// Drops the windows which are no longer `tiled` and adds the new ones in columns after the
+    /// window which was focused last according to `focus_history`, then scrolls to the window
+    /// focused last, which may be a new one.
+    pub fn sync(
+        &mut self,
+        tiled: &[WindowHandle<H>],
+        focus_history: impl IntoIterator<Item = WindowHandle<H>>,
+    ) {
+        for column in &mut self.columns {
+            column.windows.retain(|h| tiled.contains(h));
+        }
+        self.columns.retain(|c| !c.windows.is_empty());
+
+        let known = self.windows();
+        let history: Vec<_> = focus_history.into_iter().collect();
+        let index = history
+            .iter()
+            .find_map(|h| self.column_of(h))
+            .map_or(self.columns.len(), |i| i + 1);
+        let new = tiled.iter().filter(|h| !known.contains(h));
+        self.columns
+            .splice(index..index, new.map(|&handle| Column::new(handle)));
+
+        if let Some(index) = history.iter().find_map(|h| self.column_of(h)) {
+            self.scroll_to(index);
+        }
+    }
+
+    /// The window `shift` columns to the right of the column of `handle`, or to the left if
+    /// negative. It is the window of that column which was focused last according to
+    /// `focus_history`, or else its top one.
+    pub fn neighbour(
+        &self,
+        handle: &WindowHandle<H>,
+        shift: i32,
+        focus_history: impl IntoIterator<Item = WindowHandle<H>>,
+    ) -> Option<WindowHandle<H>> {
+        let index = self.column_of(handle)?.checked_add_signed(shift as isize)?;
+        let column = self.columns.get(index)?;
+        focus_history
+            .into_iter()
+            .find(|h| column.windows.contains(h))
+            .or_else(|| column.windows.first().copied())
+    }
+
+    /// Moves `handle` to the bottom of the column to the left of it.
+    ///
+    /// Returns false if `handle` is in the first column.
+    pub fn consume(&mut self, handle: &WindowHandle<H>) -> bool {
+        let Some(index) = self.column_of(handle).filter(|&i| i > 0) else {
+            return false;
+        };
+        self.columns[index].windows.retain(|h| h != handle);
+        self.columns[index - 1].windows.push(*handle);
+        if self.columns[index].windows.is_empty() {
+            self.columns.remove(index);
+        }
+        true
+    }
+
+    /// Moves `handle` out of its column into a new one to the right of it.
+    ///
+    /// Returns false if `handle` is alone in its column.
+    pub fn expel(&mut self, handle: &WindowHandle<H>) -> bool {
+        let Some(index) = self
+            .column_of(handle)
+            .filter(|&i| self.columns[i].windows.len() > 1)
+        else {
+            return false;
+        };
+        self.columns[index].windows.retain(|h| h != handle);
+        self.columns.insert(index + 1, Column::new(*handle));
+        true
+    }
+
+    /// Sets the width of the column of `handle` as a share of the workspace width, eg. `0.5` for
+    /// half of it.
+    ///
+    /// Returns false if `handle` is not in the strip.
+    pub fn set_width(&mut self, handle: &WindowHandle<H>, width: f32) -> bool {
+        let Some(index) = self.column_of(handle) else {
+            return false;
+        };
+        self.columns[index].width = width.clamp(MIN_WIDTH, 1.0);
+        true
+    }
+
+    /// The rectangles of the windows in the columns the viewport `rect` shows, clipped to it.
+    pub fn rects(&self, rect: Rect) -> Vec<(WindowHandle<H>, Rect)> {
+        let mut rects = vec![];
+        let mut start = -self.offset;
+        for column in &self.columns {
+            let end = start + column.width;
+            if end > EPSILON && start < 1.0 - EPSILON {
+                let left = rect.x + (start.max(0.0) * rect.w as f32).round() as i32;
+                let right = rect.x + (end.min(1.0) * rect.w as f32).round() as i32;
+                let column_rect = Rect {
+                    x: left,
+                    w: (right - left) as u32,
+                    ..rect
+                };
+                let split = leftwm_layouts::geometry::split(
+                    &column_rect,
+                    column.windows.len(),
+                    Some(Split::Horizontal),
+                );
+                rects.extend(column.windows.iter().copied().zip(split));
+            }
+            start = end;
+        }
+        rects
+    }
+
+    fn column_of(&self, handle: &WindowHandle<H>) -> Option<usize> {
+        self.columns.iter().position(|c| c.windows.contains(handle))
+    }
+
+    /// Scrolls as little as possible to show the column at `index`, without showing space after
+    /// the last column.
+    fn scroll_to(&mut self, index: usize) {
+        let start: f32 = self.columns[..index].iter().map(|c| c.width).sum();
+        let end = start + self.columns[index].width;
+        if end - self.offset > 1.0 {
+            self.offset = end - 1.0;
+        }
+        if start < self.offset {
+            self.offset = start;
+        }
+        let total: f32 = self.columns.iter().map(|c| c.width).sum();
+        self.offset = self.offset.min(total - 1.0).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MockHandle;
+
+    const RECT: Rect = Rect {
+        x: 0,
+        y: 0,
+        w: 800,
+        h: 600,
+    };
+
+    fn handles(ids: &[MockHandle]) -> Vec<WindowHandle<MockHandle>> {
+        ids.iter().copied().map(WindowHandle).collect()
+    }
+
+    fn shown(strip: &ColumnStrip<MockHandle>) -> Vec<(MockHandle, i32, u32)> {
+        strip
+            .rects(RECT)
+            .into_iter()
+            .map(|(h, r)| (h.0, r.x, r.w))
+            .collect()
+    }
+
+    #[test]
+    fn the_viewport_scrolls_to_the_focused_column() {
+        let mut strip = ColumnStrip::default();
+        strip.sync(&handles(&[1, 2, 3]), handles(&[]));
+        assert_eq!(shown(&strip), [(1, 0, 400), (2, 400, 400)]);
+
+        strip.sync(&handles(&[1, 2, 3]), handles(&[3, 2]));
+        assert_eq!(shown(&strip), [(2, 0, 400), (3, 400, 400)]);
+
+        // A new window opens right of the focused one, scrolling back keeps it in view.
+        strip.sync(&handles(&[1, 2, 3, 4]), handles(&[1]));
+        assert_eq!(strip.windows(), handles(&[1, 4, 2, 3]));
+        assert_eq!(shown(&strip), [(1, 0, 400), (4, 400, 400)]);
+        assert_eq!(
+            strip.neighbour(&WindowHandle(4), 1, handles(&[])),
+            Some(WindowHandle(2))
+        );
+        assert_eq!(strip.neighbour(&WindowHandle(1), -1, handles(&[])), None);
+
+        // Closing windows does not leave space after the last column.
+        assert!(strip.set_width(&WindowHandle(1), 0.25));
+        strip.sync(&handles(&[1, 3]), handles(&[3]));
+        assert_eq!(shown(&strip), [(1, 0, 200), (3, 200, 400)]);
+    }
+
+    #[test]
+    fn partly_shown_columns_are_clipped_to_the_viewport() {
+        let mut strip = ColumnStrip::default();
+        strip.sync(&handles(&[1, 2, 3]), handles(&[]));
+        assert!(strip.set_width(&WindowHandle(1), 0.75));
+        strip.sync(&handles(&[1, 2, 3]), handles(&[2]));
+        assert_eq!(shown(&strip), [(1, 0, 400), (2, 400, 400)]);
+    }
+
+    #[test]
+    fn windows_move_between_columns() {
+        let mut strip = ColumnStrip::default();
+        strip.sync(&handles(&[1, 2, 3]), handles(&[]));
+        assert!(!strip.consume(&WindowHandle(1)));
+        assert!(strip.consume(&WindowHandle(2)));
+
+        let rects = strip.rects(RECT);
+        assert_eq!(
+            rects[0],
+            (
+                WindowHandle(1),
+                Rect {
+                    w: 400,
+                    h: 300,
+                    ..RECT
+                }
+            )
+        );
+        assert_eq!(rects[1].1.y, 300);
+        assert_eq!(rects[2].0, WindowHandle(3));
+
+        assert!(!strip.expel(&WindowHandle(3)));
+        assert!(strip.expel(&WindowHandle(1)));
+        assert_eq!(strip.windows(), handles(&[2, 1, 3]));
+    }
+}
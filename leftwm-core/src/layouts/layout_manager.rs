@@ -1,9 +1,10 @@
-use crate::{config::Config, utils::helpers::cycle_vec};
+use crate::config::{Config, WindowCountLayout, Workspace};
+use crate::utils::helpers::cycle_vec;
 use leftwm_layouts::Layout;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::LayoutMode;
+use super::{LayoutMode, MANUAL, SCROLLING};
 
 /// The [`LayoutManager`] holds the actual set of [`Layout`].
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,8 +13,9 @@ pub struct LayoutManager {
     mode: LayoutMode,
 
     /// All the available layouts. Loaded from the config and
-    /// to be unchanged during runtime. The layout manager shall make
-    /// copies of those layouts for the specific workspaces and tags.
+    /// only changed at runtime by [`Self::load_layout`] and [`Self::remove_layout`].
+    /// The layout manager shall make copies of those layouts for the
+    /// specific workspaces and tags.
     available_layouts: Vec<Layout>,
 
     /// All the available layouts per workspace. Different workspaces may
@@ -25,6 +27,24 @@ pub struct LayoutManager {
     /// The actual, modifiable layouts grouped by either
     /// Workspace or Tag, depending on the configured [`LayoutMode`].
     layouts: HashMap<usize, Vec<Layout>>,
+
+    /// The layouts picked by the number of tiled windows.
+    #[serde(default)]
+    window_count_layouts: Vec<WindowCountLayout>,
+
+    /// The layouts picked by the number of tiled windows on workspaces
+    /// which replace [`Self::window_count_layouts`].
+    #[serde(default)]
+    window_count_layouts_per_ws: HashMap<usize, Vec<WindowCountLayout>>,
+
+    /// The names of the default layouts of the workspaces which have one configured.
+    #[serde(default)]
+    default_layouts: HashMap<usize, String>,
+
+    /// The number of tiled windows per Workspace or Tag, as of the last
+    /// [`Self::follow_window_count`].
+    #[serde(default)]
+    window_counts: HashMap<usize, usize>,
 }
 
 impl LayoutManager {
@@ -34,32 +54,53 @@ impl LayoutManager {
 
         tracing::trace!("Looking for layouts named: {:?}", config.layouts());
         for name in config.layouts() {
-            if let Some(def) = config
-                .layout_definitions()
-                .iter()
-                .find(|def| def.name == name)
-            {
-                available_layouts.push(def.clone());
+            if let Some(def) = definition(config, &name) {
+                available_layouts.push(def);
             } else {
                 tracing::warn!("There is no Layout with the name {:?}", name);
             }
         }
 
+        if available_layouts.is_empty() {
+            tracing::warn!(
+                "No Layouts were loaded from config - defaulting to a single default Layout"
+            );
+            available_layouts.push(Layout::default());
+        }
+
+        tracing::trace!("The general available layouts are: {:?}", available_layouts);
+
+        let mut layout_manager = Self {
+            mode: config.layout_mode(),
+            available_layouts,
+            available_layouts_per_ws: HashMap::new(),
+            layouts: HashMap::new(),
+            window_count_layouts: config.window_count_layouts(),
+            window_count_layouts_per_ws: HashMap::new(),
+            default_layouts: HashMap::new(),
+            window_counts: HashMap::new(),
+        };
+        layout_manager.load_workspaces(config, &config.workspaces().unwrap_or_default());
+        layout_manager
+    }
+
+    /// Sets up the layouts and default layouts of the configured `workspaces`, eg. the ones of
+    /// an output profile that just got applied.
+    ///
+    /// In [`LayoutMode::Workspace`] workspaces which still exist with the same available layouts
+    /// keep their current layout, the layouts of the others are reset.
+    pub fn load_workspaces(&mut self, config: &impl Config, workspaces: &[Workspace]) {
         let mut available_layouts_per_ws: HashMap<usize, Vec<Layout>> = HashMap::new();
 
-        for (i, ws) in config.workspaces().unwrap_or_default().iter().enumerate() {
+        for (i, ws) in workspaces.iter().enumerate() {
             if let Some(ws_layout_names) = &ws.layouts {
                 let wsid = i + 1;
                 for ws_layout_name in ws_layout_names {
-                    if let Some(layout) = config
-                        .layout_definitions()
-                        .iter()
-                        .find(|layout| layout.name == *ws_layout_name)
-                    {
+                    if let Some(layout) = definition(config, ws_layout_name) {
                         available_layouts_per_ws
                             .entry(wsid)
                             .and_modify(|layouts| layouts.push(layout.clone()))
-                            .or_insert_with(|| vec![layout.clone()]);
+                            .or_insert_with(|| vec![layout]);
                     } else {
                         tracing::warn!(
                             "There is no Layout with the name {:?}, but was configured on workspace {:?}",
@@ -71,11 +112,7 @@ impl LayoutManager {
             }
             if let Some(default_layout) = &ws.default_layout {
                 let wsid = i + 1;
-                if let Some(layout) = config
-                    .layout_definitions()
-                    .iter()
-                    .find(|layout| layout.name == *default_layout)
-                {
+                if let Some(layout) = definition(config, default_layout) {
                     // add the default layout to the available layouts if it's not already there
                     available_layouts_per_ws
                         .entry(wsid)
@@ -84,7 +121,7 @@ impl LayoutManager {
                                 layouts.push(layout.clone());
                             }
                         })
-                        .or_insert_with(|| vec![layout.clone()]);
+                        .or_insert_with(|| vec![layout]);
                 } else {
                     tracing::warn!(
                         "There is no Layout with the name {:?}, but was configured as default on workspace {:?}",
@@ -95,35 +132,43 @@ impl LayoutManager {
             }
         }
 
-        if available_layouts.is_empty() {
-            tracing::warn!(
-                "No Layouts were loaded from config - defaulting to a single default Layout"
-            );
-            available_layouts.push(Layout::default());
-        }
-
-        tracing::trace!("The general available layouts are: {:?}", available_layouts);
         tracing::trace!(
             "The workspace specific available layouts are: {:?}",
             available_layouts_per_ws
         );
 
-        let mut layout_manager = Self {
-            mode: config.layout_mode(),
-            available_layouts,
-            available_layouts_per_ws,
-            layouts: HashMap::new(),
-        };
+        let old_layouts_per_ws =
+            std::mem::replace(&mut self.available_layouts_per_ws, available_layouts_per_ws);
+        self.window_count_layouts_per_ws = workspaces
+            .iter()
+            .enumerate()
+            .filter_map(|(i, ws)| Some((i + 1, ws.window_count_layouts.clone()?)))
+            .collect();
+        self.default_layouts = workspaces
+            .iter()
+            .enumerate()
+            .filter_map(|(i, ws)| Some((i + 1, ws.default_layout.clone()?)))
+            .collect();
+        if self.mode == LayoutMode::Workspace {
+            // Without configured workspaces there is no telling which ones went away.
+            let kept = |wsid: &usize| {
+                (workspaces.is_empty() || *wsid <= workspaces.len())
+                    && old_layouts_per_ws.get(wsid) == self.available_layouts_per_ws.get(wsid)
+            };
+            self.layouts.retain(|wsid, _| kept(wsid));
+            self.window_counts.retain(|wsid, _| kept(wsid));
+        }
 
         // set the current layout to the default layout for workspaces that have one configured
-        for (i, ws) in config.workspaces().unwrap_or_default().iter().enumerate() {
-            if let Some(default_layout) = &ws.default_layout {
-                let wsid = i + 1;
-                layout_manager.set_layout(wsid, wsid, default_layout);
+        for (i, ws) in workspaces.iter().enumerate() {
+            let wsid = i + 1;
+            let kept = self.mode == LayoutMode::Workspace && self.layouts.contains_key(&wsid);
+            if let Some(default_layout) = &ws.default_layout
+                && !kept
+            {
+                self.set_layout(wsid, wsid, default_layout);
             }
         }
-
-        layout_manager
     }
 
     pub fn restore(&mut self, old: &LayoutManager) {
@@ -144,6 +189,12 @@ impl LayoutManager {
             return;
         }
         self.layouts.clone_from(&old.layouts);
+        self.window_counts.clone_from(&old.window_counts);
+    }
+
+    /// All the layouts loaded from the config, regardless of workspace.
+    pub fn available_layouts(&self) -> &[Layout] {
+        &self.available_layouts
     }
 
     /// Get back either the workspace ID or the tag ID, based on the current [`LayoutMode`]
@@ -241,15 +292,141 @@ impl LayoutManager {
         };
     }
 
+    /// Switches to the layout picked for `count` tiled windows, unless `count` did not change
+    /// since the last call, so a layout chosen by hand is kept until a window opens or closes.
+    pub fn follow_window_count(&mut self, wsid: usize, tagid: usize, count: usize) {
+        let id = self.id(wsid, tagid);
+        if self.window_counts.insert(id, count) == Some(count) {
+            return;
+        }
+        let picked = self
+            .window_count_layouts_per_ws
+            .get(&wsid)
+            .unwrap_or(&self.window_count_layouts)
+            .iter()
+            .find(|picker| picker.matches(count))
+            .map(|picker| picker.layout.clone());
+        if let Some(name) = picked {
+            self.set_layout(wsid, tagid, &name);
+        }
+    }
+
+    /// Moves the layouts of each tag to the new ID returned by `new_id`,
+    /// the layouts of tags mapped to [`None`] are dropped.
+    ///
+    /// Has no effect in [`LayoutMode::Workspace`].
+    pub fn renumber_tags(&mut self, new_id: impl Fn(usize) -> Option<usize>) {
+        if self.mode == LayoutMode::Workspace {
+            return;
+        }
+        self.layouts = std::mem::take(&mut self.layouts)
+            .into_iter()
+            .filter_map(|(tagid, layouts)| Some((new_id(tagid)?, layouts)))
+            .collect();
+        self.window_counts = std::mem::take(&mut self.window_counts)
+            .into_iter()
+            .filter_map(|(tagid, count)| Some((new_id(tagid)?, count)))
+            .collect();
+    }
+
+    /// Adds `layout`, or replaces the layout of the same name wherever it is available.
+    ///
+    /// A replaced layout keeps its place, so it stays the current layout of the workspaces and
+    /// tags it was current on. A new layout is appended wherever the global layouts are used.
+    pub fn load_layout(&mut self, layout: &Layout) {
+        let replace = |layouts: &mut Vec<Layout>| {
+            let old = layouts.iter_mut().find(|l| l.name == layout.name);
+            old.map(|old| *old = layout.clone()).is_some()
+        };
+
+        let is_new = !replace(&mut self.available_layouts);
+        if is_new {
+            self.available_layouts.push(layout.clone());
+        }
+        for layouts in self.available_layouts_per_ws.values_mut() {
+            replace(layouts);
+        }
+        for (id, layouts) in &mut self.layouts {
+            let uses_global =
+                self.mode == LayoutMode::Tag || !self.available_layouts_per_ws.contains_key(id);
+            if !replace(layouts) && is_new && uses_global {
+                layouts.push(layout.clone());
+            }
+        }
+    }
+
+    /// Removes the layout called `name` everywhere. Where it was the current layout, the next one
+    /// becomes current. A workspace left without layouts of its own uses the global ones.
+    ///
+    /// # Errors
+    ///
+    /// Will error if there is no such layout, if a workspace or tag would be left without
+    /// layouts, or if the layout is still the default of a workspace or picked by the number of
+    /// windows.
+    pub fn remove_layout(&mut self, name: &str) -> Result<(), String> {
+        let is_only = |layouts: &Vec<Layout>| layouts.iter().all(|l| l.name == name);
+        if !self.available_layouts.iter().any(|l| l.name == name) {
+            return Err(format!("There is no Layout with the name {name:?}"));
+        }
+        if is_only(&self.available_layouts) || self.layouts.values().any(is_only) {
+            return Err(format!("{name:?} is the only Layout left"));
+        }
+        if let Some(wsid) = self
+            .default_layouts
+            .iter()
+            .find(|(_, default)| *default == name)
+            .map(|(wsid, _)| wsid)
+        {
+            return Err(format!(
+                "{name:?} is the default Layout of workspace {wsid}"
+            ));
+        }
+        let mut pickers = self
+            .window_count_layouts
+            .iter()
+            .chain(self.window_count_layouts_per_ws.values().flatten());
+        if pickers.any(|picker| picker.layout == name) {
+            return Err(format!("{name:?} is still picked by window_count_layouts"));
+        }
+
+        self.available_layouts.retain(|l| l.name != name);
+        for layouts in self.available_layouts_per_ws.values_mut() {
+            layouts.retain(|l| l.name != name);
+        }
+        self.available_layouts_per_ws
+            .retain(|_, layouts| !layouts.is_empty());
+        for layouts in self.layouts.values_mut() {
+            layouts.retain(|l| l.name != name);
+        }
+        Ok(())
+    }
+
     // todo - low priority: reset fn, that resets all the layouts to their unchanged properties
 }
 
+/// The definition of the layout called `name`, [`MANUAL`] and [`SCROLLING`] are built in unless
+/// defined by the config.
+fn definition(config: &impl Config, name: &str) -> Option<Layout> {
+    let definition = config
+        .layout_definitions()
+        .into_iter()
+        .find(|layout| layout.name == name);
+    definition.or_else(|| {
+        [MANUAL, SCROLLING].contains(&name).then(|| Layout {
+            name: name.to_string(),
+            ..Layout::default()
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use leftwm_layouts::Layout;
+    use leftwm_layouts::geometry::Flip;
     use leftwm_layouts::layouts::Layouts;
 
     use crate::{
-        config::tests::TestConfig,
+        config::{WindowCountLayout, Workspace, tests::TestConfig},
         layouts::{self, EVEN_VERTICAL, MONOCLE},
     };
 
@@ -336,6 +513,147 @@ mod tests {
         assert_eq!(EVEN_VERTICAL, &layout_manager.layout(2, 1).name);
     }
 
+    #[test]
+    fn loading_a_layout_keeps_the_current_layouts() {
+        let mut layout_manager = layout_manager();
+        layout_manager.set_layout(2, 1, EVEN_VERTICAL);
+
+        let even_vertical = Layout {
+            flip: Flip::Horizontal,
+            ..layout_manager.layout(2, 1).clone()
+        };
+        layout_manager.load_layout(&even_vertical);
+        layout_manager.load_layout(&Layout {
+            name: "Custom".to_string(),
+            ..Layout::default()
+        });
+
+        let layout = layout_manager.layout(2, 1);
+        assert_eq!(EVEN_VERTICAL, &layout.name);
+        assert_eq!(Flip::Horizontal, layout.flip);
+        let names = |layouts: &[Layout]| -> Vec<String> {
+            layouts.iter().map(|l| l.name.clone()).collect()
+        };
+        assert_eq!(
+            names(&layout_manager.layouts[&2]),
+            [
+                EVEN_VERTICAL,
+                layouts::MAIN_AND_HORIZONTAL_STACK,
+                MONOCLE,
+                "Custom"
+            ]
+        );
+        // Workspaces with layouts of their own do not get new ones.
+        assert!(!names(layout_manager.layouts(1, 1)).contains(&"Custom".to_string()));
+    }
+
+    #[test]
+    fn removing_a_layout_moves_on_to_the_next_one() {
+        let mut layout_manager = layout_manager();
+        layout_manager.set_layout(2, 1, MONOCLE);
+        assert!(layout_manager.remove_layout(MONOCLE).is_ok());
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(2, 1).name);
+        assert!(layout_manager.remove_layout(MONOCLE).is_err());
+
+        // Workspace 7 has no other layout.
+        assert!(layout_manager.remove_layout(EVEN_VERTICAL).is_err());
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(7, 1).name);
+    }
+
+    #[test]
+    fn layouts_still_in_use_are_not_removed() {
+        let mut layout_manager = layout_manager();
+        layout_manager
+            .default_layouts
+            .insert(2, MONOCLE.to_string());
+        let err = layout_manager.remove_layout(MONOCLE).unwrap_err();
+        assert!(err.contains("workspace 2"));
+
+        layout_manager.default_layouts.remove(&2);
+        layout_manager.window_count_layouts = vec![WindowCountLayout {
+            min: 1,
+            max: Some(1),
+            layout: MONOCLE.to_string(),
+        }];
+        assert!(layout_manager.remove_layout(MONOCLE).is_err());
+        assert!(
+            layout_manager
+                .available_layouts()
+                .iter()
+                .any(|l| l.name == MONOCLE)
+        );
+    }
+
+    #[test]
+    fn layouts_follow_the_window_count() {
+        let picker = |min, max, layout: &str| WindowCountLayout {
+            min,
+            max,
+            layout: layout.to_string(),
+        };
+        let config = TestConfig {
+            layouts: vec![
+                MONOCLE.to_string(),
+                EVEN_VERTICAL.to_string(),
+                layouts::MAIN_AND_HORIZONTAL_STACK.to_string(),
+            ],
+            layout_definitions: Layouts::default().layouts,
+            window_count_layouts: vec![picker(1, Some(1), MONOCLE), picker(2, None, EVEN_VERTICAL)],
+            workspaces: Some(vec![
+                Workspace::default(),
+                Workspace {
+                    window_count_layouts: Some(vec![]),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        let mut layout_manager = LayoutManager::new(&config);
+
+        layout_manager.follow_window_count(1, 1, 3);
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(1, 1).name);
+        layout_manager.follow_window_count(1, 1, 1);
+        assert_eq!(MONOCLE, &layout_manager.layout(1, 1).name);
+
+        // A layout chosen by hand stays until the number of windows changes.
+        layout_manager.set_layout(1, 1, layouts::MAIN_AND_HORIZONTAL_STACK);
+        layout_manager.follow_window_count(1, 1, 1);
+        assert_eq!(
+            layouts::MAIN_AND_HORIZONTAL_STACK,
+            &layout_manager.layout(1, 1).name
+        );
+        layout_manager.follow_window_count(1, 1, 2);
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(1, 1).name);
+
+        // Workspace 2 replaces the pickers with none.
+        layout_manager.follow_window_count(2, 1, 2);
+        assert_eq!(MONOCLE, &layout_manager.layout(2, 1).name);
+    }
+
+    #[test]
+    fn workspaces_which_are_still_there_keep_their_layouts() {
+        let config = TestConfig {
+            layouts: vec![MONOCLE.to_string(), EVEN_VERTICAL.to_string()],
+            layout_definitions: Layouts::default().layouts,
+            ..Default::default()
+        };
+        let mut layout_manager = LayoutManager::new(&config);
+        let workspace = || Workspace {
+            layouts: Some(vec![MONOCLE.to_string(), EVEN_VERTICAL.to_string()]),
+            default_layout: Some(MONOCLE.to_string()),
+            ..Default::default()
+        };
+        let three = [workspace(), workspace(), workspace()];
+        layout_manager.load_workspaces(&config, &three);
+        layout_manager.set_layout(1, 1, EVEN_VERTICAL);
+        layout_manager.set_layout(3, 1, EVEN_VERTICAL);
+
+        layout_manager.load_workspaces(&config, &three[..2]);
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(1, 1).name);
+        assert_eq!(MONOCLE, &layout_manager.layout(2, 1).name);
+        assert!(layout_manager.layout_maybe(3, 1).is_none());
+    }
+
     #[test]
     fn default_layouts_should_be_set() {
         let mut layout_manager = layout_manager();
@@ -0,0 +1,326 @@
+//! The split trees of the [`MANUAL`](super::MANUAL) layout, which tiles windows i3-style.
+use crate::models::{Handle, WindowHandle};
+use leftwm_layouts::geometry::Rect;
+use serde::{Deserialize, Serialize};
+
+/// The smallest share either half of a split keeps when it is resized.
+const MIN_RATIO: f32 = 0.1;
+
+/// How the two halves of a split are placed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Side by side.
+    #[default]
+    Horizontal,
+    /// One above the other.
+    Vertical,
+}
+
+impl Orientation {
+    const fn flipped(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+}
+
+/// A binary tree splitting the tiled windows of a tag.
+///
+/// A new window splits the window focused before it, in the orientation requested with
+/// [`Self::split`], or else across the split that window is in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SplitTree<H: Handle> {
+    #[serde(bound = "")]
+    root: Option<Node<H>>,
+    /// The orientation the next window splits the focused one in.
+    next_split: Option<Orientation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+enum Node<H: Handle> {
+    Window(#[serde(bound = "")] WindowHandle<H>),
+    Split {
+        orientation: Orientation,
+        /// The share of the first half.
+        ratio: f32,
+        #[serde(bound = "")]
+        halves: Box<[Node<H>; 2]>,
+    },
+}
+
+impl<H: Handle> SplitTree<H> {
+    /// The windows of the tree, from left to right and top to bottom.
+    pub fn windows(&self) -> Vec<WindowHandle<H>> {
+        let mut windows = vec![];
+        if let Some(root) = &self.root {
+            root.windows(&mut windows);
+        }
+        windows
+    }
+
+    /// Drops the windows which are no longer `tiled` and adds the new ones, splitting the window
+    /// which was focused last according to `focus_history`.
+    pub fn sync(
+        &mut self,
+        tiled: &[WindowHandle<H>],
+        focus_history: impl IntoIterator<Item = WindowHandle<H>>,
+    ) {
+        let mut windows = self.windows();
+        for handle in windows.iter().filter(|h| !tiled.contains(h)) {
+            self.root = self.root.take().and_then(|root| root.remove(handle));
+        }
+        windows.retain(|h| tiled.contains(h));
+
+        let new: Vec<_> = tiled.iter().filter(|h| !windows.contains(h)).collect();
+        if new.is_empty() {
+            return;
+        }
+        let mut target = focus_history
+            .into_iter()
+            .find(|h| windows.contains(h))
+            .or_else(|| windows.last().copied());
+        for &handle in new {
+            match (&mut self.root, target) {
+                (Some(root), Some(target)) => {
+                    root.insert(&target, handle, self.next_split.take(), None);
+                }
+                _ => self.root = Some(Node::Window(handle)),
+            }
+            target = Some(handle);
+        }
+    }
+
+    /// Makes the next window split the focused one in `orientation`.
+    pub fn split(&mut self, orientation: Orientation) {
+        self.next_split = Some(orientation);
+    }
+
+    /// Flips the orientation of the split holding `handle`.
+    ///
+    /// Returns false if `handle` is not split from another window.
+    pub fn toggle_orientation(&mut self, handle: &WindowHandle<H>) -> bool {
+        let Some((orientation, _, _)) = self.root.as_mut().and_then(|root| root.parent_of(handle))
+        else {
+            return false;
+        };
+        *orientation = orientation.flipped();
+        true
+    }
+
+    /// Grows the share of `handle` in the split holding it by `delta`, eg. `0.05` for 5%, or
+    /// shrinks it for a negative `delta`.
+    ///
+    /// Returns false if `handle` is not split from another window.
+    pub fn resize(&mut self, handle: &WindowHandle<H>, delta: f32) -> bool {
+        let Some((_, ratio, is_first)) = self.root.as_mut().and_then(|root| root.parent_of(handle))
+        else {
+            return false;
+        };
+        let delta = if is_first { delta } else { -delta };
+        *ratio = (*ratio + delta).clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+        true
+    }
+
+    /// Splits `rect` between the windows of the tree.
+    pub fn rects(&self, rect: Rect) -> Vec<(WindowHandle<H>, Rect)> {
+        let mut rects = vec![];
+        if let Some(root) = &self.root {
+            root.rects(rect, &mut rects);
+        }
+        rects
+    }
+}
+
+impl<H: Handle> Node<H> {
+    fn windows(&self, windows: &mut Vec<WindowHandle<H>>) {
+        match self {
+            Self::Window(handle) => windows.push(*handle),
+            Self::Split { halves, .. } => halves.iter().for_each(|half| half.windows(windows)),
+        }
+    }
+
+    /// Returns the node without `handle`, or `None` if nothing is left of it.
+    fn remove(self, handle: &WindowHandle<H>) -> Option<Self> {
+        match self {
+            Self::Window(h) if &h == handle => None,
+            Self::Window(_) => Some(self),
+            Self::Split {
+                orientation,
+                ratio,
+                halves,
+            } => {
+                let [first, second] = *halves;
+                match (first.remove(handle), second.remove(handle)) {
+                    (Some(first), Some(second)) => Some(Self::Split {
+                        orientation,
+                        ratio,
+                        halves: Box::new([first, second]),
+                    }),
+                    // The remaining half takes the place of the split.
+                    (Some(half), None) | (None, Some(half)) => Some(half),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+
+    /// Splits the window `target` between it and `handle`. Without an `orientation`, the split is
+    /// placed across the `parent` one.
+    fn insert(
+        &mut self,
+        target: &WindowHandle<H>,
+        handle: WindowHandle<H>,
+        orientation: Option<Orientation>,
+        parent: Option<Orientation>,
+    ) -> bool {
+        match self {
+            Self::Window(h) if h == target => {
+                *self = Self::Split {
+                    orientation: orientation.unwrap_or_else(|| {
+                        parent.map_or_else(Orientation::default, Orientation::flipped)
+                    }),
+                    ratio: 0.5,
+                    halves: Box::new([Self::Window(*target), Self::Window(handle)]),
+                };
+                true
+            }
+            Self::Window(_) => false,
+            Self::Split {
+                orientation: parent,
+                halves,
+                ..
+            } => {
+                let parent = Some(*parent);
+                halves
+                    .iter_mut()
+                    .any(|half| half.insert(target, handle, orientation, parent))
+            }
+        }
+    }
+
+    /// The orientation and ratio of the split directly holding `handle`, and whether `handle` is
+    /// its first half.
+    fn parent_of(
+        &mut self,
+        handle: &WindowHandle<H>,
+    ) -> Option<(&mut Orientation, &mut f32, bool)> {
+        let Self::Split {
+            orientation,
+            ratio,
+            halves,
+        } = self
+        else {
+            return None;
+        };
+        let is = |half: &Self| matches!(half, Self::Window(h) if h == handle);
+        if is(&halves[0]) || is(&halves[1]) {
+            let is_first = is(&halves[0]);
+            return Some((orientation, ratio, is_first));
+        }
+        halves.iter_mut().find_map(|half| half.parent_of(handle))
+    }
+
+    fn rects(&self, rect: Rect, rects: &mut Vec<(WindowHandle<H>, Rect)>) {
+        match self {
+            Self::Window(handle) => rects.push((*handle, rect)),
+            Self::Split {
+                orientation,
+                ratio,
+                halves,
+            } => {
+                let (first, second) = split_rect(rect, *orientation, *ratio);
+                halves[0].rects(first, rects);
+                halves[1].rects(second, rects);
+            }
+        }
+    }
+}
+
+/// Splits `rect` in two, giving `ratio` of it to the first half.
+fn split_rect(rect: Rect, orientation: Orientation, ratio: f32) -> (Rect, Rect) {
+    match orientation {
+        Orientation::Horizontal => {
+            let w = (rect.w as f32 * ratio).round() as u32;
+            let second = Rect {
+                x: rect.x + w as i32,
+                w: rect.w - w,
+                ..rect
+            };
+            (Rect { w, ..rect }, second)
+        }
+        Orientation::Vertical => {
+            let h = (rect.h as f32 * ratio).round() as u32;
+            let second = Rect {
+                y: rect.y + h as i32,
+                h: rect.h - h,
+                ..rect
+            };
+            (Rect { h, ..rect }, second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MockHandle;
+
+    const RECT: Rect = Rect {
+        x: 0,
+        y: 0,
+        w: 800,
+        h: 600,
+    };
+
+    fn handles(ids: &[MockHandle]) -> Vec<WindowHandle<MockHandle>> {
+        ids.iter().copied().map(WindowHandle).collect()
+    }
+
+    #[test]
+    fn new_windows_split_the_last_focused_one() {
+        let mut tree = SplitTree::default();
+        tree.sync(&handles(&[1, 2]), handles(&[2]));
+        tree.split(Orientation::Vertical);
+        // Window 3 splits window 1 on top of each other, window 4 splits 3 side by side.
+        tree.sync(&handles(&[1, 2, 3]), handles(&[1, 2]));
+        tree.sync(&handles(&[1, 2, 3, 4]), handles(&[3]));
+        assert_eq!(tree.windows(), handles(&[1, 3, 4, 2]));
+
+        let rects = tree.rects(RECT);
+        let rect = |x, y, w, h| Rect { x, y, w, h };
+        assert_eq!(
+            rects.iter().map(|(_, r)| *r).collect::<Vec<_>>(),
+            [
+                rect(0, 0, 400, 300),
+                rect(0, 300, 200, 300),
+                rect(200, 300, 200, 300),
+                rect(400, 0, 400, 600),
+            ]
+        );
+
+        // The sibling of a closed window takes its place.
+        tree.sync(&handles(&[1, 2, 4]), handles(&[]));
+        assert_eq!(
+            tree.rects(RECT)[1],
+            (WindowHandle(4), rect(0, 300, 400, 300))
+        );
+    }
+
+    #[test]
+    fn splits_can_be_flipped_and_resized() {
+        let mut tree = SplitTree::default();
+        tree.sync(&handles(&[1, 2]), handles(&[]));
+        assert!(tree.resize(&WindowHandle(2), 0.25));
+        assert!(tree.resize(&WindowHandle(2), 0.25));
+        assert!(tree.toggle_orientation(&WindowHandle(1)));
+
+        let rects = tree.rects(RECT);
+        assert_eq!(rects[0].1.h, 60);
+        assert_eq!(rects[1].1.y, 60);
+
+        tree.sync(&handles(&[2]), handles(&[]));
+        assert!(!tree.resize(&WindowHandle(2), 0.1));
+        assert!(!tree.toggle_orientation(&WindowHandle(2)));
+    }
+}
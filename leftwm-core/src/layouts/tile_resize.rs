@@ -0,0 +1,315 @@
+//! Resizing tiled windows by dragging the boundaries between them with the mouse.
+use super::Orientation;
+use crate::models::{Handle, TagId, WindowHandle, WorkspaceId};
+use leftwm_layouts::Layout;
+use leftwm_layouts::geometry::{Rect, Size};
+
+/// The smallest share either side of a boundary keeps when it is dragged.
+const MIN_SHARE: f32 = 0.1;
+
+/// The tiles `layout` arranges a window of each of the `weights` in within `container`, before
+/// the windows are shrunk to their size hints. The stack tiles are resized by the `weights`.
+pub fn tile_rects(layout: &Layout, container: &Rect, weights: &[f32]) -> Vec<Rect> {
+    let mut rects = leftwm_layouts::apply(layout, weights.len(), container);
+    apply_weights(&mut rects, layout.main_window_count().unwrap_or(0), weights);
+    rects
+}
+
+/// Resizes the stack tiles of `rects`, which follow the `main_count` main tiles, by the `weights`
+/// of their windows.
+///
+/// Neighbouring tiles of the same column or row share its height or width by their weights, tiles
+/// of other stacks, like grids, are left alone.
+fn apply_weights(rects: &mut [Rect], main_count: usize, weights: &[f32]) {
+    let weight = |i: usize| weights.get(i).copied().unwrap_or(1.0);
+    let mut start = main_count;
+    while start + 1 < rects.len() {
+        let Some(orientation) = line_of(&rects[start], &rects[start + 1]) else {
+            start += 1;
+            continue;
+        };
+        let mut end = start + 1;
+        while end + 1 < rects.len() && line_of(&rects[end], &rects[end + 1]) == Some(orientation) {
+            end += 1;
+        }
+
+        let mut line: Vec<usize> = (start..=end).collect();
+        line.sort_by_key(|&i| span(&rects[i], orientation).0);
+        let origin = span(&rects[line[0]], orientation).0;
+        let total: u32 = line.iter().map(|&i| span(&rects[i], orientation).1).sum();
+        let total_weight: f32 = line.iter().map(|&i| weight(i)).sum();
+        let mut before = 0.0;
+        for i in line {
+            let from = (total as f32 * before / total_weight).round() as u32;
+            before += weight(i);
+            let to = (total as f32 * before / total_weight).round() as u32;
+            set_span(&mut rects[i], orientation, origin + from as i32, to - from);
+        }
+        start = end + 1;
+    }
+}
+
+/// A tile which can be dragged, its window, rectangle and weight.
+pub type Tile<H> = (WindowHandle<H>, Rect, f32);
+
+/// Dragging a tiled window with the mouse, which moves the boundary between the main and the stack
+/// tiles and the one between the window and its neighbour in the stack.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileResize<H: Handle> {
+    pub workspace: WorkspaceId,
+    pub tag: TagId,
+    main: Option<MainBoundary>,
+    stack: Option<StackBoundary<H>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MainBoundary {
+    orientation: Orientation,
+    /// Whether the main size grows (1) or shrinks (-1) when dragging towards the end.
+    sign: i32,
+    size: Size,
+    /// The size of the workspace along `orientation`, which relative main sizes are a share of.
+    whole: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StackBoundary<H: Handle> {
+    orientation: Orientation,
+    /// Whether the window grows (1) or shrinks (-1) when dragging towards the end.
+    sign: i32,
+    windows: [WindowHandle<H>; 2],
+    sizes: [u32; 2],
+    weights: [f32; 2],
+}
+
+impl<H: Handle> TileResize<H> {
+    /// Starts dragging the window `handle` among the `tiles` of `tag` in the `container` of
+    /// `workspace`, the first `main_count` of which are the main tiles of a layout with `main_size`.
+    ///
+    /// Returns `None` if there is no boundary to drag.
+    pub fn new(
+        handle: &WindowHandle<H>,
+        workspace: WorkspaceId,
+        tag: TagId,
+        container: Rect,
+        tiles: &[Tile<H>],
+        main_count: usize,
+        main_size: Option<Size>,
+    ) -> Option<Self> {
+        let index = tiles.iter().position(|(h, _, _)| h == handle)?;
+        let (_, rect, weight) = tiles[index];
+        let (main_tiles, stack_tiles) = tiles.split_at(main_count.min(tiles.len()));
+
+        let main = main_size
+            .zip(main_tiles.first().zip(stack_tiles.first()))
+            .map(|(size, ((_, main, _), (_, stack, _)))| {
+                let orientation = if line_of_columns(main, stack) {
+                    Orientation::Horizontal
+                } else {
+                    Orientation::Vertical
+                };
+                let after = |rect: &Rect| span(rect, orientation).0 > span(main, orientation).0;
+                let grows = if index < main_count {
+                    stack_tiles.iter().any(|(_, stack, _)| after(stack))
+                } else {
+                    after(&rect)
+                };
+                MainBoundary {
+                    orientation,
+                    sign: if grows { 1 } else { -1 },
+                    size,
+                    whole: span(&container, orientation).1,
+                }
+            });
+
+        // The boundary after the window, or else the one before it.
+        let stack = (index >= main_count)
+            .then(|| {
+                let neighbours = stack_tiles.iter().filter(|(h, _, _)| h != handle);
+                let next = neighbours.clone().find_map(|tile| {
+                    let orientation = line_of(&rect, &tile.1)?;
+                    let (position, size) = span(&rect, orientation);
+                    (span(&tile.1, orientation).0 == position + size as i32).then_some((
+                        orientation,
+                        1,
+                        tile,
+                    ))
+                });
+                next.or_else(|| {
+                    neighbours.clone().find_map(|tile| {
+                        let orientation = line_of(&rect, &tile.1)?;
+                        let (position, size) = span(&tile.1, orientation);
+                        (position + size as i32 == span(&rect, orientation).0).then_some((
+                            orientation,
+                            -1,
+                            tile,
+                        ))
+                    })
+                })
+            })
+            .flatten()
+            .map(
+                |(orientation, sign, (neighbour, other, other_weight))| StackBoundary {
+                    orientation,
+                    sign,
+                    windows: [*handle, *neighbour],
+                    sizes: [span(&rect, orientation).1, span(other, orientation).1],
+                    weights: [weight, *other_weight],
+                },
+            );
+
+        (main.is_some() || stack.is_some()).then_some(Self {
+            workspace,
+            tag,
+            main,
+            stack,
+        })
+    }
+
+    /// Moves the boundaries by the distance the mouse was dragged, changing the main size of
+    /// `layout` and returning the new weights of the windows.
+    pub fn drag(&self, (x, y): (i32, i32), layout: &mut Layout) -> Vec<(WindowHandle<H>, f32)> {
+        let along = |orientation| match orientation {
+            Orientation::Horizontal => x,
+            Orientation::Vertical => y,
+        };
+
+        if let Some(main) = &self.main {
+            let delta = main.sign * along(main.orientation);
+            let whole = main.whole as f32;
+            layout.set_main_size(match main.size {
+                Size::Ratio(ratio) => {
+                    Size::Ratio((ratio + delta as f32 / whole).clamp(MIN_SHARE, 1.0 - MIN_SHARE))
+                }
+                Size::Pixel(pixels) => Size::Pixel((pixels + delta).clamp(
+                    (whole * MIN_SHARE) as i32,
+                    (whole * (1.0 - MIN_SHARE)) as i32,
+                )),
+            });
+        }
+
+        let Some(stack) = &self.stack else {
+            return vec![];
+        };
+        let total = (stack.sizes[0] + stack.sizes[1]) as f32;
+        let size = (stack.sizes[0] as f32 + (stack.sign * along(stack.orientation)) as f32)
+            .clamp(total * MIN_SHARE, total * (1.0 - MIN_SHARE));
+        let total_weight = stack.weights[0] + stack.weights[1];
+        let weight = total_weight * size / total;
+        vec![
+            (stack.windows[0], weight),
+            (stack.windows[1], total_weight - weight),
+        ]
+    }
+}
+
+/// The orientation of the line `a` and `b` are neighbours in, if they are.
+fn line_of(a: &Rect, b: &Rect) -> Option<Orientation> {
+    if a.x == b.x && a.w == b.w {
+        Some(Orientation::Vertical)
+    } else if a.y == b.y && a.h == b.h {
+        Some(Orientation::Horizontal)
+    } else {
+        None
+    }
+}
+
+/// Whether `a` and `b` are side by side.
+fn line_of_columns(a: &Rect, b: &Rect) -> bool {
+    a.x + a.w as i32 <= b.x || b.x + b.w as i32 <= a.x
+}
+
+/// The position and size of `rect` along `orientation`.
+fn span(rect: &Rect, orientation: Orientation) -> (i32, u32) {
+    match orientation {
+        Orientation::Horizontal => (rect.x, rect.w),
+        Orientation::Vertical => (rect.y, rect.h),
+    }
+}
+
+fn set_span(rect: &mut Rect, orientation: Orientation, position: i32, size: u32) {
+    match orientation {
+        Orientation::Horizontal => (rect.x, rect.w) = (position, size),
+        Orientation::Vertical => (rect.y, rect.h) = (position, size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MockHandle;
+
+    fn rect(x: i32, y: i32, w: u32, h: u32) -> Rect {
+        Rect { x, y, w, h }
+    }
+
+    #[test]
+    fn stack_tiles_are_shared_by_weight() {
+        let container = rect(0, 0, 800, 600);
+        let rects = tile_rects(&Layout::default(), &container, &[1.0, 2.0, 1.0]);
+        assert_eq!(
+            rects,
+            [
+                rect(0, 0, 400, 600),
+                rect(400, 0, 400, 400),
+                rect(400, 400, 400, 200)
+            ]
+        );
+    }
+
+    #[test]
+    fn dragging_moves_the_main_and_stack_boundaries() {
+        let container = rect(0, 0, 800, 600);
+        let tiles: Vec<Tile<MockHandle>> = [
+            rect(0, 0, 400, 600),
+            rect(400, 0, 400, 300),
+            rect(400, 300, 400, 300),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, rect)| (WindowHandle(i as MockHandle), rect, 1.0))
+        .collect();
+        let start = |handle, main_size| {
+            TileResize::new(&WindowHandle(handle), 1, 1, container, &tiles, 1, main_size).unwrap()
+        };
+        let mut layout = Layout::default();
+
+        // Dragging the last stack window up grows it, and the stack left grows it too.
+        let weights = start(2, layout.main_size()).drag((-80, -150), &mut layout);
+        assert_eq!(layout.main_size(), Some(Size::Ratio(0.4)));
+        assert_eq!(weights, [(WindowHandle(2), 1.5), (WindowHandle(1), 0.5)]);
+
+        // The main window has no neighbour in the stack.
+        let weights = start(0, layout.main_size()).drag((800, 0), &mut layout);
+        assert_eq!(layout.main_size(), Some(Size::Ratio(0.9)));
+        assert!(weights.is_empty());
+    }
+
+    #[test]
+    fn the_main_boundary_of_a_rotated_layout_moves_with_the_height() {
+        let container = rect(0, 0, 800, 600);
+        let mut layout = Layout {
+            rotate: leftwm_layouts::geometry::Rotation::East,
+            ..Layout::default()
+        };
+        let tiles: Vec<Tile<MockHandle>> = leftwm_layouts::apply(&layout, 3, &container)
+            .into_iter()
+            .enumerate()
+            .map(|(i, rect)| (WindowHandle(i as MockHandle), rect, 1.0))
+            .collect();
+        assert_eq!(tiles[0].1, rect(0, 0, 800, 300));
+
+        let resize = TileResize::new(
+            &WindowHandle(0),
+            1,
+            1,
+            container,
+            &tiles,
+            1,
+            layout.main_size(),
+        );
+        let weights = resize.unwrap().drag((0, 60), &mut layout);
+        assert_eq!(layout.main_size(), Some(Size::Ratio(0.6)));
+        assert!(weights.is_empty());
+    }
+}
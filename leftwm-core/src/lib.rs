@@ -41,6 +41,7 @@ pub use models::Window;
 pub use models::Workspace;
 pub use state::State;
 pub use utils::child_process;
-pub use utils::command_pipe::{CommandPipe, pipe_name};
-pub use utils::return_pipe::ReturnPipe;
+pub use utils::command_socket::{
+    CommandError, CommandResult, CommandSocket, ErrorKind, Request, Response, socket_name,
+};
 pub use utils::state_socket::StateSocket;
@@ -8,6 +8,7 @@ mod mode;
 mod scratchpad;
 mod screen;
 mod size;
+mod size_hints;
 mod tag;
 mod window;
 mod window_change;
@@ -17,8 +18,10 @@ mod workspace;
 mod xyhw;
 mod xyhw_change;
 
+pub mod criteria;
 pub mod dto;
 
+pub use criteria::{Criteria, Pattern};
 pub use dock_area::DockArea;
 pub use focus_manager::FocusBehaviour;
 pub use focus_manager::FocusManager;
@@ -31,6 +34,7 @@ pub use mode::Mode;
 pub use scratchpad::{ScratchPad, ScratchPadName};
 pub use screen::{BBox, Screen};
 pub use size::Size;
+pub use size_hints::SizeHints;
 pub use window::Handle;
 #[cfg(test)]
 pub(crate) use window::MockHandle;
@@ -0,0 +1,238 @@
+use super::{Handle, TagId, Window, WorkspaceId};
+use crate::state::State;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Selects windows for a command, eg. `[class="firefox" tag=3]`.
+///
+/// Every given property has to match. `class` and `title` are regular expressions which have to
+/// match the whole `WM_CLASS` (class or instance) or title, exactly like window rules do.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Criteria {
+    pub class: Option<Pattern>,
+    pub title: Option<Pattern>,
+    pub tag: Option<TagId>,
+    pub workspace: Option<WorkspaceId>,
+    pub floating: Option<bool>,
+    pub urgent: Option<bool>,
+}
+
+impl Criteria {
+    /// Parses the inside of a criteria block, eg. `class="firefox" tag=3`.
+    ///
+    /// # Errors
+    ///
+    /// Will error on unknown keys, invalid values or invalid regular expressions.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut criteria = Self::default();
+        for (key, value) in tokenize(raw)? {
+            match key.as_str() {
+                "class" => criteria.class = Some(Pattern::new(&value)?),
+                "title" => criteria.title = Some(Pattern::new(&value)?),
+                "tag" => criteria.tag = Some(parse_value(&key, &value)?),
+                "workspace" => criteria.workspace = Some(parse_value(&key, &value)?),
+                "floating" => criteria.floating = Some(parse_value(&key, &value)?),
+                "urgent" => criteria.urgent = Some(parse_value(&key, &value)?),
+                _ => return Err(format!("unknown criteria `{key}`")),
+            }
+        }
+        if criteria == Self::default() {
+            return Err("criteria must not be empty".to_owned());
+        }
+        Ok(criteria)
+    }
+
+    /// Whether `window` has all the requested properties.
+    pub fn matches<H: Handle>(&self, window: &Window<H>, state: &State<H>) -> bool {
+        let regex_matches = |pattern: &Option<Pattern>, values: [&Option<String>; 2]| {
+            pattern.as_ref().is_none_or(|pattern| {
+                values.iter().any(|value| {
+                    value
+                        .as_deref()
+                        .is_some_and(|s| matches_whole(&pattern.0, s))
+                })
+            })
+        };
+
+        window.is_managed()
+            && regex_matches(&self.class, [&window.res_class, &window.res_name])
+            && regex_matches(&self.title, [&window.legacy_name, &window.name])
+            && self.tag.is_none_or(|tag| window.has_tag(&tag))
+            && self.workspace.is_none_or(|id| {
+                state
+                    .workspaces
+                    .iter()
+                    .any(|ws| ws.id == id && ws.is_displaying(window))
+            })
+            && self
+                .floating
+                .is_none_or(|floating| window.floating() == floating)
+            && self.urgent.is_none_or(|urgent| window.urgent == urgent)
+    }
+}
+
+impl fmt::Display for Criteria {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(class) = &self.class {
+            parts.push(format!("class={:?}", class.as_str()));
+        }
+        if let Some(title) = &self.title {
+            parts.push(format!("title={:?}", title.as_str()));
+        }
+        if let Some(tag) = self.tag {
+            parts.push(format!("tag={tag}"));
+        }
+        if let Some(workspace) = self.workspace {
+            parts.push(format!("workspace={workspace}"));
+        }
+        if let Some(floating) = self.floating {
+            parts.push(format!("floating={floating}"));
+        }
+        if let Some(urgent) = self.urgent {
+            parts.push(format!("urgent={urgent}"));
+        }
+        write!(f, "[{}]", parts.join(" "))
+    }
+}
+
+/// A regular expression of a [`Criteria`], compiled once when the criteria is parsed.
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// # Errors
+    ///
+    /// Will error if `pattern` is not a valid regular expression.
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|e| format!("invalid regex `{pattern}`: {e}"))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Whether `re` matches the whole of `s`.
+///
+/// We check if replacing the text with the regex makes the string empty. If the original string
+/// is already empty, this would match it to every regex, so we need to check for that. However,
+/// if the regex is explicitly for empty strings, we still want empty strings to match it.
+pub fn matches_whole(re: &Regex, s: &str) -> bool {
+    re.replace(s, "").is_empty() && (!s.is_empty() || re.as_str().is_empty())
+}
+
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for criteria `{key}`"))
+}
+
+/// Splits `key=value key="quoted value"` pairs, quotes may be escaped with a backslash.
+fn tokenize(raw: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = vec![];
+    let mut chars = raw.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(pairs);
+        }
+
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|&c| c != '=' && !c.is_whitespace())).collect();
+        if chars.next() != Some('=') {
+            return Err(format!("expected `=` after criteria `{key}`"));
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('\\') => value.extend(chars.next()),
+                    Some('"') => break,
+                    Some(c) => value.push(c),
+                    None => return Err(format!("unterminated quote for criteria `{key}`")),
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())));
+        }
+        pairs.push((key, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manager;
+    use crate::models::{MockHandle, Screen, WindowHandle};
+
+    #[test]
+    fn parse_quoted_and_plain_values() {
+        let criteria = Criteria::parse(r#"class="firefox" title=".*Zoom \"Meeting\".*" tag=3"#)
+            .expect("valid criteria");
+        assert_eq!(
+            criteria.class.as_ref().map(Pattern::as_str),
+            Some("firefox")
+        );
+        assert_eq!(
+            criteria.title.as_ref().map(Pattern::as_str),
+            Some(r#".*Zoom "Meeting".*"#)
+        );
+        assert_eq!(criteria.tag, Some(3));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_criteria() {
+        assert!(Criteria::parse("").is_err());
+        assert!(Criteria::parse("colour=red").is_err());
+        assert!(Criteria::parse("tag=three").is_err());
+        assert!(Criteria::parse(r#"class="(""#).is_err());
+        assert!(Criteria::parse(r#"class="firefox"#).is_err());
+    }
+
+    #[test]
+    fn class_has_to_match_completely() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        window.res_class = Some("firefox-esr".to_string());
+        manager.window_created_handler(window, -1, -1);
+
+        let window = &manager.state.windows[0];
+        let matches = |raw: &str| {
+            Criteria::parse(raw)
+                .unwrap()
+                .matches(window, &manager.state)
+        };
+        assert!(matches("class=firefox.*"));
+        assert!(!matches("class=firefox"));
+        assert!(matches("class=firefox.* tag=1 workspace=1 floating=false"));
+        assert!(!matches("class=firefox.* tag=2"));
+    }
+}
@@ -1,13 +1,16 @@
 use crate::state::State;
 use serde::{Deserialize, Serialize};
 
-use super::Handle;
+use super::{Handle, Window, WindowHandle};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Viewport {
     pub id: usize,
     pub output: String,
     pub tag: String,
+    /// Every tag in view, starting with `tag`.
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub h: u32,
     pub w: u32,
     pub x: i32,
@@ -15,6 +18,60 @@ pub struct Viewport {
     pub layout: String,
 }
 
+/// A managed window, as shown to bars and scripts.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DisplayWindow {
+    /// Serialized the same way the display server serializes its window handles.
+    pub handle: serde_json::Value,
+    pub class: Option<String>,
+    pub title: Option<String>,
+    pub tag: Option<String>,
+    /// Every tag the window is on, starting with `tag`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Id of the workspace currently displaying the window, if any.
+    pub workspace: Option<usize>,
+    /// Whether the window is on screen, that is displayed by a workspace and not minimized.
+    #[serde(default)]
+    pub visible: bool,
+    pub focused: bool,
+    pub floating: bool,
+    pub fullscreen: bool,
+    pub sticky: bool,
+    pub urgent: bool,
+    #[serde(default)]
+    pub minimized: bool,
+    /// Names given to the window by `MarkWindow`, sorted.
+    #[serde(default)]
+    pub marks: Vec<String>,
+    pub h: i32,
+    pub w: i32,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A normal tag, as returned to `GetTags` queries.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DisplayTag {
+    pub id: usize,
+    pub label: String,
+    /// Id of the workspace currently displaying the tag, if any.
+    pub workspace: Option<usize>,
+    pub windows: usize,
+    pub urgent: bool,
+}
+
+/// A configured scratchpad, as returned to `GetScratchpads` queries.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DisplayScratchPad {
+    pub name: String,
+    pub command: String,
+    /// Windows spawned for the scratchpad, the first one is the one toggled.
+    pub windows: Vec<serde_json::Value>,
+    pub visible: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ManagerState {
     pub window_title: Option<String>,
@@ -23,6 +80,11 @@ pub struct ManagerState {
     pub active_desktop: Vec<String>,
     pub working_tags: Vec<String>,
     pub urgent_tags: Vec<String>,
+    #[serde(default)]
+    pub windows: Vec<DisplayWindow>,
+    /// Handles of the minimized windows, the one `RestoreLastMinimized` restores comes last.
+    #[serde(default)]
+    pub minimized: Vec<serde_json::Value>,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -52,11 +114,16 @@ pub struct DisplayWorkspace {
 pub struct DisplayState {
     pub window_title: String,
     pub workspaces: Vec<DisplayWorkspace>,
+    pub windows: Vec<DisplayWindow>,
 }
 
 impl From<ManagerState> for DisplayState {
     fn from(m: ManagerState) -> Self {
-        let visible: Vec<String> = m.viewports.iter().map(|vp| vp.tag.clone()).collect();
+        let visible: Vec<String> = m
+            .viewports
+            .iter()
+            .flat_map(|vp| viewport_tags(vp).iter().cloned())
+            .collect();
         let workspaces = m
             .viewports
             .iter()
@@ -76,10 +143,21 @@ impl From<ManagerState> for DisplayState {
         Self {
             workspaces,
             window_title: m.window_title.unwrap_or_default(),
+            windows: m.windows,
         }
     }
 }
 
+/// The tags in view of `viewport`, falling back to its main tag for states written before
+/// `tags` existed.
+fn viewport_tags(viewport: &Viewport) -> &[String] {
+    if viewport.tags.is_empty() {
+        std::slice::from_ref(&viewport.tag)
+    } else {
+        &viewport.tags
+    }
+}
+
 fn viewport_into_display_workspace(
     all_tags: &[String],
     focused: &[String],
@@ -95,7 +173,7 @@ fn viewport_into_display_workspace(
         .map(|(index, t)| TagsForWorkspace {
             name: t.clone(),
             index,
-            mine: viewport.tag == *t,
+            mine: viewport_tags(viewport).contains(t),
             visible: visible.contains(t),
             focused: focused.contains(t),
             urgent: urgent_tags.contains(t),
@@ -144,6 +222,11 @@ impl<H: Handle> From<&State<H>> for ManagerState {
                 .map(|tag_id| state.tags.get(tag_id).map(|tag| tag.label.clone()))
                 .unwrap()
                 .unwrap();
+            let tags = ws
+                .tags()
+                .filter_map(|tag_id| state.tags.get(tag_id))
+                .map(|tag| tag.label.clone())
+                .collect();
 
             let layout_name: String = ws
                 .tag
@@ -163,6 +246,7 @@ impl<H: Handle> From<&State<H>> for ManagerState {
                 id: ws.id,
                 output,
                 tag: tag_label,
+                tags,
                 x: ws.xyhw.x(),
                 y: ws.xyhw.y(),
                 h: ws.xyhw.h() as u32,
@@ -172,16 +256,22 @@ impl<H: Handle> From<&State<H>> for ManagerState {
         }
         let active_desktop = match state.focus_manager.workspace(&state.workspaces) {
             Some(ws) => ws
-                .tag
-                .iter()
-                .map(|&tag_id| state.tags.get(tag_id).unwrap().label.clone())
+                .tags()
+                .map(|tag_id| state.tags.get(tag_id).unwrap().label.clone())
                 .collect(),
             None => vec![], // todo ??
         };
-        let window_title = match state.focus_manager.window(&state.windows) {
+        let focused = state.focus_manager.window(&state.windows);
+        let window_title = match focused {
             Some(win) => win.name.clone(),
             None => None,
         };
+        let windows = state
+            .windows
+            .iter()
+            .filter(|w| w.is_managed())
+            .map(|w| window_into_display_window(state, w, focused))
+            .collect();
         Self {
             window_title,
             desktop_names: state
@@ -194,6 +284,275 @@ impl<H: Handle> From<&State<H>> for ManagerState {
             active_desktop,
             urgent_tags,
             working_tags,
+            windows,
+            minimized: state.minimized.iter().map(|&h| handle_value(h)).collect(),
         }
     }
 }
+
+/// The kinds of [`StateEvent`]s a state socket peer can subscribe to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    WindowCreated,
+    WindowDestroyed,
+    FocusChanged,
+    TagChanged,
+    LayoutChanged,
+    UrgentChanged,
+    ScreenAdded,
+}
+
+/// Sent by a state socket peer to receive events instead of state snapshots.
+///
+/// An empty list subscribes to every topic.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Subscription {
+    pub subscribe: Vec<Topic>,
+}
+
+impl Subscription {
+    pub fn wants(&self, topic: Topic) -> bool {
+        self.subscribe.is_empty() || self.subscribe.contains(&topic)
+    }
+}
+
+/// A discrete change of the manager state.
+///
+/// Window handles are serialized the same way the display server serializes them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StateEvent {
+    WindowCreated {
+        handle: serde_json::Value,
+    },
+    WindowDestroyed {
+        handle: serde_json::Value,
+    },
+    FocusChanged {
+        handle: Option<serde_json::Value>,
+    },
+    TagChanged {
+        workspace: usize,
+        tag: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+    LayoutChanged {
+        workspace: usize,
+        layout: String,
+    },
+    UrgentChanged {
+        handle: serde_json::Value,
+        urgent: bool,
+    },
+    ScreenAdded {
+        workspace: usize,
+        output: String,
+        h: u32,
+        w: u32,
+        x: i32,
+        y: i32,
+    },
+}
+
+impl StateEvent {
+    pub const fn topic(&self) -> Topic {
+        match self {
+            Self::WindowCreated { .. } => Topic::WindowCreated,
+            Self::WindowDestroyed { .. } => Topic::WindowDestroyed,
+            Self::FocusChanged { .. } => Topic::FocusChanged,
+            Self::TagChanged { .. } => Topic::TagChanged,
+            Self::LayoutChanged { .. } => Topic::LayoutChanged,
+            Self::UrgentChanged { .. } => Topic::UrgentChanged,
+            Self::ScreenAdded { .. } => Topic::ScreenAdded,
+        }
+    }
+}
+
+/// The parts of the state events are derived from, compared between two writes of the
+/// state socket.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventSnapshot {
+    /// Handle and urgency of every window.
+    windows: Vec<(serde_json::Value, bool)>,
+    focused: Option<serde_json::Value>,
+    viewports: Vec<Viewport>,
+}
+
+impl<H: Handle> From<&State<H>> for EventSnapshot {
+    fn from(state: &State<H>) -> Self {
+        let windows = state
+            .windows
+            .iter()
+            .map(|w| (handle_value(w.handle), w.urgent))
+            .collect();
+        let focused = state
+            .focus_manager
+            .window(&state.windows)
+            .map(|w| handle_value(w.handle));
+        Self {
+            windows,
+            focused,
+            viewports: ManagerState::from(state).viewports,
+        }
+    }
+}
+
+fn window_into_display_window<H: Handle>(
+    state: &State<H>,
+    window: &Window<H>,
+    focused: Option<&Window<H>>,
+) -> DisplayWindow {
+    let xyhw = window.calculated_xyhw();
+    let mut marks: Vec<String> = state
+        .marks
+        .iter()
+        .filter(|(_, handle)| **handle == window.handle)
+        .map(|(name, _)| name.clone())
+        .collect();
+    marks.sort();
+    let workspace = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.is_displaying(window))
+        .map(|ws| ws.id);
+    let minimized = state.minimized.contains(&window.handle);
+    DisplayWindow {
+        handle: handle_value(window.handle),
+        class: window.res_class.clone(),
+        title: window.name.clone(),
+        tag: window
+            .tag
+            .and_then(|tag_id| state.tags.get(tag_id))
+            .map(|tag| tag.label.clone()),
+        tags: window
+            .tags()
+            .filter_map(|tag_id| state.tags.get(tag_id))
+            .map(|tag| tag.label.clone())
+            .collect(),
+        workspace,
+        visible: workspace.is_some() && !minimized,
+        focused: focused.is_some_and(|f| f.handle == window.handle),
+        floating: window.floating(),
+        fullscreen: window.is_fullscreen(),
+        sticky: window.is_sticky(),
+        urgent: window.urgent,
+        minimized,
+        marks,
+        h: xyhw.h(),
+        w: xyhw.w(),
+        x: xyhw.x(),
+        y: xyhw.y(),
+    }
+}
+
+fn handle_value<H: Handle>(handle: WindowHandle<H>) -> serde_json::Value {
+    serde_json::to_value(handle).unwrap_or_default()
+}
+
+impl EventSnapshot {
+    /// Lists what happened between `old` and `self`.
+    pub fn events_since(&self, old: &Self) -> Vec<StateEvent> {
+        let mut events = vec![];
+
+        for (handle, _) in &old.windows {
+            if !self.windows.iter().any(|(h, _)| h == handle) {
+                events.push(StateEvent::WindowDestroyed {
+                    handle: handle.clone(),
+                });
+            }
+        }
+        for (handle, urgent) in &self.windows {
+            match old.windows.iter().find(|(h, _)| h == handle) {
+                None => events.push(StateEvent::WindowCreated {
+                    handle: handle.clone(),
+                }),
+                Some((_, was_urgent)) if was_urgent != urgent => {
+                    events.push(StateEvent::UrgentChanged {
+                        handle: handle.clone(),
+                        urgent: *urgent,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        for vp in &self.viewports {
+            match old.viewports.iter().find(|o| o.id == vp.id) {
+                None => events.push(StateEvent::ScreenAdded {
+                    workspace: vp.id,
+                    output: vp.output.clone(),
+                    h: vp.h,
+                    w: vp.w,
+                    x: vp.x,
+                    y: vp.y,
+                }),
+                Some(o) => {
+                    if o.tag != vp.tag || o.tags != vp.tags {
+                        events.push(StateEvent::TagChanged {
+                            workspace: vp.id,
+                            tag: vp.tag.clone(),
+                            tags: vp.tags.clone(),
+                        });
+                    }
+                    if o.layout != vp.layout {
+                        events.push(StateEvent::LayoutChanged {
+                            workspace: vp.id,
+                            layout: vp.layout.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if self.focused != old.focused {
+            events.push(StateEvent::FocusChanged {
+                handle: self.focused.clone(),
+            });
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manager;
+    use crate::models::{MockHandle, Screen};
+
+    #[test]
+    fn manager_state_lists_managed_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::<MockHandle>(1), Some("term".into()), None);
+        window.res_class = Some("Alacritty".into());
+        manager.window_created_handler(window, -1, -1);
+        manager.window_created_handler(
+            Window::new(WindowHandle::<MockHandle>(2), None, None),
+            -1,
+            -1,
+        );
+        manager.command_handler(&crate::Command::SendWindowToTag {
+            window: Some(WindowHandle(2)),
+            tag: 2,
+        });
+
+        let state = ManagerState::from(&manager.state);
+        assert_eq!(state.windows.len(), 2);
+
+        let term = &state.windows[0];
+        assert_eq!(term.handle, serde_json::json!(1));
+        assert_eq!(term.class.as_deref(), Some("Alacritty"));
+        assert_eq!(term.title.as_deref(), Some("term"));
+        assert_eq!(term.tag.as_deref(), Some("1"));
+        assert_eq!(term.workspace, Some(1));
+        assert!(term.visible);
+
+        let hidden = &state.windows[1];
+        assert_eq!(hidden.tag.as_deref(), Some("2"));
+        assert_eq!(hidden.workspace, None);
+        assert!(!hidden.visible);
+    }
+}
@@ -5,6 +5,7 @@ use crate::config::Config;
 use crate::display_servers::DisplayServer;
 use crate::state::State;
 use crate::utils::child_process::Children;
+use crate::utils::recorder::Recorder;
 use std::sync::{Arc, atomic::AtomicBool};
 
 use super::Handle;
@@ -18,6 +19,9 @@ pub struct Manager<H: Handle, C, SERVER> {
     pub(crate) children: Children,
     pub(crate) reap_requested: Arc<AtomicBool>,
     pub(crate) reload_requested: bool,
+    /// Whether the state saved before a reload is still to be restored by the event loop.
+    pub(crate) restore_pending: bool,
+    pub(crate) recorder: Option<Recorder>,
     pub display_server: SERVER,
 }
 
@@ -34,6 +38,8 @@ where
             children: Default::default(),
             reap_requested: Default::default(),
             reload_requested: false,
+            restore_pending: false,
+            recorder: None,
         }
     }
 }
@@ -79,54 +85,15 @@ impl
     ) -> Self {
         // needs to mimic what the display server would do when it starts,
         // specifically in respect to how workspaces are created for the screens
-        // even more specifically, I'm recreating the behavior of `XlibDisplayServer::initial_events`)
-        // by iterating through the configured workspaces and creating them for screens whose outputs match a workspace's output
-
-        // figure out all the screens that need to be created
-        let mut events = vec![];
-        if let Some(workspaces) = config.workspaces() {
-            for (i, wsc) in workspaces.iter().enumerate() {
-                let mut screen = super::Screen::from(wsc);
-                screen.root =
-                    crate::models::WindowHandle(crate::models::window::MockHandle::default());
-                // If there is a screen corresponding to the given output, create the workspace
-                match screens.iter().find(|s| s.output == wsc.output) {
-                    Some(output_match) => {
-                        if wsc.relative.unwrap_or(false) {
-                            screen.bbox.add(output_match.bbox);
-                        }
-                        screen.id = Some(i + 1);
-                    }
-                    None => continue,
-                }
-
-                // the only events we care about are ScreenCreate events, so instead of collecting DisplayEvents, we'll just collect Screens
-                events.push(screen);
-            }
-
-            let auto_derive_workspaces: bool = config.auto_derive_workspaces() || events.is_empty();
-            let mut next_id = workspaces.len() + 1;
-
-            // If there is no hardcoded workspace layout, add every screen not mentioned in the config.
-            if auto_derive_workspaces {
-                screens
-                    .iter()
-                    .filter(|screen| !workspaces.iter().any(|wsc| wsc.output == screen.output))
-                    .for_each(|screen| {
-                        let mut s = screen.clone();
-                        s.id = Some(next_id);
-                        next_id += 1;
-                        events.push(s);
-                    });
-            }
-        }
+        let mut tracker = crate::utils::screen_tracker::ScreenTracker::new(&config);
+        let events = tracker.update(screens);
 
         // now, create the manager
         let mut manager = Self::new(config);
 
         // and apply the events
-        for screen in events {
-            manager.screen_create_handler(screen);
+        for event in events {
+            manager.display_event_handler(event);
         }
 
         manager
@@ -157,6 +124,24 @@ impl
             ..TestConfig::default()
         })
     }
+
+    /// A manager offering only `layouts`, with a single 800x600 screen.
+    pub fn new_test_with_layouts(tags: Vec<String>, layouts: Vec<String>) -> Self {
+        use crate::config::tests::TestConfig;
+        let mut manager = Self::new(TestConfig {
+            tags,
+            layouts,
+            ..TestConfig::default()
+        });
+        let bbox = super::BBox {
+            x: 0,
+            y: 0,
+            width: 800,
+            height: 600,
+        };
+        manager.screen_create_handler(super::Screen::new(bbox, String::new()));
+        manager
+    }
 }
 
 #[cfg(test)]
@@ -13,7 +13,7 @@ pub struct Screen<H: Handle> {
 }
 
 /// Screen Bounding Box
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BBox {
     pub x: i32,
     pub y: i32,
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// How a window wants to be sized, from its `WM_NORMAL_HINTS`.
+///
+/// Sizes are `(width, height)` and aspect ratios `(numerator, denominator)` of the width to the
+/// height.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizeHints {
+    pub min: Option<(i32, i32)>,
+    pub max: Option<(i32, i32)>,
+    pub base: Option<(i32, i32)>,
+    pub increment: Option<(i32, i32)>,
+    pub min_aspect: Option<(i32, i32)>,
+    pub max_aspect: Option<(i32, i32)>,
+}
+
+impl SizeHints {
+    /// The largest size up to `width` and `height` the hints allow, following ICCCM like dwm does.
+    ///
+    /// The size never grows beyond `width` and `height`, even if the minimum size asks for it.
+    #[must_use]
+    pub fn fit(&self, width: i32, height: i32) -> (i32, i32) {
+        // Either of the base and minimum size stands in for the other one.
+        let (base_w, base_h) = self.base.or(self.min).unwrap_or_default();
+        let (min_w, min_h) = self.min.or(self.base).unwrap_or_default();
+        let base_is_min = (base_w, base_h) == (min_w, min_h);
+
+        let (mut w, mut h) = (width, height);
+        if !base_is_min {
+            w -= base_w;
+            h -= base_h;
+        }
+        let ratio = |aspect: Option<(i32, i32)>| {
+            aspect
+                .filter(|&(numerator, denominator)| numerator > 0 && denominator > 0)
+                .map(|(numerator, denominator)| numerator as f32 / denominator as f32)
+        };
+        if let (Some(min_ratio), Some(max_ratio)) = (ratio(self.min_aspect), ratio(self.max_aspect))
+            && w > 0
+            && h > 0
+        {
+            let current = w as f32 / h as f32;
+            if current > max_ratio {
+                w = (h as f32 * max_ratio).round() as i32;
+            } else if current < min_ratio {
+                h = (w as f32 / min_ratio).round() as i32;
+            }
+        }
+        if base_is_min {
+            w -= base_w;
+            h -= base_h;
+        }
+
+        let (increment_w, increment_h) = self.increment.unwrap_or_default();
+        if increment_w > 0 && w > 0 {
+            w -= w % increment_w;
+        }
+        if increment_h > 0 && h > 0 {
+            h -= h % increment_h;
+        }
+        w = (w + base_w).max(min_w);
+        h = (h + base_h).max(min_h);
+
+        let (max_w, max_h) = self.max.unwrap_or_default();
+        if max_w > 0 {
+            w = w.min(max_w);
+        }
+        if max_h > 0 {
+            h = h.min(max_h);
+        }
+        (w.min(width), h.min(height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_snap_to_increments_above_the_base_size() {
+        // A terminal with 7x14 cells and a 4px padding.
+        let hints = SizeHints {
+            base: Some((4, 4)),
+            min: Some((11, 18)),
+            increment: Some((7, 14)),
+            ..SizeHints::default()
+        };
+        assert_eq!(hints.fit(400, 300), (396, 298));
+        assert_eq!(hints.fit(5, 5), (5, 5));
+    }
+
+    #[test]
+    fn sizes_keep_within_the_maximum_and_aspect_ratio() {
+        let fixed = SizeHints {
+            min: Some((200, 100)),
+            max: Some((200, 100)),
+            ..SizeHints::default()
+        };
+        assert_eq!(fixed.fit(400, 300), (200, 100));
+
+        let video = SizeHints {
+            min_aspect: Some((16, 9)),
+            max_aspect: Some((16, 9)),
+            ..SizeHints::default()
+        };
+        assert_eq!(video.fit(400, 300), (400, 225));
+        assert_eq!(video.fit(400, 100), (178, 100));
+    }
+}
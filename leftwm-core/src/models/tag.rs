@@ -1,5 +1,7 @@
-use super::{Handle, TagId, Xyhw};
-use crate::{Window, Workspace, layouts::LayoutManager};
+use super::{Handle, TagId, WindowHandle, Xyhw};
+use crate::layouts::{ColumnStrip, LayoutManager, MANUAL, SCROLLING, SplitTree, tile_rects};
+use crate::{Window, Workspace};
+use leftwm_layouts::geometry::Rect;
 use serde::{Deserialize, Serialize};
 
 /// Wrapper struct holding all the tags.
@@ -64,6 +66,15 @@ impl Tags {
         id
     }
 
+    /// Create a new tag with the provided label at runtime, eg. by `Command::AddTag`, and append
+    /// it to the list of normal tags. Unlike configured tags, it is kept on a reload of the config.
+    /// The ID will be assigned automatically and returned.
+    pub fn add_new_at_runtime(&mut self, label: &str) -> TagId {
+        let id = self.add_new(label);
+        self.normal[id - 1].added = true;
+        id
+    }
+
     /// Create a new tag with the provided layout, labelling it directly with its ID,
     /// and append it to the list of normal tags.
     /// The ID will be assigned automatically and returned.
@@ -75,8 +86,36 @@ impl Tags {
     // todo: add_new_at(position, label, layout)
     // -> shifting all one to the right and re-number them (vec.insert)
 
-    // todo: remove(id)
-    // -> shifting all right of the removed tag one to the left and re-number them
+    /// Remove the normal tag with the provided ID.
+    /// All tags to the right of the removed tag are shifted
+    /// one to the left and re-numbered accordingly.
+    pub fn remove(&mut self, id: TagId) -> Option<Tag> {
+        if id < 1 || id > self.normal.len() {
+            return None;
+        }
+        let tag = self.normal.remove(id - 1);
+        self.renumber_normal();
+        Some(tag)
+    }
+
+    /// Move the normal tag with the provided ID to `position` (1-based),
+    /// shifting the tags in between and re-numbering them accordingly.
+    pub fn move_to(&mut self, id: TagId, position: TagId) -> Option<()> {
+        let len = self.normal.len();
+        if id < 1 || id > len || position < 1 || position > len {
+            return None;
+        }
+        let tag = self.normal.remove(id - 1);
+        self.normal.insert(position - 1, tag);
+        self.renumber_normal();
+        Some(())
+    }
+
+    fn renumber_normal(&mut self) {
+        for (i, tag) in self.normal.iter_mut().enumerate() {
+            tag.id = i + 1; // tag id starts at 1
+        }
+    }
 
     /// Create a new hidden tag with the provided label,
     /// and append it to the list of hidden tags.
@@ -94,6 +133,7 @@ impl Tags {
                 id: next_id,
                 label: label.to_string(),
                 hidden: true,
+                added: false,
             };
             let id = tag.id;
             self.hidden.push(tag);
@@ -206,6 +246,12 @@ pub struct Tag {
     /// Hidden tags are internal only, and
     /// are unknown to other programs (eg. polybar)
     pub hidden: bool,
+
+    /// Indicates whether the tag was added at
+    /// runtime rather than configured, those
+    /// are restored after the config is reloaded.
+    #[serde(default)]
+    pub added: bool,
 }
 
 // This clippy is mainly for readability, but changing it triggers `error[E0277]` '... is not an iterator'
@@ -218,19 +264,31 @@ impl Tag {
             id,
             label: label.to_owned(),
             hidden: false,
+            added: false,
         }
     }
 
+    /// Arranges the windows of every tag `workspace` displays, using the layout of this tag. A
+    /// window on tags other `workspaces` display too is left to the one laying it out.
+    ///
+    /// The [`MANUAL`] layout tiles them by `split_tree`, where new windows split the one focused
+    /// last according to `focus_history`, and the [`SCROLLING`] layout by `column_strip`, which
+    /// scrolls to that window. Other layouts share the stack between windows by their
+    /// `tile_weight`.
+    #[allow(clippy::too_many_arguments)]
     pub fn update_windows<H: Handle>(
         &self,
         windows: &mut [Window<H>],
         workspace: &Workspace,
+        workspaces: &[Workspace],
         layout_manager: &mut LayoutManager,
+        split_tree: &mut SplitTree<H>,
+        column_strip: &mut ColumnStrip<H>,
+        focus_history: impl IntoIterator<Item = WindowHandle<H>>,
     ) {
-        if let Some(window) = windows
-            .iter_mut()
-            .find(|w| w.has_tag(&self.id) && w.is_fullscreen())
-        {
+        // Hidden windows are neither shown nor take up space in the layout.
+        let shown = |w: &Window<H>| workspace.lays_out(w, workspaces) && !w.is_hidden();
+        if let Some(window) = windows.iter_mut().find(|w| shown(w) && w.is_fullscreen()) {
             window.set_visible(true);
             window.normal = workspace.xyhw;
 
@@ -238,7 +296,7 @@ impl Tag {
             windows
                 .iter_mut()
                 .filter(|w| {
-                    w.has_tag(&self.id)
+                    shown(w)
                         && (w.transient == Some(handle)
                             || w.r#type.is_dialog_like()
                             || w.states.contains(&super::WindowState::Above) && w.floating())
@@ -247,16 +305,13 @@ impl Tag {
                 .for_each(|w| {
                     w.set_visible(true);
                 });
-        } else if let Some(window) = windows
-            .iter_mut()
-            .find(|w| w.has_tag(&self.id) && w.is_maximized())
-        {
+        } else if let Some(window) = windows.iter_mut().find(|w| shown(w) && w.is_maximized()) {
             window.set_visible(true);
             window.normal = workspace.rect().into();
 
             windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.floating())
+                .filter(|w| shown(w) && w.floating())
                 .for_each(|w| {
                     w.set_visible(true);
                 });
@@ -264,7 +319,7 @@ impl Tag {
             // Don't bother updating the other windows when a window is fullscreen.
             // Mark all windows for this workspace as visible.
             let mut all_mine: Vec<&mut Window<H>> =
-                windows.iter_mut().filter(|w| w.has_tag(&self.id)).collect();
+                windows.iter_mut().filter(|w| shown(w)).collect();
             for w in all_mine.iter_mut() {
                 w.set_visible(true);
             }
@@ -272,15 +327,39 @@ impl Tag {
             // Update the location / visibility of all non-floating windows.
             let mut managed_nonfloat: Vec<&mut Window<H>> = windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.is_managed() && !w.floating())
+                .filter(|w| shown(w) && w.is_managed() && !w.floating())
                 .collect();
-            let def = layout_manager.layout(workspace.id, workspace.tag.unwrap_or(1));
-            let rects = leftwm_layouts::apply(def, managed_nonfloat.len(), &workspace.rect());
+            layout_manager.follow_window_count(workspace.id, self.id, managed_nonfloat.len());
+            let def = layout_manager.layout(workspace.id, self.id);
+            let handles: Vec<_> = managed_nonfloat.iter().map(|w| w.handle).collect();
+            let by_handle = |rects: Vec<(WindowHandle<H>, Rect)>| -> Vec<Option<Rect>> {
+                handles
+                    .iter()
+                    .map(|h| {
+                        rects
+                            .iter()
+                            .find(|(handle, _)| handle == h)
+                            .map(|(_, r)| *r)
+                    })
+                    .collect()
+            };
+            let rects = if def.name == MANUAL {
+                split_tree.sync(&handles, focus_history);
+                by_handle(split_tree.rects(workspace.rect()))
+            } else if def.name == SCROLLING {
+                column_strip.sync(&handles, focus_history);
+                by_handle(column_strip.rects(workspace.rect()))
+            } else {
+                let weights: Vec<f32> = managed_nonfloat.iter().map(|w| w.tile_weight).collect();
+                let rects = tile_rects(def, &workspace.rect(), &weights);
+                rects.into_iter().map(Some).collect()
+            };
             for (i, window) in managed_nonfloat.iter_mut().enumerate() {
-                match rects.get(i) {
+                match rects.get(i).copied().flatten() {
                     Some(rect) => {
-                        window.normal = Xyhw::from(*rect);
+                        window.normal = Xyhw::from(rect);
                         window.container_size = Some(workspace.xyhw);
+                        window.fit_size_hints();
                     }
                     None => {
                         window.set_visible(false);
@@ -291,7 +370,7 @@ impl Tag {
             // Update the location of all floating windows.
             windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.is_managed() && w.floating())
+                .filter(|w| shown(w) && w.is_managed() && w.floating())
                 .for_each(|w| w.normal = workspace.xyhw);
         }
     }
@@ -314,6 +393,40 @@ mod tests {
         assert_eq!(code_id, 4);
     }
 
+    #[test]
+    fn removed_and_moved_tags_are_renumbered() {
+        let mut tags = Tags::new();
+        for label in ["home", "chat", "surf", "code"] {
+            tags.add_new(label);
+        }
+        tags.add_new_hidden("NSP");
+        let labels = |tags: &Tags| -> Vec<(usize, String)> {
+            tags.normal()
+                .iter()
+                .map(|tag| (tag.id, tag.label.clone()))
+                .collect()
+        };
+
+        assert_eq!(
+            tags.remove(2).map(|tag| tag.label),
+            Some("chat".to_string())
+        );
+        assert_eq!(
+            labels(&tags),
+            [(1, "home".into()), (2, "surf".into()), (3, "code".into())]
+        );
+
+        assert!(tags.move_to(3, 1).is_some());
+        assert_eq!(
+            labels(&tags),
+            [(1, "code".into()), (2, "home".into()), (3, "surf".into())]
+        );
+
+        assert!(tags.remove(4).is_none());
+        assert!(tags.move_to(1, 4).is_none());
+        assert_eq!(tags.get(usize::MAX).map(|tag| tag.hidden), Some(true));
+    }
+
     #[test]
     fn hidden_tags_are_numbered_in_order() {
         let mut tags = Tags::new();
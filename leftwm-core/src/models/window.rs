@@ -7,7 +7,9 @@ use super::WindowState;
 use super::WindowType;
 use crate::Workspace;
 use crate::config::WindowHidingStrategy;
+use crate::models::FocusOnActivationBehaviour;
 use crate::models::Margins;
+use crate::models::SizeHints;
 use crate::models::TagId;
 use crate::models::Xyhw;
 use crate::models::XyhwBuilder;
@@ -62,11 +64,21 @@ pub struct Window<H: Handle> {
     pub pid: Option<u32>,
     pub r#type: WindowType,
     pub tag: Option<TagId>,
+    /// Tags the window is also shown on, set by `ToggleWindowTag`.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub border: i32,
     pub margin: Margins,
     pub margin_multiplier: f32,
+    /// The share of its column or row of the stack the window tiles in, changed by dragging the
+    /// boundary to a neighbour.
+    #[serde(default = "default_tile_weight")]
+    pub tile_weight: f32,
     pub states: Vec<WindowState>,
     pub requested: Option<Xyhw>,
+    /// The `WM_NORMAL_HINTS` of the window, which its tile is shrunk to.
+    #[serde(default)]
+    pub size_hints: Option<SizeHints>,
     pub normal: Xyhw,
     pub start_loc: Option<Xyhw>,
     pub container_size: Option<Xyhw>,
@@ -75,6 +87,22 @@ pub struct Window<H: Handle> {
     pub res_name: Option<String>,
     pub res_class: Option<String>,
     pub hiding_strategy: Option<WindowHidingStrategy>,
+    /// Whether the window swallows the terminal it was launched from, set by window rules.
+    pub swallow: Option<bool>,
+    /// How the window asking for attention is handled, set by window rules. It is marked as
+    /// urgent if not set.
+    pub urgency: Option<FocusOnActivationBehaviour>,
+    /// Whether the window keeps to its `size_hints` when tiled, set by window rules. It does if
+    /// not set.
+    #[serde(default)]
+    pub honor_size_hints: Option<bool>,
+    /// The terminal hidden while this window is open.
+    #[serde(default, bound = "")]
+    pub swallowed: Option<WindowHandle<H>>,
+}
+
+const fn default_tile_weight() -> f32 {
+    1.0
 }
 
 impl<H: Handle> Window<H> {
@@ -95,12 +123,15 @@ impl<H: Handle> Window<H> {
             legacy_name: None,
             r#type: WindowType::Normal,
             tag: None,
+            extra_tags: vec![],
             border: 1,
             margin: Margins::new(10),
             margin_multiplier: 1.0,
+            tile_weight: default_tile_weight(),
             states: vec![],
             normal: XyhwBuilder::default().into(),
             requested: None,
+            size_hints: None,
             floating: None,
             start_loc: None,
             container_size: None,
@@ -108,6 +139,10 @@ impl<H: Handle> Window<H> {
             res_name: None,
             res_class: None,
             hiding_strategy: None,
+            swallow: None,
+            urgency: None,
+            honor_size_hints: None,
+            swallowed: None,
         }
     }
 
@@ -176,6 +211,11 @@ impl<H: Handle> Window<H> {
         self.states.contains(&WindowState::Sticky)
     }
 
+    #[must_use]
+    pub fn is_hidden(&self) -> bool {
+        self.states.contains(&WindowState::Hidden)
+    }
+
     #[must_use]
     pub fn must_float(&self) -> bool {
         self.must_float
@@ -226,7 +266,7 @@ impl<H: Handle> Window<H> {
         if self.is_fullscreen() {
             value = self.normal.w();
         } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
-            let relative = self.normal + self.floating.unwrap_or_default();
+            let relative = self.floating_xyhw();
             value = relative.w() - (self.border * 2);
         } else {
             value = self.normal.w()
@@ -249,7 +289,7 @@ impl<H: Handle> Window<H> {
         if self.is_fullscreen() {
             value = self.normal.h();
         } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
-            let relative = self.normal + self.floating.unwrap_or_default();
+            let relative = self.floating_xyhw();
             value = relative.h() - (self.border * 2);
         } else {
             value = self.normal.h()
@@ -266,6 +306,24 @@ impl<H: Handle> Window<H> {
         value
     }
 
+    /// Shrinks the tile of the window to the size its `size_hints` allow, centered where the tile
+    /// was, unless a window rule turned them off.
+    pub fn fit_size_hints(&mut self) {
+        let Some(hints) = self
+            .size_hints
+            .filter(|_| self.honor_size_hints != Some(false))
+        else {
+            return;
+        };
+        let (width, height) = (self.width(), self.height());
+        let (fit_width, fit_height) = hints.fit(width, height);
+        let (shrink_w, shrink_h) = (width - fit_width, height - fit_height);
+        self.normal.set_x(self.normal.x() + shrink_w / 2);
+        self.normal.set_y(self.normal.y() + shrink_h / 2);
+        self.normal.set_w(self.normal.w() - shrink_w);
+        self.normal.set_h(self.normal.h() - shrink_h);
+    }
+
     pub fn set_x(&mut self, x: i32) {
         self.normal.set_x(x);
     }
@@ -283,7 +341,7 @@ impl<H: Handle> Window<H> {
         if self.is_fullscreen() {
             self.normal.x()
         } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
-            let relative = self.normal + self.floating.unwrap_or_default();
+            let relative = self.floating_xyhw();
             relative.x()
         } else {
             self.normal.x() + (self.margin.left as f32 * self.margin_multiplier) as i32
@@ -295,13 +353,27 @@ impl<H: Handle> Window<H> {
         if self.is_fullscreen() {
             self.normal.y()
         } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
-            let relative = self.normal + self.floating.unwrap_or_default();
+            let relative = self.floating_xyhw();
             relative.y()
         } else {
             self.normal.y() + (self.margin.top as f32 * self.margin_multiplier) as i32
         }
     }
 
+    /// Where the window floats, spanning `normal` in the directions it is maximized in.
+    fn floating_xyhw(&self) -> Xyhw {
+        let mut relative = self.normal + self.floating.unwrap_or_default();
+        if self.states.contains(&WindowState::MaximizedHorz) {
+            relative.set_x(self.normal.x());
+            relative.set_w(self.normal.w());
+        }
+        if self.states.contains(&WindowState::MaximizedVert) {
+            relative.set_y(self.normal.y());
+            relative.set_h(self.normal.h());
+        }
+        relative
+    }
+
     #[must_use]
     pub fn calculated_xyhw(&self) -> Xyhw {
         XyhwBuilder {
@@ -330,15 +402,57 @@ impl<H: Handle> Window<H> {
 
     pub fn tag(&mut self, tag: &TagId) {
         self.tag = Some(*tag);
+        self.extra_tags.retain(|t| t != tag);
     }
 
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
-        self.tag == Some(*tag)
+        self.tag == Some(*tag) || self.extra_tags.contains(tag)
+    }
+
+    /// All the tags the window is on, starting with its main tag.
+    pub fn tags(&self) -> impl Iterator<Item = TagId> + '_ {
+        self.tag.into_iter().chain(self.extra_tags.iter().copied())
+    }
+
+    /// The tag the window is laid out by, the first of its tags, starting with its main tag, which
+    /// one of `workspaces` displays.
+    #[must_use]
+    pub fn shown_tag(&self, workspaces: &[Workspace]) -> Option<TagId> {
+        self.tags()
+            .find(|tag| workspaces.iter().any(|ws| ws.has_tag(tag)))
+    }
+
+    /// Returns true if the window is tiled alongside the windows of `tag`, that is if the
+    /// workspace displaying `tag` lays it out, or if it is on `tag` when no workspace displays it.
+    #[must_use]
+    pub fn is_on_tag(&self, tag: TagId, workspaces: &[Workspace]) -> bool {
+        match workspaces.iter().find(|ws| ws.has_tag(&tag)) {
+            Some(ws) => ws.lays_out(self, workspaces),
+            None => self.has_tag(&tag),
+        }
     }
 
     pub fn untag(&mut self) {
         self.tag = None;
+        self.extra_tags.clear();
+    }
+
+    /// Puts the window on `tag` too, or takes it off `tag` if it is already there.
+    ///
+    /// Returns `None` if the window is untagged or `tag` is its only tag.
+    pub fn toggle_tag(&mut self, tag: TagId) -> Option<()> {
+        if self.tag? == tag {
+            if self.extra_tags.is_empty() {
+                return None;
+            }
+            self.tag = Some(self.extra_tags.remove(0));
+        } else if let Some(index) = self.extra_tags.iter().position(|&t| t == tag) {
+            self.extra_tags.remove(index);
+        } else {
+            self.extra_tags.push(tag);
+        }
+        Some(())
     }
 
     #[must_use]
@@ -400,4 +514,61 @@ mod tests {
         subject.untag();
         assert!(!subject.has_tag(&1), "was unable to untag the window");
     }
+
+    #[test]
+    fn tiled_window_is_shrunk_to_its_size_hints_and_centered() {
+        let mut subject = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        subject.border = 0;
+        subject.margin = Margins::new(0);
+        subject.size_hints = Some(SizeHints {
+            increment: Some((7, 14)),
+            ..SizeHints::default()
+        });
+        let tile: Xyhw = XyhwBuilder {
+            w: 400,
+            h: 300,
+            ..XyhwBuilder::default()
+        }
+        .into();
+
+        subject.normal = tile;
+        subject.fit_size_hints();
+        assert_eq!(
+            (subject.x(), subject.y(), subject.width(), subject.height()),
+            (0, 3, 399, 294)
+        );
+
+        subject.normal = tile;
+        subject.honor_size_hints = Some(false);
+        subject.fit_size_hints();
+        assert_eq!(subject.normal, tile);
+    }
+
+    #[test]
+    fn floating_window_spans_the_directions_it_is_maximized_in() {
+        let mut subject = Window::new(WindowHandle::<MockHandle>(1), None, None);
+        subject.set_floating(true);
+        subject.border = 0;
+        subject.normal = XyhwBuilder {
+            w: 1000,
+            h: 800,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        subject.set_floating_exact(
+            XyhwBuilder {
+                x: 100,
+                y: 100,
+                w: 300,
+                h: 200,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        subject.states.push(WindowState::MaximizedVert);
+        assert_eq!(
+            (subject.x(), subject.y(), subject.width(), subject.height()),
+            (100, 0, 300, 800)
+        );
+    }
 }
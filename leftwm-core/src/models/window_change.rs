@@ -5,11 +5,13 @@ use super::WindowHandle;
 use super::WindowState;
 use super::WindowType;
 use super::Xyhw;
-use crate::models::{Margins, XyhwChange};
+use crate::models::{Margins, SizeHints, XyhwChange};
+use serde::{Deserialize, Serialize};
 
 type MaybeName = Option<String>;
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(bound = "")]
 pub struct WindowChange<H: Handle> {
     pub handle: WindowHandle<H>,
     pub transient: Option<MaybeWindowHandle<H>>,
@@ -20,6 +22,7 @@ pub struct WindowChange<H: Handle> {
     pub floating: Option<XyhwChange>,
     pub strut: Option<XyhwChange>,
     pub requested: Option<Xyhw>,
+    pub size_hints: Option<SizeHints>,
     pub states: Option<Vec<WindowState>>,
 }
 
@@ -36,6 +39,7 @@ impl<H: Handle> WindowChange<H> {
             floating: None,
             strut: None,
             requested: None,
+            size_hints: None,
             states: None,
         }
     }
@@ -60,8 +64,7 @@ impl<H: Handle> WindowChange<H> {
         if let Some(urgent) = self.urgent {
             let changed_urgent = window.urgent != urgent;
             changed = changed || changed_urgent;
-            // If the window is already visible, there is no need to mark it as urgent
-            window.urgent = urgent && !window.visible();
+            window.urgent = urgent;
         }
         if let Some(mut floating_change) = self.floating {
             // Reposition if dialog or modal.
@@ -82,6 +85,10 @@ impl<H: Handle> WindowChange<H> {
         if let Some(requested) = self.requested {
             window.requested = Some(requested);
         }
+        if let Some(size_hints) = self.size_hints {
+            changed = changed || window.size_hints != Some(size_hints);
+            window.size_hints = Some(size_hints);
+        }
         if let Some(r#type) = &self.r#type {
             let changed_type = &window.r#type != r#type;
             changed = changed || changed_type;
@@ -10,7 +10,10 @@ use super::{Handle, WorkspaceId};
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Workspace {
     // tag represents the currently visible tag
-    pub tag: Option<TagId>, // TODO: Make this a list.
+    pub tag: Option<TagId>,
+    /// Tags displayed alongside `tag`, added by `ToggleTagView`.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub margin: Margins,
     pub margin_multiplier: f32,
     pub gutters: Vec<Gutter>,
@@ -28,7 +31,7 @@ impl fmt::Debug for Workspace {
             f,
             "Workspace {{ id: {}, tags: {:?}, x: {}, y: {} }}",
             self.id,
-            self.tag,
+            self.tags().collect::<Vec<_>>(),
             self.xyhw.x(),
             self.xyhw.y()
         )
@@ -46,6 +49,7 @@ impl Workspace {
     pub fn new(bbox: BBox, id: usize) -> Self {
         Self {
             tag: None,
+            extra_tags: vec![],
             margin: Margins::new(10),
             margin_multiplier: 1.0,
             gutters: vec![],
@@ -95,6 +99,31 @@ impl Workspace {
 
     pub fn show_tag(&mut self, tag: &TagId) {
         self.tag = Some(*tag);
+        self.extra_tags.clear();
+    }
+
+    /// Adds `tag` to the displayed tags, or removes it if it is already displayed.
+    ///
+    /// Returns `None` if `tag` is the only displayed tag.
+    pub fn toggle_tag(&mut self, tag: TagId) -> Option<()> {
+        if self.tag == Some(tag) {
+            if self.extra_tags.is_empty() {
+                return None;
+            }
+            self.tag = Some(self.extra_tags.remove(0));
+        } else if let Some(index) = self.extra_tags.iter().position(|&t| t == tag) {
+            self.extra_tags.remove(index);
+        } else if self.tag.is_some() {
+            self.extra_tags.push(tag);
+        } else {
+            self.tag = Some(tag);
+        }
+        Some(())
+    }
+
+    /// All the displayed tags, starting with the main one.
+    pub fn tags(&self) -> impl Iterator<Item = TagId> + '_ {
+        self.tag.into_iter().chain(self.extra_tags.iter().copied())
     }
 
     #[must_use]
@@ -104,16 +133,23 @@ impl Workspace {
 
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
-        self.tag == Some(*tag)
+        self.tag == Some(*tag) || self.extra_tags.contains(tag)
     }
 
     /// Returns true if the workspace is displays a given window.
     #[must_use]
     pub fn is_displaying<H: Handle>(&self, window: &Window<H>) -> bool {
-        if let Some(tag) = &window.tag {
-            return self.has_tag(tag);
-        }
-        false
+        window.tags().any(|tag| self.has_tag(&tag))
+    }
+
+    /// Returns true if the workspace arranges `window`. A window on tags displayed by several of
+    /// `workspaces` is arranged by the one displaying the first of them, see
+    /// [`Window::shown_tag`].
+    #[must_use]
+    pub fn lays_out<H: Handle>(&self, window: &Window<H>, workspaces: &[Self]) -> bool {
+        window
+            .shown_tag(workspaces)
+            .is_some_and(|tag| self.has_tag(&tag))
     }
 
     /// Returns true if the workspace is to update the locations info of this window.
@@ -168,6 +204,19 @@ impl Workspace {
         self.xyhw_avoided.center_halfed()
     }
 
+    /// Moves and resizes the workspace to `bbox`, eg. when its monitor changed resolution.
+    pub fn set_bbox(&mut self, bbox: BBox) {
+        self.xyhw = XyhwBuilder {
+            h: bbox.height,
+            w: bbox.width,
+            x: bbox.x,
+            y: bbox.y,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        self.update_avoided_areas();
+    }
+
     pub fn update_avoided_areas(&mut self) {
         let mut xyhw = self.xyhw;
         for a in &self.avoid {
@@ -2,16 +2,17 @@
 
 use crate::DisplayAction;
 use crate::child_process::ChildID;
-use crate::config::{Config, InsertBehavior, ScratchPad};
-use crate::layouts::LayoutManager;
+use crate::config::{self, Config, InsertBehavior, ScratchPad};
+use crate::layouts::{ColumnStrip, LayoutManager, SplitTree, TileResize};
 use crate::models::{
-    FocusManager, Handle, Mode, ScratchPadName, Screen, Tags, Window, WindowHandle, WindowState,
-    WindowType, Workspace,
+    FocusManager, Handle, Mode, ScratchPadName, Screen, TagId, Tags, Window, WindowHandle,
+    WindowState, WindowType, Workspace, WorkspaceId,
 };
 use leftwm_layouts::Layout;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct State<H: Handle> {
     #[serde(bound = "")]
@@ -22,9 +23,36 @@ pub struct State<H: Handle> {
     #[serde(bound = "")]
     pub focus_manager: FocusManager<H>,
     pub layout_manager: LayoutManager,
+    /// How the windows of each tag are split while the tag uses the `Manual` layout.
+    #[serde(default, bound = "")]
+    pub split_trees: HashMap<TagId, SplitTree<H>>,
+    /// The columns of each tag while the tag uses the `Scrolling` layout.
+    #[serde(default, bound = "")]
+    pub column_strips: HashMap<TagId, ColumnStrip<H>>,
     #[serde(bound = "")]
     pub mode: Mode<H>,
+    /// The last workspace, kept after its screen was destroyed until a new screen replaces it.
+    #[serde(skip)]
+    pub orphaned_workspace: Option<WorkspaceId>,
+    /// The `default_tag` of each workspace of the workspace config or output profile in use.
+    #[serde(skip)]
+    pub default_tags: HashMap<WorkspaceId, TagId>,
+    /// The boundaries moved while a tiled window is resized with the mouse.
+    #[serde(skip)]
+    pub tile_resize: Option<TileResize<H>>,
     pub active_scratchpads: HashMap<ScratchPadName, VecDeque<ChildID>>,
+    /// Windows named by `MarkWindow`, a window may have several marks.
+    #[serde(default, bound = "")]
+    pub marks: HashMap<String, WindowHandle<H>>,
+    /// Minimized windows in the order they were minimized, the last one is restored first.
+    #[serde(default, bound = "")]
+    pub minimized: Vec<WindowHandle<H>>,
+    /// Urgent windows in the order they asked for attention, see `Command::FocusUrgent`.
+    #[serde(default, bound = "")]
+    pub urgent: Vec<WindowHandle<H>>,
+    /// Whether all windows but the docks and desktop are hidden, see `Command::ShowDesktop`.
+    #[serde(default)]
+    pub showing_desktop: bool,
     #[serde(bound = "")]
     pub actions: VecDeque<DisplayAction<H>>,
     pub tags: Tags, // List of all known tags.
@@ -50,15 +78,25 @@ impl<H: Handle> State<H> {
             tags.add_new(label.as_str());
         });
         tags.add_new_hidden("NSP");
+        tags.add_new_hidden("SWL");
 
         Self {
             focus_manager: FocusManager::new(config),
             layout_manager: LayoutManager::new(config),
+            split_trees: Default::default(),
+            column_strips: Default::default(),
             screens: Default::default(),
             windows: Default::default(),
             workspaces: Default::default(),
             mode: Default::default(),
+            orphaned_workspace: None,
+            default_tags: default_tags(&config.workspaces().unwrap_or_default()),
+            tile_resize: None,
             active_scratchpads: Default::default(),
+            marks: Default::default(),
+            minimized: Default::default(),
+            urgent: Default::default(),
+            showing_desktop: false,
             actions: Default::default(),
             tags,
             scratchpads: config.create_list_of_scratchpads(),
@@ -96,12 +134,19 @@ impl<H: Handle> State<H> {
         sorter.sort(Window::is_fullscreen);
 
         // Floating windows.
-        sorter.sort(|w| w.r#type == WindowType::Normal && w.floating());
+        sorter.sort(|w| {
+            w.r#type == WindowType::Normal
+                && w.floating()
+                && !w.states.contains(&WindowState::Below)
+        });
 
         // Maximized windows.
         sorter.sort(|w| w.r#type == WindowType::Normal && w.is_maximized());
 
         // Tiled windows.
+        sorter.sort(|w| w.r#type == WindowType::Normal && !w.states.contains(&WindowState::Below));
+
+        // Windows explicitly marked as below the others.
         sorter.sort(|w| w.r#type == WindowType::Normal);
 
         // Last docks.
@@ -168,6 +213,46 @@ impl<H: Handle> State<H> {
         Some(())
     }
 
+    /// Shows the windows hidden by `Command::ShowDesktop` again, eg. when a tag is switched to.
+    pub(crate) fn stop_showing_desktop(&mut self) {
+        if self.showing_desktop {
+            self.showing_desktop = false;
+            self.actions
+                .push_back(DisplayAction::SetShowingDesktop(false));
+        }
+    }
+
+    /// Keeps `minimized` in line with the `Hidden` state of a window, a window which was just
+    /// minimized goes on top of the restore stack.
+    pub(crate) fn track_minimized(&mut self, handle: &WindowHandle<H>) {
+        let hidden = self
+            .windows
+            .iter()
+            .any(|w| &w.handle == handle && w.is_hidden());
+        track(&mut self.minimized, handle, hidden);
+    }
+
+    /// Keeps `urgent` in line with the urgency of a window, a window which just became urgent
+    /// goes last.
+    pub(crate) fn track_urgent(&mut self, handle: &WindowHandle<H>) {
+        let urgent = self.windows.iter().any(|w| &w.handle == handle && w.urgent);
+        track(&mut self.urgent, handle, urgent);
+    }
+
+    /// Restores the order minimized windows are restored in, windows which were not minimized
+    /// before go on top.
+    fn restore_minimized(&mut self, old_minimized: &[WindowHandle<H>]) {
+        let handles: Vec<_> = old_minimized
+            .iter()
+            .copied()
+            .chain(self.windows.iter().map(|w| w.handle))
+            .collect();
+        self.minimized.clear();
+        for handle in &handles {
+            self.track_minimized(handle);
+        }
+    }
+
     pub fn update_static(&mut self) {
         self.windows
             .iter_mut()
@@ -198,12 +283,20 @@ impl<H: Handle> State<H> {
     pub fn restore_state(&mut self, old_state: &Self) {
         tracing::debug!("Restoring old state");
 
-        // Restore tags.
-        for old_tag in old_state.tags.all() {
+        // Restore tags, those added at runtime follow the configured ones. `tag_ids` maps the
+        // old ids of the tags which still exist to their new ones.
+        let mut tag_ids = HashMap::new();
+        for old_tag in old_state.tags.all().into_iter().filter(|t| !t.added) {
             if let Some(tag) = self.tags.get_mut(old_tag.id) {
                 tag.hidden = old_tag.hidden;
+                tag_ids.insert(old_tag.id, old_tag.id);
             }
         }
+        for old_tag in old_state.tags.all().into_iter().filter(|t| t.added) {
+            let id = self.tags.add_new_at_runtime(&old_tag.label);
+            tag_ids.insert(old_tag.id, id);
+        }
+        let tag_id = |id: TagId| tag_ids.get(&id).copied();
 
         let are_tags_equal = self.tags.all().eq(&old_state.tags.all());
 
@@ -227,20 +320,20 @@ impl<H: Handle> State<H> {
                 new_window.normal = old_window.normal;
                 if are_tags_equal {
                     new_window.tag = old_window.tag;
+                    new_window.extra_tags.clone_from(&old_window.extra_tags);
                 } else {
-                    let mut new_tag = old_window.tag;
-                    // Only retain the tag if it still exists, otherwise default to tag 1
-                    match new_tag {
-                        Some(tag) if self.tags.get(tag).is_some() => {}
-                        _ => new_tag = Some(1),
-                    }
+                    // Only retain the tags which still exist, otherwise default to tag 1
+                    let new_tag = old_window.tag.and_then(tag_id).unwrap_or(1);
                     new_window.untag();
-                    for &tag_id in new_tag.iter() {
-                        new_window.tag(&tag_id);
-                    }
+                    new_window.tag(&new_tag);
+                    new_window.extra_tags = (old_window.extra_tags.iter())
+                        .filter_map(|&t| tag_id(t))
+                        .filter(|&t| t != new_tag)
+                        .collect();
                 }
                 new_window.strut = old_window.strut;
                 new_window.states.clone_from(&old_window.states);
+                new_window.swallowed = old_window.swallowed;
                 ordered.push(new_window.clone());
                 self.windows.remove(index);
 
@@ -254,8 +347,7 @@ impl<H: Handle> State<H> {
         }
         self.windows.append(&mut ordered);
 
-        // This is needed due to mutable/immutable borrows.
-        let all_tags = &self.tags;
+        self.restore_minimized(&old_state.minimized);
 
         // Restore workspaces.
         for workspace in &mut self.workspaces {
@@ -264,16 +356,10 @@ impl<H: Handle> State<H> {
                 workspace.margin_multiplier = old_workspace.margin_multiplier;
                 if are_tags_equal {
                     workspace.tag = old_workspace.tag;
+                    workspace.extra_tags.clone_from(&old_workspace.extra_tags);
                 } else {
-                    let mut new_tag = old_workspace.tag;
                     // Only retain the tag if it still exists, otherwise default to tag 1
-                    match new_tag {
-                        Some(tag) if all_tags.get(tag).is_some() => {}
-                        _ => new_tag = Some(1),
-                    }
-                    for &tag_id in new_tag.iter() {
-                        workspace.tag = Some(tag_id);
-                    }
+                    workspace.tag = Some(old_workspace.tag.and_then(tag_id).unwrap_or(1));
                 }
             }
         }
@@ -284,18 +370,20 @@ impl<H: Handle> State<H> {
                 .insert(scratchpad.clone(), id.clone());
         }
 
+        // Restore marks of windows which are still around.
+        for (name, handle) in &old_state.marks {
+            if self.windows.iter().any(|w| &w.handle == handle) {
+                self.marks.insert(name.clone(), *handle);
+            }
+        }
+
         // Restore focus.
-        self.focus_manager
-            .tags_last_window
-            .clone_from(&old_state.focus_manager.tags_last_window);
-        self.focus_manager
-            .tags_last_window
-            .retain(|&id, _| all_tags.get(id).is_some());
-        let tag_id = match old_state.focus_manager.tag(0) {
-            // If the tag still exists it should be displayed on a workspace.
-            Some(tag_id) if self.tags.get(tag_id).is_some() => tag_id,
-            // If the tag doesn't exist, tag 1 should be displayed on a workspace.
-            Some(_) => 1,
+        self.focus_manager.tags_last_window = (old_state.focus_manager.tags_last_window.iter())
+            .filter_map(|(&id, &handle)| Some((tag_id(id)?, handle)))
+            .collect();
+        let focused_tag = match old_state.focus_manager.tag(0) {
+            // If the tag still exists it should be displayed on a workspace, otherwise tag 1.
+            Some(old_id) => tag_id(old_id).unwrap_or(1),
             // If we don't have any tag history (We should), focus the tag on workspace 1.
             None => match self.workspaces.first() {
                 Some(ws) => ws.tag.unwrap_or(1),
@@ -303,10 +391,33 @@ impl<H: Handle> State<H> {
                 _ => 1,
             },
         };
-        self.focus_tag(&tag_id);
+        self.focus_tag(&focused_tag);
 
         // Restore layout manager
         self.layout_manager.restore(&old_state.layout_manager);
+        if are_tags_equal {
+            self.split_trees.clone_from(&old_state.split_trees);
+            self.column_strips.clone_from(&old_state.column_strips);
+        }
+    }
+}
+
+/// The `default_tag` of each configured workspace, by workspace id.
+pub(crate) fn default_tags(workspaces: &[config::Workspace]) -> HashMap<WorkspaceId, TagId> {
+    workspaces
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ws)| Some((i + 1, ws.default_tag?)))
+        .collect()
+}
+
+/// Appends `handle` to `list` if `tracked` and it is missing, or removes it if not `tracked`.
+fn track<H: Handle>(list: &mut Vec<WindowHandle<H>>, handle: &WindowHandle<H>, tracked: bool) {
+    let listed = list.contains(handle);
+    if tracked && !listed {
+        list.push(*handle);
+    } else if !tracked && listed {
+        list.retain(|h| h != handle);
     }
 }
 
@@ -1,8 +1,9 @@
 //! Various shared functions that `LeftWM` uses.
 pub mod child_process;
-pub mod command_pipe;
+pub mod command_socket;
 pub mod helpers;
 pub mod modmask_lookup;
-pub mod return_pipe;
+pub mod recorder;
+pub mod screen_tracker;
 pub mod state_socket;
 pub mod window_updater;
@@ -0,0 +1,1223 @@
+//! Creates a socket to listen for external commands and reply to them.
+//!
+//! Every client connection speaks newline-delimited JSON: each line sent to the socket is a
+//! [`Request`] and is answered by exactly one [`Response`] line carrying the same `id`.
+use crate::models::{Criteria, Handle, TagId};
+use crate::{Command, ReleaseScratchPadOption, command};
+use leftwm_layouts::geometry::Direction as FocusDirection;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{env, fmt};
+use tokio::fs;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot};
+
+/// A single command sent by a client.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Chosen by the client, echoed back in the matching [`Response`].
+    pub id: u64,
+    /// The command in the same text format accepted by `leftwm-command`.
+    pub command: String,
+}
+
+/// The reply to a [`Request`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Response {
+    pub id: u64,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<CommandError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn new(id: u64, result: CommandResult) -> Self {
+        match result {
+            Ok(payload) => Self {
+                id,
+                success: true,
+                error: None,
+                payload,
+            },
+            Err(error) => Self {
+                id,
+                success: false,
+                error: Some(error),
+                payload: None,
+            },
+        }
+    }
+}
+
+/// Outcome of executing a command: an optional payload on success.
+pub type CommandResult = Result<Option<serde_json::Value>, CommandError>;
+
+/// Why a request could not be executed.
+#[derive(thiserror::Error, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[error("{kind}: {message}")]
+pub struct CommandError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The request line was not valid JSON or lacked a field.
+    InvalidRequest,
+    /// The command was recognized but its arguments could not be parsed.
+    InvalidArguments,
+    /// No built-in or config handler knows this command.
+    UnknownCommand,
+    /// The command was understood but could not be carried out.
+    ExecutionFailed,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidRequest => write!(f, "invalid request"),
+            Self::InvalidArguments => write!(f, "invalid arguments"),
+            Self::UnknownCommand => write!(f, "unknown command"),
+            Self::ExecutionFailed => write!(f, "execution failed"),
+        }
+    }
+}
+
+/// A parsed command waiting to be executed by the event loop.
+#[derive(Debug)]
+pub struct PendingCommand<H: Handle> {
+    pub command: Command<H>,
+    reply: oneshot::Sender<CommandResult>,
+}
+
+impl<H: Handle> PendingCommand<H> {
+    /// A command as if a client sent it, with the receiver of its outcome.
+    #[cfg(test)]
+    pub(crate) fn new(command: Command<H>) -> (Self, oneshot::Receiver<CommandResult>) {
+        let (reply, rx) = oneshot::channel();
+        (Self { command, reply }, rx)
+    }
+
+    /// Send the outcome back to the client that issued the command.
+    pub fn reply(self, result: CommandResult) {
+        // The client may have hung up already, nobody is left to tell.
+        self.reply.send(result).ok();
+    }
+}
+
+/// Holds socket file location, the listener task and a receiver.
+#[derive(Debug)]
+pub struct CommandSocket<H: Handle> {
+    socket_file: PathBuf,
+    listener: Option<tokio::task::JoinHandle<()>>,
+    rx: mpsc::UnboundedReceiver<PendingCommand<H>>,
+}
+
+impl<H: Handle> Drop for CommandSocket<H> {
+    fn drop(&mut self) {
+        assert!(
+            std::thread::panicking() || self.listener.is_none(),
+            "CommandSocket has to be shutdown explicitly before drop"
+        );
+    }
+}
+
+impl<H: Handle> CommandSocket<H> {
+    /// Bind to the Unix socket and listen for clients.
+    /// # Errors
+    ///
+    /// Will error if the socket cannot be bound, likely a filesystem issue
+    /// such as inadequate permissions.
+    pub async fn listen(socket_file: PathBuf) -> Result<Self, std::io::Error> {
+        let listener = if let Ok(m) = UnixListener::bind(&socket_file) {
+            m
+        } else {
+            fs::remove_file(&socket_file).await?;
+            UnixListener::bind(&socket_file)?
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let listener = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((peer, _)) => {
+                        tokio::spawn(serve_peer(peer, tx.clone()));
+                    }
+                    Err(e) => tracing::error!("Accept failed = {:?}", e),
+                }
+            }
+        });
+
+        Ok(Self {
+            socket_file,
+            listener: Some(listener),
+            rx,
+        })
+    }
+
+    /// Explicitly shutdown `CommandSocket` to perform cleanup.
+    pub async fn shutdown(&mut self) {
+        self.rx.close();
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+            listener.await.ok();
+            fs::remove_file(self.socket_file.as_path()).await.ok();
+        }
+    }
+
+    pub async fn read_command(&mut self) -> Option<PendingCommand<H>> {
+        self.rx.recv().await
+    }
+}
+
+pub fn socket_name() -> PathBuf {
+    let display = env::var("DISPLAY")
+        .ok()
+        .and_then(|d| d.rsplit_once(':').map(|(_, r)| r.to_owned()))
+        .unwrap_or_else(|| "0".to_string());
+
+    PathBuf::from(format!("command-{display}.sock"))
+}
+
+async fn serve_peer<H: Handle>(peer: UnixStream, tx: mpsc::UnboundedSender<PendingCommand<H>>) {
+    let (reader, mut writer) = peer.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => Response::new(request.id, handle_request(&request, &tx).await),
+            Err(err) => Response::new(
+                0,
+                Err(CommandError::new(
+                    ErrorKind::InvalidRequest,
+                    err.to_string(),
+                )),
+            ),
+        };
+        let Ok(mut json) = serde_json::to_string(&response) else {
+            continue;
+        };
+        json.push('\n');
+        if writer.write_all(json.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn handle_request<H: Handle>(
+    request: &Request,
+    tx: &mpsc::UnboundedSender<PendingCommand<H>>,
+) -> CommandResult {
+    let command = parse_command(&request.command).map_err(|err| {
+        tracing::error!("An error occurred while parsing the command: {}", err);
+        CommandError::new(ErrorKind::InvalidArguments, err.to_string())
+    })?;
+
+    let (reply, rx) = oneshot::channel();
+    tx.send(PendingCommand { command, reply })
+        .map_err(|_| CommandError::new(ErrorKind::ExecutionFailed, "leftwm is shutting down"))?;
+    rx.await
+        .map_err(|_| CommandError::new(ErrorKind::ExecutionFailed, "command was dropped"))?
+}
+
+fn parse_command<H: Handle>(s: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    if let Some(criteria) = s.strip_prefix('[') {
+        return build_with_criteria(criteria);
+    }
+    let (head, rest) = s.split_once(' ').unwrap_or((s, ""));
+    match head {
+        // Move Window
+        "MoveWindowDown" => Ok(Command::MoveWindowDown),
+        "MoveWindowTop" => build_move_window_top(rest),
+        "SwapWindowTop" => build_swap_window_top(rest),
+        "MoveWindowUp" => Ok(Command::MoveWindowUp),
+        "MoveWindowToNextTag" => build_move_window_to_next_tag(rest),
+        "MoveWindowToPreviousTag" => build_move_window_to_previous_tag(rest),
+        "MoveWindowToLastWorkspace" => Ok(Command::MoveWindowToLastWorkspace),
+        "MoveWindowToNextWorkspace" => Ok(Command::MoveWindowToNextWorkspace),
+        "MoveWindowToPreviousWorkspace" => Ok(Command::MoveWindowToPreviousWorkspace),
+        "MoveWindowAt" => build_move_window_dir(rest),
+        "SendWindowToTag" => build_send_window_to_tag(rest),
+        // Focus Navigation
+        "FocusWindowDown" => Ok(Command::FocusWindowDown),
+        "FocusWindowTop" => build_focus_window_top(rest),
+        "FocusWindowUp" => Ok(Command::FocusWindowUp),
+        "FocusWindowAt" => build_focus_window_dir(rest),
+        "FocusNextTag" => build_focus_next_tag(rest),
+        "FocusPreviousTag" => build_focus_previous_tag(rest),
+        "FocusWorkspaceNext" => Ok(Command::FocusWorkspaceNext),
+        "FocusWorkspacePrevious" => Ok(Command::FocusWorkspacePrevious),
+        "FocusWindow" => build_focus_window(rest),
+        "FocusUrgent" => Ok(Command::FocusUrgent),
+        // Layout
+        "DecreaseMainWidth" | "DecreaseMainSize" => build_decrease_main_size(rest), // 'DecreaseMainWidth' deprecated
+        "IncreaseMainWidth" | "IncreaseMainSize" => build_increase_main_size(rest), // 'IncreaseMainWidth' deprecated
+        "DecreaseMainCount" => Ok(Command::DecreaseMainCount()),
+        "IncreaseMainCount" => Ok(Command::IncreaseMainCount()),
+        "NextLayout" => Ok(Command::NextLayout),
+        "PreviousLayout" => Ok(Command::PreviousLayout),
+        "RotateTag" => Ok(Command::RotateTag),
+        "SetLayout" => build_set_layout(rest),
+        "SetMarginMultiplier" => build_set_margin_multiplier(rest),
+        "SplitHorizontal" => Ok(Command::SplitHorizontal),
+        "SplitVertical" => Ok(Command::SplitVertical),
+        "ToggleSplitOrientation" => Ok(Command::ToggleSplitOrientation),
+        "ResizeSplit" => Ok(Command::ResizeSplit(
+            rest.parse()
+                .map_err(|_| "argument percentage was missing or invalid")?,
+        )),
+        "ScrollLeft" => Ok(Command::ScrollLeft),
+        "ScrollRight" => Ok(Command::ScrollRight),
+        "ConsumeIntoColumn" => Ok(Command::ConsumeIntoColumn),
+        "ExpelFromColumn" => Ok(Command::ExpelFromColumn),
+        "SetColumnWidth" => Ok(Command::SetColumnWidth(
+            rest.parse()
+                .map_err(|_| "argument percentage was missing or invalid")?,
+        )),
+        // Scratchpad
+        "ToggleScratchPad" => build_toggle_scratchpad(rest),
+        "AttachScratchPad" => build_attach_scratchpad(rest),
+        "ReleaseScratchPad" => Ok(build_release_scratchpad(rest)),
+        "NextScratchPadWindow" => Ok(Command::NextScratchPadWindow {
+            scratchpad: rest.to_owned().into(),
+        }),
+        "PrevScratchPadWindow" => Ok(Command::PrevScratchPadWindow {
+            scratchpad: rest.to_owned().into(),
+        }),
+        // Floating
+        "FloatingToTile" => Ok(Command::FloatingToTile),
+        "TileToFloating" => Ok(Command::TileToFloating),
+        "ToggleFloating" => Ok(Command::ToggleFloating),
+        // Workspace/Tag
+        "GoToTag" => build_go_to_tag(rest),
+        "ReturnToLastTag" => Ok(Command::ReturnToLastTag),
+        "SendWorkspaceToTag" => build_send_workspace_to_tag(rest),
+        "AddTag" => build_add_tag(rest),
+        "RemoveTag" => build_remove_tag(rest),
+        "RenameTag" => build_rename_tag(rest),
+        "MoveTag" => build_move_tag(rest),
+        "ToggleTagView" => Ok(Command::ToggleTagView(build_tag_id(rest)?)),
+        "ToggleWindowTag" => Ok(Command::ToggleWindowTag(build_tag_id(rest)?)),
+        "SwapScreens" => Ok(Command::SwapScreens),
+        "ToggleFullScreen" => Ok(Command::ToggleFullScreen),
+        "ToggleMaximized" => Ok(Command::ToggleMaximized),
+        "ToggleSticky" => Ok(Command::ToggleSticky),
+        "ToggleAbove" => Ok(Command::ToggleAbove),
+        "ShowDesktop" => build_show_desktop(rest),
+        "MinimizeWindow" => Ok(Command::MinimizeWindow(None)),
+        "RestoreLastMinimized" => Ok(Command::RestoreLastMinimized),
+        "RestoreMinimized" => build_restore_minimized(rest),
+        // Marks
+        "MarkWindow" => Ok(Command::MarkWindow(build_mark_name(rest)?)),
+        "UnmarkWindow" => build_unmark_window(rest),
+        "FocusMark" => Ok(Command::FocusMark(build_mark_name(rest)?)),
+        "SwapWithMark" => Ok(Command::SwapWithMark(build_mark_name(rest)?)),
+        "SendMarkToTag" => build_send_mark_to_tag(rest),
+        // Queries
+        "GetWindows" => Ok(Command::GetWindows),
+        "GetWorkspaces" => Ok(Command::GetWorkspaces),
+        "GetTags" => Ok(Command::GetTags),
+        "GetLayouts" => Ok(Command::GetLayouts),
+        "GetScratchpads" => Ok(Command::GetScratchpads),
+        "GetFocused" => Ok(Command::GetFocused),
+        "GetConfig" => Ok(Command::GetConfig),
+        // General
+        "CloseWindow" => Ok(Command::CloseWindow),
+        "CloseAllOtherWindows" => Ok(Command::CloseAllOtherWindows),
+        "SoftReload" => Ok(Command::SoftReload),
+        _ => Ok(Command::Other(s.into())),
+    }
+}
+
+fn build_with_criteria<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    // Find the closing bracket, ignoring brackets within quoted values.
+    let mut quoted = false;
+    let mut escaped = false;
+    let end = raw
+        .char_indices()
+        .find(|&(_, c)| {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => quoted = !quoted,
+                ']' if !quoted => return true,
+                _ => {}
+            }
+            false
+        })
+        .map(|(i, _)| i)
+        .ok_or("missing closing bracket for criteria")?;
+    let criteria = Criteria::parse(&raw[..end])?;
+    let rest = raw[end + 1..].trim_start();
+    if rest.is_empty() {
+        Err("missing command after criteria")?;
+    }
+    if rest.starts_with('[') {
+        Err("only a single criteria block is allowed")?;
+    }
+    Ok(Command::WithCriteria {
+        criteria,
+        command: Box::new(parse_command(rest)?),
+    })
+}
+
+fn build_attach_scratchpad<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let name = if raw.is_empty() {
+        return Err("missing argument scratchpad's name".into());
+    } else {
+        raw
+    };
+    Ok(Command::AttachScratchPad {
+        scratchpad: name.into(),
+        window: None,
+    })
+}
+
+fn build_release_scratchpad<H: Handle>(raw: &str) -> Command<H> {
+    if raw.is_empty() {
+        Command::ReleaseScratchPad {
+            window: ReleaseScratchPadOption::None,
+            tag: None,
+        }
+    } else if let Ok(tag_id) = usize::from_str(raw) {
+        Command::ReleaseScratchPad {
+            window: ReleaseScratchPadOption::None,
+            tag: Some(tag_id),
+        }
+    } else {
+        Command::ReleaseScratchPad {
+            window: ReleaseScratchPadOption::ScratchpadName(raw.into()),
+            tag: None,
+        }
+    }
+}
+
+fn build_toggle_scratchpad<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let name = if raw.is_empty() {
+        return Err("missing argument scratchpad's name".into());
+    } else {
+        raw
+    };
+    Ok(Command::ToggleScratchPad(name.into()))
+}
+
+fn build_go_to_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let headless = without_head(raw, "GoToTag ");
+    let mut parts = headless.split(' ');
+    let tag: TagId = parts
+        .next()
+        .ok_or("missing argument tag_id")?
+        .parse()
+        .or(Err("argument tag_id was missing or not a valid tag number"))?;
+    let swap: bool = match parts.next().ok_or("missing argument swap")?.parse() {
+        Ok(b) => b,
+        Err(_) => Err("argument swap was not true or false")?,
+    };
+    Ok(Command::GoToTag { tag, swap })
+}
+
+fn build_send_window_to_tag<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let tag_id = if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    } else {
+        match TagId::from_str(raw) {
+            Ok(tag) => tag,
+            Err(_) => Err("argument tag_id was not a valid tag number")?,
+        }
+    };
+    Ok(Command::SendWindowToTag {
+        window: None,
+        tag: tag_id,
+    })
+}
+
+fn build_show_desktop<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let show = if raw.is_empty() {
+        None
+    } else {
+        match bool::from_str(raw) {
+            Ok(bl) => Some(bl),
+            Err(_) => Err("Argument show was not true or false")?,
+        }
+    };
+    Ok(Command::ShowDesktop(show))
+}
+
+/// The handle is given the way `GetWindows` and the state socket show it.
+fn build_restore_minimized<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument handle".into());
+    }
+    match serde_json::from_str(raw) {
+        Ok(handle) => Ok(Command::RestoreMinimized(handle)),
+        Err(_) => Err("argument handle was not a valid window handle")?,
+    }
+}
+
+fn build_add_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument label".into());
+    }
+    Ok(Command::AddTag(raw.to_owned()))
+}
+
+fn build_remove_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let tag = if raw.is_empty() {
+        None
+    } else {
+        match TagId::from_str(raw) {
+            Ok(tag) => Some(tag),
+            Err(_) => Err("argument tag_id was not a valid tag number")?,
+        }
+    };
+    Ok(Command::RemoveTag(tag))
+}
+
+fn build_rename_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let (tag, label) = raw.split_once(' ').ok_or("missing argument label")?;
+    let tag = match TagId::from_str(tag) {
+        Ok(tag) => tag,
+        Err(_) => Err("argument tag_id was not a valid tag number")?,
+    };
+    Ok(Command::RenameTag {
+        tag,
+        label: label.to_owned(),
+    })
+}
+
+fn build_move_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let mut parts = raw.split(' ');
+    let tag: TagId = match parts.next().ok_or("missing argument tag_id")?.parse() {
+        Ok(tag) => tag,
+        Err(_) => Err("argument tag_id was not a valid tag number")?,
+    };
+    let position: TagId = match parts.next().ok_or("missing argument position")?.parse() {
+        Ok(position) => position,
+        Err(_) => Err("argument position was not a valid tag number")?,
+    };
+    Ok(Command::MoveTag { tag, position })
+}
+
+fn build_tag_id(raw: &str) -> Result<TagId, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    }
+    TagId::from_str(raw).map_err(|_| "argument tag_id was not a valid tag number".into())
+}
+
+fn build_mark_name(raw: &str) -> Result<String, Box<dyn std::error::Error>> {
+    match raw {
+        "" => Err("missing argument mark name".into()),
+        name if name.contains(char::is_whitespace) => {
+            Err("argument mark name must not contain whitespace".into())
+        }
+        name => Ok(name.to_owned()),
+    }
+}
+
+fn build_unmark_window<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let mark = if raw.is_empty() {
+        None
+    } else {
+        Some(build_mark_name(raw)?)
+    };
+    Ok(Command::UnmarkWindow(mark))
+}
+
+fn build_send_mark_to_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let (mark, tag) = raw.split_once(' ').ok_or("missing argument tag_id")?;
+    let tag = match TagId::from_str(tag) {
+        Ok(tag) => tag,
+        Err(_) => Err("argument tag_id was not a valid tag number")?,
+    };
+    Ok(Command::SendMarkToTag {
+        mark: build_mark_name(mark)?,
+        tag,
+    })
+}
+
+fn build_send_workspace_to_tag<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument workspace index".into());
+    }
+    let mut parts: std::str::Split<'_, char> = raw.split(' ');
+    let ws_index: usize = match parts
+        .next()
+        .expect("split() always returns an array of at least 1 element")
+        .parse()
+    {
+        Ok(ws) => ws,
+        Err(_) => Err("argument workspace index was not a valid workspace number")?,
+    };
+    let tag_index: usize = match parts.next().ok_or("missing argument tag index")?.parse() {
+        Ok(tag) => tag,
+        Err(_) => Err("argument tag index was not a valid tag number")?,
+    };
+    Ok(Command::SendWorkspaceToTag(ws_index, tag_index))
+}
+
+fn build_set_layout<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let layout_name = if raw.is_empty() {
+        return Err("missing layout name".into());
+    } else {
+        raw
+    };
+    Ok(Command::SetLayout(String::from(layout_name)))
+}
+
+fn build_set_margin_multiplier<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let margin_multiplier = if raw.is_empty() {
+        return Err("missing argument multiplier".into());
+    } else {
+        f32::from_str(raw)?
+    };
+    Ok(Command::SetMarginMultiplier(margin_multiplier))
+}
+
+fn build_focus_window_top<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let swap = if raw.is_empty() {
+        false
+    } else {
+        match bool::from_str(raw) {
+            Ok(bl) => bl,
+            Err(_) => Err("Argument swap was not true or false")?,
+        }
+    };
+    Ok(Command::FocusWindowTop { swap })
+}
+
+fn build_focus_window_dir<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let dir = if raw.is_empty() {
+        FocusDirection::North
+    } else {
+        match FocusDirection::from_str(raw) {
+            Ok(d) => d,
+            Err(()) => Err("Argument direction was missing or invalid")?,
+        }
+    };
+    Ok(Command::FocusWindowAt(dir))
+}
+
+fn build_move_window_dir<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let dir = if raw.is_empty() {
+        FocusDirection::North
+    } else {
+        match FocusDirection::from_str(raw) {
+            Ok(d) => d,
+            Err(()) => Err("Argument direction was missing or invalid")?,
+        }
+    };
+    Ok(Command::MoveWindowAt(dir))
+}
+
+fn build_move_window_top<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let swap = if raw.is_empty() {
+        true
+    } else {
+        match bool::from_str(raw) {
+            Ok(bl) => bl,
+            Err(_) => Err("Argument swap was not true or false")?,
+        }
+    };
+    Ok(Command::MoveWindowTop { swap })
+}
+
+fn build_swap_window_top<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let swap = if raw.is_empty() {
+        true
+    } else {
+        match bool::from_str(raw) {
+            Ok(bl) => bl,
+            Err(_) => Err("Argument swap was not true or false")?,
+        }
+    };
+    Ok(Command::SwapWindowTop { swap })
+}
+
+fn build_move_window_to_next_tag<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let follow = if raw.is_empty() {
+        true
+    } else {
+        match bool::from_str(raw) {
+            Ok(bl) => bl,
+            Err(_) => Err("Argument follow was not true or false")?,
+        }
+    };
+    Ok(Command::MoveWindowToNextTag { follow })
+}
+
+fn build_move_window_to_previous_tag<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let follow = if raw.is_empty() {
+        true
+    } else {
+        match bool::from_str(raw) {
+            Ok(bl) => bl,
+            Err(_) => Err("Argument follow was not true or false")?,
+        }
+    };
+    Ok(Command::MoveWindowToPreviousTag { follow })
+}
+
+fn build_increase_main_size<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let mut parts = raw.split(' ');
+    let change: i32 = match parts.next().ok_or("missing argument change")?.parse() {
+        Ok(num) => num,
+        Err(_) => Err("argument change was missing or invalid")?,
+    };
+    Ok(Command::IncreaseMainSize(change))
+}
+
+fn build_decrease_main_size<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    let mut parts = raw.split(' ');
+    let change: i32 = match parts.next().ok_or("missing argument change")?.parse() {
+        Ok(num) => num,
+        Err(_) => Err("argument change was missing or invalid")?,
+    };
+    Ok(Command::DecreaseMainSize(change))
+}
+
+fn build_focus_next_tag<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    match raw {
+        "ignore_empty" | "goto_used" => Ok(Command::FocusNextTag {
+            behavior: command::FocusDeltaBehavior::IgnoreEmpty,
+        }),
+        "ignore_used" | "goto_empty" => Ok(Command::FocusNextTag {
+            behavior: command::FocusDeltaBehavior::IgnoreUsed,
+        }),
+        "default" | "" => Ok(Command::FocusNextTag {
+            behavior: command::FocusDeltaBehavior::Default,
+        }),
+        _ => Err(Box::new(InvalidFocusDeltaBehaviorError {
+            attempted_value: raw.to_owned(),
+            command: Command::<H>::FocusNextTag {
+                behavior: command::FocusDeltaBehavior::Default,
+            },
+        })),
+    }
+}
+
+fn build_focus_previous_tag<H: Handle>(
+    raw: &str,
+) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    match raw {
+        "ignore_empty" | "goto_used" => Ok(Command::FocusPreviousTag {
+            behavior: command::FocusDeltaBehavior::IgnoreEmpty,
+        }),
+        "ignore_used" | "goto_empty" => Ok(Command::FocusPreviousTag {
+            behavior: command::FocusDeltaBehavior::IgnoreUsed,
+        }),
+
+        "default" | "" => Ok(Command::FocusPreviousTag {
+            behavior: command::FocusDeltaBehavior::Default,
+        }),
+        _ => Err(Box::new(InvalidFocusDeltaBehaviorError {
+            attempted_value: raw.to_owned(),
+            command: Command::<H>::FocusPreviousTag {
+                behavior: command::FocusDeltaBehavior::Default,
+            },
+        })),
+    }
+}
+
+fn build_focus_window<H: Handle>(raw: &str) -> Result<Command<H>, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        Err("argument window class was missing")?;
+    }
+
+    Ok(Command::FocusWindow(String::from(raw)))
+}
+
+fn without_head<'a>(s: &'a str, head: &'a str) -> &'a str {
+    if !s.starts_with(head) {
+        return s;
+    }
+    &s[head.len()..]
+}
+
+#[derive(Debug)]
+struct InvalidFocusDeltaBehaviorError<H: Handle> {
+    attempted_value: String,
+    command: Command<H>,
+}
+
+impl<H: Handle> Error for InvalidFocusDeltaBehaviorError<H> {}
+
+impl<H: Handle> fmt::Display for InvalidFocusDeltaBehaviorError<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.command {
+            Command::FocusNextTag { .. } => write!(
+                f,
+                "Invalid behavior for FocusNextTag: {}",
+                self.attempted_value
+            ),
+            Command::FocusPreviousTag { .. } => write!(
+                f,
+                "Invalid behavior for FocusPreviousTag: {}",
+                self.attempted_value
+            ),
+            _ => write!(f, "Invalid behavior: {}", self.attempted_value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{MockHandle, Pattern, WindowHandle};
+    use crate::utils::helpers::test::temp_path;
+    use tokio::io::Lines;
+    use tokio::net::unix::OwnedReadHalf;
+
+    async fn send(
+        writer: &mut tokio::net::unix::OwnedWriteHalf,
+        lines: &mut Lines<BufReader<OwnedReadHalf>>,
+        line: &str,
+    ) -> Response {
+        writer.write_all(line.as_bytes()).await.unwrap();
+        writer.write_all(b"\n").await.unwrap();
+        serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn read_good_command() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::<MockHandle>::listen(socket_file.clone())
+            .await
+            .unwrap();
+
+        let (reader, mut writer) = UnixStream::connect(&socket_file)
+            .await
+            .unwrap()
+            .into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let client = tokio::spawn(async move {
+            send(
+                &mut writer,
+                &mut lines,
+                r#"{"id":7,"command":"SoftReload"}"#,
+            )
+            .await
+        });
+
+        let pending = command_socket.read_command().await.unwrap();
+        assert_eq!(Command::SoftReload, pending.command);
+        pending.reply(Ok(None));
+
+        let response = client.await.unwrap();
+        assert_eq!(Response::new(7, Ok(None)), response);
+
+        command_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn read_bad_command() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::<MockHandle>::listen(socket_file.clone())
+            .await
+            .unwrap();
+
+        let (reader, mut writer) = UnixStream::connect(&socket_file)
+            .await
+            .unwrap()
+            .into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let client = tokio::spawn(async move {
+            send(
+                &mut writer,
+                &mut lines,
+                r#"{"id":1,"command":"Hello World"}"#,
+            )
+            .await
+        });
+
+        let pending = command_socket.read_command().await.unwrap();
+        assert_eq!(Command::Other("Hello World".to_string()), pending.command);
+        let error = CommandError::new(ErrorKind::UnknownCommand, "Hello");
+        pending.reply(Err(error.clone()));
+
+        let response = client.await.unwrap();
+        assert!(!response.success);
+        assert_eq!(Some(error), response.error);
+
+        command_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_are_answered_directly() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::<MockHandle>::listen(socket_file.clone())
+            .await
+            .unwrap();
+
+        let (reader, mut writer) = UnixStream::connect(&socket_file)
+            .await
+            .unwrap()
+            .into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        let response = send(&mut writer, &mut lines, "SoftReload").await;
+        assert_eq!(
+            Some(ErrorKind::InvalidRequest),
+            response.error.map(|e| e.kind)
+        );
+
+        let response = send(
+            &mut writer,
+            &mut lines,
+            r#"{"id":3,"command":"SendWindowToTag"}"#,
+        )
+        .await;
+        assert_eq!(3, response.id);
+        assert_eq!(
+            Some(ErrorKind::InvalidArguments),
+            response.error.map(|e| e.kind)
+        );
+
+        command_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn replies_go_to_the_right_client() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::<MockHandle>::listen(socket_file.clone())
+            .await
+            .unwrap();
+
+        let mut clients = vec![];
+        for id in 0..2 {
+            let (reader, mut writer) = UnixStream::connect(&socket_file)
+                .await
+                .unwrap()
+                .into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let request = format!(r#"{{"id":{id},"command":"GoToTag {} false"}}"#, id + 1);
+            clients.push(tokio::spawn(async move {
+                send(&mut writer, &mut lines, &request).await
+            }));
+        }
+
+        for _ in 0..2 {
+            let pending = command_socket.read_command().await.unwrap();
+            let Command::GoToTag { tag, .. } = pending.command else {
+                panic!("unexpected command");
+            };
+            pending.reply(Ok(Some(serde_json::json!(tag))));
+        }
+
+        for (id, client) in clients.into_iter().enumerate() {
+            let response = client.await.unwrap();
+            assert_eq!(id as u64, response.id);
+            assert_eq!(Some(serde_json::json!(id + 1)), response.payload);
+        }
+
+        command_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn socket_cleanup() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::<MockHandle>::listen(socket_file.clone())
+            .await
+            .unwrap();
+        command_socket.shutdown().await;
+        assert!(!socket_file.exists());
+    }
+
+    #[test]
+    fn parse_command_with_criteria() {
+        let command = parse_command::<MockHandle>(r#"[class="firefox" title="a]b"] CloseWindow"#);
+        let Ok(Command::WithCriteria { criteria, command }) = command else {
+            panic!("expected criteria, got {command:?}");
+        };
+        assert_eq!(
+            criteria.class.as_ref().map(Pattern::as_str),
+            Some("firefox")
+        );
+        assert_eq!(criteria.title.as_ref().map(Pattern::as_str), Some("a]b"));
+        assert_eq!(*command, Command::CloseWindow);
+
+        assert!(parse_command::<MockHandle>("[tag=3 CloseWindow").is_err());
+        assert!(parse_command::<MockHandle>("[tag=3]").is_err());
+        assert!(parse_command::<MockHandle>("[tag=3] [tag=2] CloseWindow").is_err());
+        assert!(parse_command::<MockHandle>("[tag=3] SendWindowToTag").is_err());
+    }
+
+    #[test]
+    fn parse_tag_commands() {
+        assert_eq!(
+            parse_command::<MockHandle>("AddTag my project").unwrap(),
+            Command::AddTag("my project".to_owned())
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("RemoveTag").unwrap(),
+            Command::RemoveTag(None)
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("RenameTag 2 web").unwrap(),
+            Command::RenameTag {
+                tag: 2,
+                label: "web".to_owned()
+            }
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("MoveTag 4 1").unwrap(),
+            Command::MoveTag {
+                tag: 4,
+                position: 1
+            }
+        );
+        assert!(parse_command::<MockHandle>("AddTag").is_err());
+        assert!(parse_command::<MockHandle>("RenameTag web").is_err());
+        assert!(parse_command::<MockHandle>("MoveTag 4").is_err());
+        assert_eq!(
+            parse_command::<MockHandle>("ToggleTagView 3").unwrap(),
+            Command::ToggleTagView(3)
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("ToggleWindowTag 2").unwrap(),
+            Command::ToggleWindowTag(2)
+        );
+        assert!(parse_command::<MockHandle>("ToggleTagView").is_err());
+        assert!(parse_command::<MockHandle>("ToggleWindowTag web").is_err());
+    }
+
+    #[test]
+    fn parse_show_desktop() {
+        assert_eq!(
+            parse_command::<MockHandle>("ShowDesktop").unwrap(),
+            Command::ShowDesktop(None)
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("ShowDesktop false").unwrap(),
+            Command::ShowDesktop(Some(false))
+        );
+        assert!(parse_command::<MockHandle>("ShowDesktop maybe").is_err());
+    }
+
+    #[test]
+    fn parse_minimize_commands() {
+        assert_eq!(
+            parse_command::<MockHandle>("MinimizeWindow").unwrap(),
+            Command::MinimizeWindow(None)
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("RestoreMinimized 42").unwrap(),
+            Command::RestoreMinimized(WindowHandle(42))
+        );
+        assert!(parse_command::<MockHandle>("RestoreMinimized").is_err());
+        assert!(parse_command::<MockHandle>("RestoreMinimized firefox").is_err());
+    }
+
+    #[test]
+    fn parse_split_commands() {
+        assert_eq!(
+            parse_command::<MockHandle>("SplitVertical").unwrap(),
+            Command::SplitVertical
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("ResizeSplit -5").unwrap(),
+            Command::ResizeSplit(-5)
+        );
+        assert!(parse_command::<MockHandle>("ResizeSplit").is_err());
+    }
+
+    #[test]
+    fn parse_column_commands() {
+        assert_eq!(
+            parse_command::<MockHandle>("ScrollLeft").unwrap(),
+            Command::ScrollLeft
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("SetColumnWidth 75").unwrap(),
+            Command::SetColumnWidth(75)
+        );
+        assert!(parse_command::<MockHandle>("SetColumnWidth wide").is_err());
+    }
+
+    #[test]
+    fn parse_focus_urgent() {
+        assert_eq!(
+            parse_command::<MockHandle>("FocusUrgent").unwrap(),
+            Command::FocusUrgent
+        );
+    }
+
+    #[test]
+    fn parse_mark_commands() {
+        assert_eq!(
+            parse_command::<MockHandle>("MarkWindow build").unwrap(),
+            Command::MarkWindow("build".to_owned())
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("UnmarkWindow").unwrap(),
+            Command::UnmarkWindow(None)
+        );
+        assert_eq!(
+            parse_command::<MockHandle>("SendMarkToTag build 3").unwrap(),
+            Command::SendMarkToTag {
+                mark: "build".to_owned(),
+                tag: 3
+            }
+        );
+        assert!(parse_command::<MockHandle>("MarkWindow").is_err());
+        assert!(parse_command::<MockHandle>("FocusMark main editor").is_err());
+        assert!(parse_command::<MockHandle>("SendMarkToTag build").is_err());
+    }
+
+    #[test]
+    fn build_toggle_scratchpad_without_parameter() {
+        assert!(build_toggle_scratchpad::<MockHandle>("").is_err());
+    }
+
+    #[test]
+    fn build_send_window_to_tag_without_parameter() {
+        assert!(build_toggle_scratchpad::<MockHandle>("").is_err());
+    }
+
+    #[test]
+    fn build_send_workspace_to_tag_without_parameter() {
+        assert!(build_send_workspace_to_tag::<MockHandle>("").is_err());
+    }
+
+    #[test]
+    fn build_set_layout_without_parameter() {
+        assert!(build_set_layout::<MockHandle>("").is_err());
+    }
+
+    #[test]
+    fn build_set_margin_multiplier_without_parameter() {
+        assert!(build_set_margin_multiplier::<MockHandle>("").is_err());
+    }
+
+    #[test]
+    fn build_move_window_top_without_parameter() {
+        assert_eq!(
+            build_move_window_top::<MockHandle>("").unwrap(),
+            Command::MoveWindowTop { swap: true }
+        );
+    }
+
+    #[test]
+    fn build_focus_window_top_without_parameter() {
+        assert_eq!(
+            build_focus_window_top::<MockHandle>("").unwrap(),
+            Command::FocusWindowTop { swap: false }
+        );
+    }
+
+    #[test]
+    fn build_focus_window_dir_without_parameter() {
+        assert_eq!(
+            build_focus_window_dir::<MockHandle>("").unwrap(),
+            Command::FocusWindowAt(FocusDirection::North)
+        );
+    }
+
+    #[test]
+    fn build_move_window_dir_without_parameter() {
+        assert_eq!(
+            build_move_window_dir::<MockHandle>("").unwrap(),
+            Command::MoveWindowAt(FocusDirection::North)
+        );
+    }
+
+    #[test]
+    fn build_move_window_to_next_tag_without_parameter() {
+        assert_eq!(
+            build_move_window_to_next_tag::<MockHandle>("").unwrap(),
+            Command::MoveWindowToNextTag { follow: true }
+        );
+    }
+
+    #[test]
+    fn build_move_window_to_previous_tag_without_parameter() {
+        assert_eq!(
+            build_move_window_to_previous_tag::<MockHandle>("").unwrap(),
+            Command::MoveWindowToPreviousTag { follow: true }
+        );
+    }
+
+    #[test]
+    fn build_focus_next_tag_without_parameter() {
+        assert_eq!(
+            build_focus_next_tag::<MockHandle>("").unwrap(),
+            Command::FocusNextTag {
+                behavior: command::FocusDeltaBehavior::Default
+            }
+        );
+    }
+
+    #[test]
+    fn build_focus_previous_tag_without_parameter() {
+        assert_eq!(
+            build_focus_previous_tag::<MockHandle>("").unwrap(),
+            Command::FocusPreviousTag {
+                behavior: command::FocusDeltaBehavior::Default
+            }
+        );
+    }
+
+    #[test]
+    fn build_focus_next_tag_with_invalid() {
+        assert_eq!(
+            build_focus_next_tag::<MockHandle>("gurke")
+                .unwrap_err()
+                .to_string(),
+            (InvalidFocusDeltaBehaviorError {
+                attempted_value: String::from("gurke"),
+                command: Command::<MockHandle>::FocusNextTag {
+                    behavior: command::FocusDeltaBehavior::Default,
+                }
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn build_focus_previous_tag_with_invalid() {
+        assert_eq!(
+            build_focus_previous_tag::<MockHandle>("gurke")
+                .unwrap_err()
+                .to_string(),
+            (InvalidFocusDeltaBehaviorError {
+                attempted_value: String::from("gurke"),
+                command: Command::<MockHandle>::FocusPreviousTag {
+                    behavior: command::FocusDeltaBehavior::Default,
+                }
+            })
+            .to_string()
+        );
+    }
+}
@@ -142,6 +142,16 @@ impl<'de> Deserialize<'de> for ModMask {
             {
                 Ok(ModMask::from_bits_retain(v))
             }
+
+            // Self-describing formats like JSON hand out every number as `u64`.
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                u16::try_from(v)
+                    .map(ModMask::from_bits_retain)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+            }
         }
 
         deserializer.deserialize_u16(ModmaskVisitor)
@@ -0,0 +1,213 @@
+//! Records the display events and commands the event loop handles, so that they can be replayed
+//! against a [`HeadlessDisplayServer`] to reproduce a bug.
+//!
+//! Every start of the worker, including a `SoftReload`, starts a new recording. Its first events
+//! are followed by the state restored from the previous run, so that the recording replays from
+//! the same starting state.
+use crate::display_servers::HeadlessDisplayServer;
+use crate::errors::Result;
+use crate::models::Handle;
+use crate::{Command, Config, DisplayEvent, Manager, State};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, LineWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// Something the event loop handled.
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(bound = "")]
+pub enum Recorded<H: Handle> {
+    Event(DisplayEvent<H>),
+    Command(Command<H>),
+    /// The state once the state of the previous run was restored into it.
+    Restored(Box<State<H>>),
+}
+
+/// A [`Recorded`] borrowing what it records, which is written the same way.
+#[derive(Serialize)]
+#[serde(bound = "")]
+enum RecordedRef<'a, H: Handle> {
+    Restored(&'a State<H>),
+}
+
+/// A line of a recording.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(bound = "")]
+pub struct Entry<H: Handle> {
+    /// Milliseconds since the recording started.
+    pub millis: u128,
+    pub recorded: Recorded<H>,
+}
+
+/// Writes a recording as JSON lines, one [`Entry`] per line.
+#[derive(Debug)]
+pub struct Recorder {
+    file: LineWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Starts a new recording at `path`, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Will error if the file cannot be created.
+    pub fn create(path: &Path) -> Result<Self> {
+        Ok(Self {
+            file: LineWriter::new(File::create(path)?),
+            started: Instant::now(),
+        })
+    }
+
+    pub fn record<H: Handle>(&mut self, recorded: &Recorded<H>) {
+        self.write(recorded);
+    }
+
+    /// Records `state` as [`Recorded::Restored`].
+    pub fn record_restored<H: Handle>(&mut self, state: &State<H>) {
+        self.write(&RecordedRef::Restored(state));
+    }
+
+    fn write(&mut self, recorded: &impl Serialize) {
+        /// An [`Entry`] borrowing what it records.
+        #[derive(Serialize)]
+        struct Line<'a, R: Serialize> {
+            millis: u128,
+            recorded: &'a R,
+        }
+
+        let line = Line {
+            millis: self.started.elapsed().as_millis(),
+            recorded,
+        };
+        let written = serde_json::to_writer(&mut self.file, &line)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.file.write_all(b"\n"));
+        if let Err(err) = written {
+            tracing::warn!("Failed to record an entry: {}", err);
+        }
+    }
+}
+
+/// Reads a recording written by a [`Recorder`].
+///
+/// # Errors
+///
+/// Will error if the recording cannot be read or a line is no valid [`Entry`].
+pub fn read<H: Handle>(reader: impl BufRead) -> Result<Vec<Entry<H>>> {
+    let mut entries = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            entries.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(entries)
+}
+
+impl<H: Handle, C: Config> Manager<H, C, HeadlessDisplayServer<H>> {
+    /// Handles the recorded events and commands in order, as the event loop did when recording.
+    pub fn replay(&mut self, entries: impl IntoIterator<Item = Entry<H>>) {
+        for entry in entries {
+            let event = match entry.recorded {
+                Recorded::Event(event) => event,
+                Recorded::Command(command) => DisplayEvent::SendCommand(command),
+                Recorded::Restored(state) => {
+                    self.state.restore_state(&state);
+                    continue;
+                }
+            };
+            self.display_server.push_event(event);
+            self.run_until_idle();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Window;
+    use crate::config::tests::TestConfig;
+    use crate::models::{BBox, MockHandle, Screen, WindowHandle};
+    use crate::utils::command_socket::PendingCommand;
+    use crate::utils::modmask_lookup::{Button, ModMask};
+    use leftwm_layouts::layouts::Layouts;
+
+    fn headless() -> Manager<MockHandle, TestConfig, HeadlessDisplayServer<MockHandle>> {
+        let defs = Layouts::default().layouts;
+        Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            layouts: defs.iter().map(|def| def.name.clone()).collect(),
+            layout_definitions: defs,
+            ..TestConfig::default()
+        })
+    }
+
+    fn first_events() -> Vec<DisplayEvent<MockHandle>> {
+        let bbox = BBox {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 600,
+        };
+        vec![
+            DisplayEvent::ScreenCreate(Screen::new(bbox, "HDMI-1".to_owned())),
+            DisplayEvent::WindowCreate(Window::new(WindowHandle(1), None, None), 0, 0),
+            DisplayEvent::WindowCreate(Window::new(WindowHandle(2), None, None), 0, 0),
+        ]
+    }
+
+    fn run(manager: &mut Manager<MockHandle, TestConfig, HeadlessDisplayServer<MockHandle>>) {
+        for event in first_events() {
+            manager.display_server.push_event(event);
+        }
+        manager.run_until_idle();
+    }
+
+    #[test]
+    fn a_recording_replays_to_the_same_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.jsonl");
+
+        // The state saved before a reload, with the second window on the second tag.
+        let mut previous = headless();
+        run(&mut previous);
+        previous.state.windows[1].tag = Some(2);
+
+        let mut live = headless();
+        live.recorder = Some(Recorder::create(&path).unwrap());
+        run(&mut live);
+        // Restored as the event loop does once it handled the first events.
+        live.state.restore_state(&previous.state);
+        if let Some(recorder) = &mut live.recorder {
+            recorder.record_restored(&live.state);
+        }
+        let click = DisplayEvent::MouseCombo(ModMask::Zero, Button::Main, WindowHandle(1), 10, 10);
+        live.display_server.push_event(click);
+        live.run_until_idle();
+        let (pending, _reply) = PendingCommand::new(Command::MoveWindowToNextTag { follow: false });
+        live.execute_command(pending);
+
+        let file = std::io::BufReader::new(File::open(&path).unwrap());
+        let entries = read::<MockHandle>(file).unwrap();
+        assert_eq!(entries.len(), 6);
+        assert!(matches!(entries[3].recorded, Recorded::Restored(_)));
+        let mut replayed = headless();
+        replayed.replay(entries);
+
+        let tags = |manager: &Manager<_, _, _>| -> Vec<_> {
+            let mut tags: Vec<_> = manager
+                .state
+                .windows
+                .iter()
+                .map(|w| (w.handle.0, w.tag))
+                .collect();
+            tags.sort_unstable();
+            tags
+        };
+        assert_eq!(tags(&live), [(1, Some(2)), (2, Some(2))]);
+        assert_eq!(tags(&replayed), tags(&live));
+    }
+}
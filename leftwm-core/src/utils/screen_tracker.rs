@@ -0,0 +1,314 @@
+//! Turns the outputs a display server reports into the screens leftwm creates workspaces for.
+use crate::DisplayEvent;
+use crate::config::{Config, OutputProfile, Workspace};
+use crate::models::{Handle, Screen, WorkspaceId};
+
+/// Splits outputs into screens following the output profile matching them or else the
+/// `workspaces` config, and remembers the result so that the next set of outputs, eg. after a
+/// monitor got plugged in, can be turned into `OutputProfileChanged`, `ScreenCreate`,
+/// `ScreenUpdate` and `ScreenDestroy` events.
+#[derive(Debug, Clone, Default)]
+pub struct ScreenTracker<H: Handle> {
+    workspaces: Option<Vec<Workspace>>,
+    output_profiles: Vec<OutputProfile>,
+    auto_derive_workspaces: bool,
+    profile: Option<String>,
+    screens: Vec<Screen<H>>,
+}
+
+impl<H: Handle> ScreenTracker<H> {
+    #[must_use]
+    pub fn new(config: &impl Config) -> Self {
+        let mut tracker = Self::default();
+        tracker.load_config(config);
+        tracker
+    }
+
+    /// Follows the `workspaces` and `output_profiles` config from now on. Screens already created
+    /// are left alone.
+    pub fn load_config(&mut self, config: &impl Config) {
+        self.workspaces = config.workspaces();
+        self.output_profiles = config.output_profiles();
+        self.auto_derive_workspaces = config.auto_derive_workspaces();
+    }
+
+    /// The screens as of the last `update`.
+    #[must_use]
+    pub fn screens(&self) -> &[Screen<H>] {
+        &self.screens
+    }
+
+    /// Works out the screens of `outputs` and returns the events turning the previous screens
+    /// into them. Screens keep their workspace ID as long as their output is connected.
+    pub fn update(&mut self, outputs: &[Screen<H>]) -> Vec<DisplayEvent<H>> {
+        let connected: Vec<&str> = outputs.iter().map(|o| o.output.as_str()).collect();
+        let profile = self
+            .output_profiles
+            .iter()
+            .find(|profile| profile.matches(&connected));
+        let workspaces = match profile {
+            Some(profile) => Some(profile.workspaces.as_slice()),
+            None => self.workspaces.as_deref(),
+        };
+        let screens = self.screens_for(workspaces, outputs);
+        let mut events = vec![];
+
+        // The layouts of the profile have to be set up before its workspaces are created.
+        let profile = profile.map(|profile| profile.name.clone());
+        if profile != self.profile {
+            events.push(DisplayEvent::OutputProfileChanged(profile.clone()));
+            self.profile = profile;
+        }
+
+        // Screens are destroyed last, so that their windows have somewhere to go.
+        for screen in &screens {
+            match self.screens.iter().find(|old| old.id == screen.id) {
+                None => events.push(DisplayEvent::ScreenCreate(screen.clone())),
+                Some(old) if old.bbox != screen.bbox || old.output != screen.output => {
+                    events.push(DisplayEvent::ScreenUpdate(screen.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for old in &self.screens {
+            if !screens.iter().any(|s| s.id == old.id) {
+                events.extend(old.id.map(DisplayEvent::ScreenDestroy));
+            }
+        }
+
+        self.screens = screens;
+        events
+    }
+
+    fn screens_for(
+        &self,
+        workspaces: Option<&[Workspace]>,
+        outputs: &[Screen<H>],
+    ) -> Vec<Screen<H>> {
+        let Some(workspaces) = workspaces else {
+            return vec![];
+        };
+
+        let mut screens = vec![];
+        for (i, wsc) in workspaces.iter().enumerate() {
+            // If there is a screen corresponding to the given output, create the workspace
+            let Some(output) = outputs.iter().find(|o| o.output == wsc.output) else {
+                continue;
+            };
+            let mut screen = Screen::from(wsc);
+            screen.root = output.root;
+            if wsc.relative.unwrap_or(false) {
+                screen.bbox.add(output.bbox);
+            }
+            screen.id = Some(i + 1);
+            screens.push(screen);
+        }
+
+        let auto_derive_workspaces = if self.auto_derive_workspaces {
+            true
+        } else if screens.is_empty() {
+            tracing::warn!(
+                "No Workspace in Workspace config matches connected screen. Falling back to \"auto_derive_workspaces: true\"."
+            );
+            true
+        } else {
+            false
+        };
+
+        // If there is no hardcoded workspace layout, add every screen not mentioned in the config.
+        if auto_derive_workspaces {
+            let mut next_id = self
+                .screens
+                .iter()
+                .filter_map(|s| s.id)
+                .fold(workspaces.len(), WorkspaceId::max)
+                + 1;
+            for output in outputs
+                .iter()
+                .filter(|o| !workspaces.iter().any(|wsc| wsc.output == o.output))
+            {
+                let mut screen = output.clone();
+                // Keep the ID of a screen whose output was already connected.
+                let id = self
+                    .screens
+                    .iter()
+                    .filter(|s| s.output == output.output && s.id > Some(workspaces.len()))
+                    .find(|s| !screens.iter().any(|taken: &Screen<H>| taken.id == s.id))
+                    .and_then(|s| s.id)
+                    .unwrap_or_else(|| {
+                        let id = next_id;
+                        next_id += 1;
+                        id
+                    });
+                screen.id = Some(id);
+                screens.push(screen);
+            }
+        }
+        screens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manager;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{BBox, MockHandle};
+
+    fn output(name: &str, x: i32) -> Screen<MockHandle> {
+        let bbox = BBox {
+            x,
+            y: 0,
+            width: 800,
+            height: 600,
+        };
+        Screen::new(bbox, name.to_owned())
+    }
+
+    fn summary(events: &[DisplayEvent<MockHandle>]) -> Vec<(&'static str, usize)> {
+        events
+            .iter()
+            .map(|event| match event {
+                DisplayEvent::ScreenCreate(s) => ("create", s.id.unwrap_or_default()),
+                DisplayEvent::ScreenUpdate(s) => ("update", s.id.unwrap_or_default()),
+                DisplayEvent::ScreenDestroy(id) => ("destroy", *id),
+                DisplayEvent::OutputProfileChanged(name) => {
+                    ("profile", usize::from(name.is_some()))
+                }
+                _ => ("other", 0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plugging_outputs_in_and_out_keeps_workspace_ids() {
+        let config = TestConfig {
+            workspaces: Some(vec![]),
+            ..TestConfig::default()
+        };
+        let mut tracker = ScreenTracker::new(&config);
+
+        let events = tracker.update(&[output("eDP-1", 0), output("HDMI-1", 800)]);
+        assert_eq!(summary(&events), [("create", 1), ("create", 2)]);
+
+        let events = tracker.update(&[output("HDMI-1", 800)]);
+        assert_eq!(summary(&events), [("destroy", 1)]);
+
+        let events = tracker.update(&[output("HDMI-1", 0), output("eDP-1", 1920)]);
+        assert_eq!(summary(&events), [("update", 2), ("create", 3)]);
+
+        assert!(
+            tracker
+                .update(&[output("HDMI-1", 0), output("eDP-1", 1920)])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn configured_workspaces_follow_their_output() {
+        let workspace = Workspace {
+            output: "HDMI-1".to_owned(),
+            relative: Some(true),
+            width: 400,
+            height: 600,
+            ..Workspace::default()
+        };
+        let config = TestConfig {
+            workspaces: Some(vec![
+                workspace.clone(),
+                Workspace {
+                    x: 400,
+                    ..workspace
+                },
+            ]),
+            ..TestConfig::default()
+        };
+        let mut tracker = ScreenTracker::new(&config);
+
+        // `eDP-1` is not configured, it gets a workspace derived from the output.
+        let events = tracker.update(&[output("eDP-1", 0)]);
+        assert_eq!(summary(&events), [("create", 3)]);
+
+        let events = tracker.update(&[output("eDP-1", 0), output("HDMI-1", 800)]);
+        assert_eq!(summary(&events), [("create", 1), ("create", 2)]);
+        let xs: Vec<_> = tracker.screens().iter().map(|s| s.bbox.x).collect();
+        assert_eq!(xs, [800, 1200, 0]);
+    }
+
+    #[test]
+    fn the_profile_matching_all_outputs_is_selected() {
+        let workspace = |output: &str, x| Workspace {
+            output: output.to_owned(),
+            relative: Some(true),
+            x,
+            width: 400,
+            height: 600,
+            ..Workspace::default()
+        };
+        let config = TestConfig {
+            workspaces: Some(vec![workspace("eDP-1", 0)]),
+            output_profiles: vec![OutputProfile {
+                name: "docked".to_owned(),
+                outputs: vec!["HDMI-1".to_owned(), "eDP-1".to_owned()],
+                workspaces: vec![
+                    workspace("HDMI-1", 0),
+                    workspace("HDMI-1", 400),
+                    workspace("eDP-1", 0),
+                ],
+            }],
+            ..TestConfig::default()
+        };
+        let mut tracker = ScreenTracker::new(&config);
+
+        let events = tracker.update(&[output("eDP-1", 0)]);
+        assert_eq!(summary(&events), [("create", 1)]);
+
+        let events = tracker.update(&[output("eDP-1", 0), output("HDMI-1", 800)]);
+        assert_eq!(
+            summary(&events),
+            [("profile", 1), ("update", 1), ("create", 2), ("create", 3)]
+        );
+        let xs: Vec<_> = tracker.screens().iter().map(|s| s.bbox.x).collect();
+        assert_eq!(xs, [800, 1200, 0]);
+
+        let events = tracker.update(&[output("eDP-1", 0)]);
+        assert_eq!(
+            summary(&events),
+            [
+                ("profile", 0),
+                ("update", 1),
+                ("destroy", 2),
+                ("destroy", 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn the_only_output_can_be_swapped_for_another() {
+        let config = TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            workspaces: Some(vec![]),
+            ..TestConfig::default()
+        };
+        let mut tracker = ScreenTracker::new(&config);
+        let mut manager: Manager<MockHandle, TestConfig, MockDisplayServer<MockHandle>> =
+            Manager::new(config);
+        let mut update = |outputs: &[Screen<MockHandle>]| {
+            for event in tracker.update(outputs) {
+                manager.display_event_handler(event);
+            }
+        };
+
+        // The laptop lid is closed with only an external monitor left.
+        update(&[output("eDP-1", 0)]);
+        update(&[output("HDMI-1", 0)]);
+
+        let outputs: Vec<_> = (manager.state.screens.iter())
+            .map(|s| (s.id, s.output.as_str()))
+            .collect();
+        assert_eq!(outputs, [(Some(2), "HDMI-1")]);
+        let ids: Vec<_> = manager.state.workspaces.iter().map(|ws| ws.id).collect();
+        assert_eq!(ids, [2]);
+    }
+}
@@ -1,16 +1,24 @@
-use crate::errors::{LeftError, Result};
+use crate::errors::Result;
 use crate::models::Handle;
-use crate::models::dto::ManagerState;
+use crate::models::dto::{EventSnapshot, ManagerState, StateEvent, Subscription};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
-use tokio::net::{UnixListener, UnixStream};
-use tokio::sync::Mutex;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixListener;
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::{Mutex, watch};
+
+#[derive(Debug)]
+struct Peer {
+    stream: OwnedWriteHalf,
+    /// `None` until the peer subscribes, it then receives events instead of snapshots.
+    subscription: watch::Receiver<Option<Subscription>>,
+}
 
 #[derive(Debug, Default)]
 struct State {
-    peers: Vec<Option<UnixStream>>,
+    peers: Vec<Option<Peer>>,
     last_state: String,
 }
 
@@ -19,6 +27,7 @@ pub struct StateSocket {
     state: Arc<Mutex<State>>,
     listener: Option<tokio::task::JoinHandle<()>>,
     socket_file: PathBuf,
+    last_snapshot: Option<EventSnapshot>,
 }
 
 impl Drop for StateSocket {
@@ -53,8 +62,8 @@ impl StateSocket {
         }
     }
 
+    /// Sends the new state to snapshot peers and what changed to subscribed peers.
     /// # Errors
-    /// Will return Err if a mut ref to the peer is unavailable.
     /// Will return error if state cannot be serialized
     pub async fn write_manager_state<H: Handle>(
         &mut self,
@@ -64,20 +73,31 @@ impl StateSocket {
             let state: ManagerState = raw_state.into();
             let mut json = serde_json::to_string(&state)?;
             json.push('\n');
+            let snapshot = EventSnapshot::from(raw_state);
+            let events = self
+                .last_snapshot
+                .as_ref()
+                .map(|old| snapshot.events_since(old))
+                .unwrap_or_default();
+            self.last_snapshot = Some(snapshot);
+
             let mut state = self.state.lock().await;
 
             let state_changed = json != state.last_state;
-            if state_changed {
+            if state_changed || !events.is_empty() {
                 state.peers.retain(std::option::Option::is_some);
-                for peer in &mut state.peers {
-                    if peer
-                        .as_mut()
-                        .ok_or(LeftError::StreamError)?
-                        .write_all(json.as_bytes())
-                        .await
-                        .is_err()
-                    {
-                        peer.take();
+                for slot in &mut state.peers {
+                    let Some(peer) = slot.as_mut() else {
+                        continue;
+                    };
+                    let subscription = peer.subscription.borrow().clone();
+                    let written = match subscription {
+                        None if state_changed => peer.stream.write_all(json.as_bytes()).await,
+                        None => Ok(()),
+                        Some(subscription) => write_events(peer, &subscription, &events).await,
+                    };
+                    if written.is_err() {
+                        slot.take();
                     }
                 }
                 state.last_state = json;
@@ -98,10 +118,16 @@ impl StateSocket {
         Ok(tokio::spawn(async move {
             loop {
                 match listener.accept().await {
-                    Ok((mut peer, _)) => {
+                    Ok((peer, _)) => {
+                        let (reader, mut stream) = peer.into_split();
                         let mut state = state.lock().await;
-                        if peer.write_all(state.last_state.as_bytes()).await.is_ok() {
-                            state.peers.push(Some(peer));
+                        if stream.write_all(state.last_state.as_bytes()).await.is_ok() {
+                            let (tx, subscription) = watch::channel(None);
+                            tokio::spawn(read_subscriptions(reader, tx));
+                            state.peers.push(Some(Peer {
+                                stream,
+                                subscription,
+                            }));
                         }
                     }
                     Err(e) => tracing::error!("Accept failed = {:?}", e),
@@ -111,12 +137,41 @@ impl StateSocket {
     }
 }
 
+async fn write_events(
+    peer: &mut Peer,
+    subscription: &Subscription,
+    events: &[StateEvent],
+) -> std::io::Result<()> {
+    for event in events.iter().filter(|e| subscription.wants(e.topic())) {
+        let mut json = serde_json::to_string(event)?;
+        json.push('\n');
+        peer.stream.write_all(json.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+async fn read_subscriptions(reader: OwnedReadHalf, tx: watch::Sender<Option<Subscription>>) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        match serde_json::from_str::<Subscription>(&line) {
+            Ok(subscription) => {
+                if tx.send(Some(subscription)).is_err() {
+                    return;
+                }
+            }
+            Err(err) => tracing::warn!("Invalid state socket subscription: {}", err),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::Manager;
+    use crate::models::dto::Topic;
+    use crate::models::{MockHandle, Screen, Window, WindowHandle};
     use crate::utils::helpers::test::temp_path;
-    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::UnixStream;
 
     #[tokio::test]
     async fn multiple_peers() {
@@ -211,4 +266,67 @@ mod test {
         assert!(!socket_file.exists());
         old_socket.shutdown().await;
     }
+
+    #[tokio::test]
+    async fn subscribed_peer_gets_events() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+
+        let socket_file = temp_path().await.unwrap();
+        let mut state_socket = StateSocket::default();
+        state_socket.listen(socket_file.clone()).await.unwrap();
+        state_socket
+            .write_manager_state(&manager.state)
+            .await
+            .unwrap();
+
+        let (reader, mut writer) = UnixStream::connect(socket_file).await.unwrap().into_split();
+        let mut lines = BufReader::new(reader).lines();
+        // The current snapshot is always sent first.
+        lines.next_line().await.unwrap().unwrap();
+
+        let subscription = Subscription {
+            subscribe: vec![Topic::WindowCreated, Topic::TagChanged],
+        };
+        let mut json = serde_json::to_string(&subscription).unwrap();
+        json.push('\n');
+        writer.write_all(json.as_bytes()).await.unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::<MockHandle>(1), None, None),
+            -1,
+            -1,
+        );
+        state_socket
+            .write_manager_state(&manager.state)
+            .await
+            .unwrap();
+        assert_eq!(
+            StateEvent::WindowCreated {
+                handle: serde_json::json!(1)
+            },
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap()
+        );
+
+        // Focus changes are filtered out, only the tag change comes through.
+        manager.command_handler(&crate::Command::GoToTag {
+            tag: 2,
+            swap: false,
+        });
+        state_socket
+            .write_manager_state(&manager.state)
+            .await
+            .unwrap();
+        assert_eq!(
+            StateEvent::TagChanged {
+                workspace: 1,
+                tag: "2".to_string(),
+                tags: vec!["2".to_string()],
+            },
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap()
+        );
+
+        state_socket.shutdown().await;
+    }
 }
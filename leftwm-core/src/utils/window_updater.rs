@@ -18,8 +18,25 @@ impl<H: Handle, C: Config, SERVER: DisplayServer<H>> Manager<H, C, SERVER> {
             let windows = &mut self.state.windows;
             let all_tags = &self.state.tags;
             if let Some(Some(tag)) = ws.tag.map(|tag_id| all_tags.get(tag_id)) {
-                tag.update_windows(windows, ws, &mut self.state.layout_manager);
+                let history = self.state.focus_manager.window_history.iter().flatten();
+                tag.update_windows(
+                    windows,
+                    ws,
+                    &self.state.workspaces,
+                    &mut self.state.layout_manager,
+                    self.state.split_trees.entry(tag.id).or_default(),
+                    self.state.column_strips.entry(tag.id).or_default(),
+                    history.copied(),
+                );
             }
         }
+
+        if self.state.showing_desktop {
+            self.state
+                .windows
+                .iter_mut()
+                .filter(|w| w.is_managed())
+                .for_each(|w| w.set_visible(false));
+        }
     }
 }
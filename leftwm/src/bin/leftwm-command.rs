@@ -1,50 +1,61 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use clap::{arg, command};
 use leftwm::BaseCommand;
-use leftwm_core::ReturnPipe;
-use std::fs::OpenOptions;
-use std::io::prelude::*;
-use std::path::{Path, PathBuf};
+use leftwm_core::{Request, Response};
 use std::process::exit;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
 use xdg::BaseDirectories;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let matches = get_command().get_matches();
 
-    let file_name = leftwm_core::pipe_name();
-    let file_path = BaseDirectories::with_prefix("leftwm")
-        .find_runtime_file(&file_name)
-        .with_context(|| format!("ERROR: Couldn't find {}", file_name.display()))?;
-    let mut file = OpenOptions::new()
-        .append(true)
-        .open(file_path)
-        .with_context(|| format!("ERROR: Couldn't open {}", file_name.display()))?;
     let mut exit_code = 0;
     if let Some(commands) = matches.get_many::<String>("COMMAND") {
-        let mut ret_pipe = get_return_pipe().await?;
-        for command in commands {
-            if let Err(e) = writeln!(file, "{command}") {
-                eprintln!("ERROR: Couldn't write to commands.pipe: {e}");
-                continue;
-            }
+        let file_name = leftwm_core::socket_name();
+        let file_path = BaseDirectories::with_prefix("leftwm")
+            .find_runtime_file(&file_name)
+            .with_context(|| format!("ERROR: Couldn't find {}", file_name.display()))?;
+        let stream = UnixStream::connect(file_path)
+            .await
+            .with_context(|| format!("ERROR: Couldn't connect to {}", file_name.display()))?;
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        for (id, command) in (0..).zip(commands) {
+            let mut request = serde_json::to_string(&Request {
+                id,
+                command: command.clone(),
+            })?;
+            request.push('\n');
+            writer
+                .write_all(request.as_bytes())
+                .await
+                .context("ERROR: Couldn't send command")?;
+
             tokio::select! {
-                Some(res) = ret_pipe.read_return() => {
-                    if let Some((result, msg)) = res.split_once(' ') {
-                        match result {
-                            "OK:" => println!("{command}: {msg}"),
-                            "ERROR:" => {eprintln!("{command}: {msg}"); exit_code = 1;},
-                            _ => println!("{command}: {res}"),
+                response = read_response(&mut lines, id) => match response {
+                    Ok(Response { success: true, payload: Some(payload), .. }) => println!("{payload}"),
+                    Ok(Response { success: true, .. }) => println!("{command}: Command executed successfully"),
+                    Ok(Response { error, .. }) => {
+                        match error {
+                            Some(error) => eprintln!("{command}: {error}"),
+                            None => eprintln!("{command}: Command failed"),
                         }
-                    } else {
-                        println!("{command}: {res}");
+                        exit_code = 1;
+                    }
+                    Err(err) => {
+                        eprintln!("{command}: {err}");
+                        exit_code = 1;
                     }
+                },
+                () = timeout(5000) => {
+                    eprintln!("WARN: timeout waiting for a reply. Command may have executed, but errors will not be displayed.");
+                    exit_code = 1;
                 }
-                () = timeout(5000) => {eprintln!("WARN: timeout connecting to return pipe. Command may have executed, but errors will not be displayed."); exit_code = 1;}
-                else => {eprintln!("WARN: timeout connection to return pipe. Command may have executed, but errors will not be displayed."); exit_code = 1;}
             }
         }
-        drop(ret_pipe);
     }
 
     if matches.get_flag("list") {
@@ -75,6 +86,11 @@ Available Commands:{}
 Note about commands with arguments:
     Use quotations for the command and arguments, like this:
     leftwm-command \"<command> <args>\"
+
+Note about criteria:
+    Prefix a command with criteria to run it on every matching window instead of the focused one:
+    leftwm-command '[class=\"firefox\" tag=3] CloseWindow'
+    Supported criteria are class, title (regex), tag, workspace (int), floating and urgent (bool).
 For more information please visit:
 https://github.com/leftwm/leftwm/wiki/External-Commands\
 ",
@@ -82,31 +98,18 @@ https://github.com/leftwm/leftwm/wiki/External-Commands\
     );
 }
 
-#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Error {
-    #[error("Couldn't create the file: '{0}'")]
-    CreateFile(PathBuf),
-
-    #[error("Couldn't connect to file: '{0}'")]
-    ConnectToFile(PathBuf),
-}
-
-async fn get_return_pipe() -> Result<ReturnPipe, Error> {
-    let file_name = ReturnPipe::pipe_name();
-
-    let pipe_file =
-        place_runtime_file(&file_name).map_err(|_| Error::CreateFile(file_name.clone()))?;
-
-    ReturnPipe::new(pipe_file)
-        .await
-        .map_err(|_| Error::ConnectToFile(file_name))
-}
-
-fn place_runtime_file<P>(path: P) -> std::io::Result<PathBuf>
-where
-    P: AsRef<Path>,
-{
-    xdg::BaseDirectories::with_prefix("leftwm").place_runtime_file(path)
+async fn read_response(
+    lines: &mut tokio::io::Lines<BufReader<tokio::net::unix::OwnedReadHalf>>,
+    id: u64,
+) -> Result<Response> {
+    while let Some(line) = lines.next_line().await? {
+        let response: Response =
+            serde_json::from_str(&line).context("ERROR: Couldn't parse reply")?;
+        if response.id == id {
+            return Ok(response);
+        }
+    }
+    bail!("ERROR: LeftWM closed the connection")
 }
 
 async fn timeout(mills: u64) {
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use clap::{arg, command};
+use leftwm::Config;
+use leftwm_core::Manager;
+use leftwm_core::display_servers::HeadlessDisplayServer;
+use leftwm_core::models::dto::ManagerState;
+use leftwm_core::utils::recorder;
+use ron::{Options, extensions::Extensions};
+use std::fs::{self, File};
+use std::io::BufReader;
+
+fn main() -> Result<()> {
+    let matches = command!("LeftWM Replay")
+        .about("Replays a recording made with `record_path` without a display and prints the resulting state")
+        .help_template(leftwm::utils::get_help_template())
+        .args(&[
+            arg!(-c --config [FILE] "The config to replay with, the current config otherwise"),
+            arg!(-a --actions "Also print every action sent to the display server, one per line"),
+            arg!(<RECORDING> "The recording to replay"),
+        ])
+        .get_matches();
+
+    let config = match matches.get_one::<String>("config") {
+        Some(path) => Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME | Extensions::UNWRAP_NEWTYPES)
+            .from_str(&fs::read_to_string(path)?)?,
+        None => leftwm::load(),
+    };
+    let recording = matches
+        .get_one::<String>("RECORDING")
+        .expect("the recording is required");
+    let entries = recorder::read(BufReader::new(File::open(recording)?))?;
+
+    // X window IDs fit into 29 bits, so recordings of either backend replay with `i32` handles.
+    let mut manager = Manager::<i32, Config, HeadlessDisplayServer<i32>>::new(config);
+    manager.replay(entries);
+
+    if matches.get_flag("actions") {
+        for action in manager.display_server.actions() {
+            println!("{}", serde_json::to_string(action)?);
+        }
+    }
+    let state = ManagerState::from(&manager.state);
+    println!("{}", serde_json::to_string_pretty(&state)?);
+    Ok(())
+}
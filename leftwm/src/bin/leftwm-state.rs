@@ -1,12 +1,12 @@
 use clap::{arg, command};
 use leftwm_core::errors::{LeftError, Result};
-use leftwm_core::models::dto::{DisplayState, ManagerState};
+use leftwm_core::models::dto::{DisplayState, ManagerState, StateEvent, Subscription, Topic};
 use liquid::Template;
 use std::ffi::OsStr;
 use std::path::Path;
 use std::str;
 use tokio::fs;
-use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
 use tokio::net::UnixStream;
 use xdg::BaseDirectories;
 
@@ -21,9 +21,22 @@ async fn main() -> Result<()> {
     let ws_id = matches.get_one("workspace").copied();
     let newline = matches.get_flag("newline");
     let once = matches.get_flag("quit");
+    let events = matches.get_one::<String>("events");
 
     let mut stream_reader = stream_reader().await?;
-    if let Some(template_file) = template_file {
+    if let Some(events) = events {
+        subscribe(&mut stream_reader, events).await?;
+        while let Some(line) = stream_reader.next_line().await? {
+            // Snapshots are sent until the subscription is read, skip anything but events.
+            if serde_json::from_str::<StateEvent>(&line).is_err() {
+                continue;
+            }
+            println!("{line}");
+            if once {
+                break;
+            }
+        }
+    } else if let Some(template_file) = template_file {
         let path = Path::new(template_file);
         let partials = get_partials(path.parent()).await?;
         let template_str = fs::read_to_string(template_file).await?;
@@ -138,6 +151,21 @@ fn template_handler(
             liquid::model::Value::scalar(display.window_title),
         );
         globals.insert("workspace".into(), liquid::model::Value::Object(workspace));
+        // Every window on the tags of the workspace, `visible` tells the ones on screen.
+        let tags: Vec<_> = display.workspaces[ws_id]
+            .tags
+            .iter()
+            .filter(|t| t.mine)
+            .map(|t| &t.name)
+            .collect();
+        let windows: Vec<_> = display
+            .windows
+            .iter()
+            .filter(|w| w.tags.iter().chain(&w.tag).any(|t| tags.contains(&t)))
+            .collect();
+        let json = serde_json::to_string(&windows)?;
+        let windows: liquid::model::Array = serde_json::from_str(&json)?;
+        globals.insert("windows".into(), liquid::model::Value::Array(windows));
         globals
     } else {
         let json = serde_json::to_string(&display)?;
@@ -159,6 +187,24 @@ fn template_handler(
     Ok(())
 }
 
+/// Switch the connection from state snapshots to events of the comma separated `topics`.
+async fn subscribe(stream_reader: &mut Lines<BufReader<UnixStream>>, topics: &str) -> Result<()> {
+    let subscribe = topics
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| serde_json::from_value::<Topic>(t.into()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let mut json = serde_json::to_string(&Subscription { subscribe })?;
+    json.push('\n');
+    stream_reader
+        .get_mut()
+        .get_mut()
+        .write_all(json.as_bytes())
+        .await?;
+    Ok(())
+}
+
 async fn stream_reader() -> Result<Lines<BufReader<UnixStream>>> {
     let base = BaseDirectories::with_prefix("leftwm");
     let socket_file = base.place_runtime_file("current_state.sock")?;
@@ -177,6 +223,10 @@ fn get_command() -> clap::Command {
                 .value_parser(clap::value_parser!(usize)),
             arg!(-n --newline "Print new lines in the output"),
             arg!(-q --quit "Prints the state once and quits"),
+            arg!(-e --events [TOPICS] "Print events instead of the state, for a comma separated list of topics (all if empty): window_created, window_destroyed, focus_changed, tag_changed, layout_changed, urgent_changed, screen_added")
+                .conflicts_with_all(["template", "string", "workspace"])
+                .num_args(0..=1)
+                .default_missing_value(""),
         ])
 }
 
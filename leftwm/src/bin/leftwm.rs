@@ -1,7 +1,7 @@
 //! Starts leftwm programs.
 //!
 //! If no arguments are passed, starts `leftwm-worker`. If arguments are passed, starts
-//! `leftwm-{check, command, state, theme}` as specified, and passes along any extra arguments.
+//! `leftwm-{check, command, state, theme, replay}` as specified, and passes along any extra arguments.
 use clap::command;
 use leftwm_core::child_process::{self, Nanny};
 use std::env;
@@ -20,13 +20,17 @@ const SUBCOMMAND_PREFIX: &str = "leftwm-";
 
 const SUBCOMMAND_NAME_INDEX: usize = 0;
 const SUBCOMMAND_DESCRIPTION_INDEX: usize = 1;
-const AVAILABLE_SUBCOMMANDS: [[&str; 2]; 6] = [
+const AVAILABLE_SUBCOMMANDS: [[&str; 2]; 7] = [
     ["check", "Check syntax of the configuration file"],
     ["command", "Send external commands to LeftWM"],
     ["state", "Print the current state of LeftWM"],
     ["theme", "Manage LeftWM themes"],
     ["config", "Manage LeftWM configuration file"],
     ["log", "Retrieves information logged by leftwm-worker"],
+    [
+        "replay",
+        "Replays a recording of leftwm-worker without a display",
+    ],
 ];
 
 fn main() {
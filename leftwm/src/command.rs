@@ -32,6 +32,16 @@ pub enum BaseCommand {
     ToggleMaximized,
     ToggleSticky,
     ToggleAbove,
+    /// Args: `show` (bool, optional)
+    /// Note: Without a value, the desktop is shown or the windows are shown again.
+    ShowDesktop,
+    /// Note: Hides the focused window until it is restored.
+    MinimizeWindow,
+    /// Note: Restores the window which was minimized last.
+    RestoreLastMinimized,
+    /// Args: `handle` (int)
+    /// Note: Restores a minimized window, see `GetWindows` for the handles.
+    RestoreMinimized,
     GotoTag,
     ReturnToLastTag,
     FloatingToTile,
@@ -52,6 +62,8 @@ pub enum BaseCommand {
     FocusWindowTop,
     /// Args: `direction` (string, optional)
     FocusWindowAt,
+    /// Note: Focuses the window which has been urgent for the longest time, on any tag.
+    FocusUrgent,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
     /// Args: `tag_index` (int)
@@ -79,9 +91,75 @@ pub enum BaseCommand {
     DecreaseMainCount,
     /// Args: `multiplier-value` (float)
     SetMarginMultiplier,
+    /// Note: Only in the `Manual` layout, the next window opens next to the focused one.
+    SplitHorizontal,
+    /// Note: Only in the `Manual` layout, the next window opens below the focused one.
+    SplitVertical,
+    /// Note: Only in the `Manual` layout.
+    ToggleSplitOrientation,
+    /// Args: `percentage` (int, negative to shrink)
+    /// Note: Only in the `Manual` layout, resizes the focused window within its split.
+    ResizeSplit,
+    /// Note: Only in the `Scrolling` layout, scrolls to the column left of the focused window.
+    ScrollLeft,
+    /// Note: Only in the `Scrolling` layout, scrolls to the column right of the focused window.
+    ScrollRight,
+    /// Note: Only in the `Scrolling` layout, moves the focused window into the column left of it.
+    ConsumeIntoColumn,
+    /// Note: Only in the `Scrolling` layout, moves the focused window into a column of its own.
+    ExpelFromColumn,
+    /// Args: `percentage` (int, of the workspace width)
+    /// Note: Only in the `Scrolling` layout.
+    SetColumnWidth,
     UnloadTheme,
     /// Args: `Path_to/theme.ron`
     LoadTheme,
+    /// Args: `Path_to/layout.ron` or the layout itself in RON
+    /// Note: Replaces the layout of the same name, until the next reload.
+    LoadLayout,
+    /// Args: `LayoutName`
+    /// Note: The last layout of a workspace or tag cannot be removed.
+    RemoveLayout,
+    /// Args: `label` (string)
+    AddTag,
+    /// Args: `tag_index` (int, optional)
+    /// Note: Without a tag, the focused tag is removed. Its windows move to the previous tag.
+    RemoveTag,
+    /// Args: `tag_index` (int) `label` (string)
+    RenameTag,
+    /// Args: `tag_index` (int) `position` (int)
+    MoveTag,
+    /// Args: `tag_index` (int)
+    /// Note: Adds the tag to the focused workspace's view, or removes it if it is in view.
+    ToggleTagView,
+    /// Args: `tag_index` (int)
+    /// Note: Puts the focused window on the tag too, or takes it off the tag.
+    ToggleWindowTag,
+    /// Args: `mark` (string)
+    MarkWindow,
+    /// Args: `mark` (string, optional)
+    /// Note: Without a mark, all marks of the focused window are removed.
+    UnmarkWindow,
+    /// Args: `mark` (string)
+    FocusMark,
+    /// Args: `mark` (string)
+    SwapWithMark,
+    /// Args: `mark` (string) `tag_index` (int)
+    SendMarkToTag,
+    /// Prints all managed windows as JSON.
+    GetWindows,
+    /// Prints all workspaces as JSON.
+    GetWorkspaces,
+    /// Prints all tags as JSON.
+    GetTags,
+    /// Prints the names of the available layouts as JSON.
+    GetLayouts,
+    /// Prints all scratchpads as JSON.
+    GetScratchpads,
+    /// Prints the focused window as JSON.
+    GetFocused,
+    /// Prints the loaded config as JSON.
+    GetConfig,
 }
 
 impl std::convert::From<BaseCommand> for String {
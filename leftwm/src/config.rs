@@ -13,9 +13,13 @@ use super::ThemeConfig;
 use crate::config::keybind::Keybind;
 use anyhow::Result;
 use leftwm_core::{
-    DisplayAction, DisplayServer, Manager, ReturnPipe,
-    config::{InsertBehavior, ScratchPad, WindowHidingStrategy, Workspace},
+    CommandError, DisplayAction, DisplayServer, ErrorKind, Manager,
+    config::{
+        InsertBehavior, OutputProfile, ScratchPad, WindowCountLayout, WindowHidingStrategy,
+        Workspace,
+    },
     layouts::LayoutMode,
+    models::criteria::matches_whole,
     models::{
         FocusBehaviour, FocusOnActivationBehaviour, Gutter, Handle, Margins, Window, WindowState,
         WindowType,
@@ -31,13 +35,13 @@ use ron::{
     ser::{PrettyConfig, to_string_pretty},
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryInto;
+use std::default::Default;
 use std::env;
 use std::fs;
 use std::fs::File;
 use std::io::prelude::Write;
 use std::path::{Path, PathBuf};
-use std::{convert::TryInto, fs::OpenOptions};
-use std::{default::Default, error::Error};
 use xdg::BaseDirectories;
 
 /// Path to file where state will be dumped upon soft reload.
@@ -75,6 +79,12 @@ pub struct WindowHook {
     /// Handle the window as if it was of this `_NET_WM_WINDOW_TYPE`
     pub spawn_as_type: Option<WindowType>,
     pub hiding_strategy: Option<WindowHidingStrategy>,
+    /// Swallow the terminal the window was launched from, overrides `swallow_terminals`
+    pub swallow: Option<bool>,
+    /// What to do when the window asks for attention: `DoNothing`, `MarkUrgent` or `SwitchTo`
+    pub urgency: Option<FocusOnActivationBehaviour>,
+    /// Keep to the `WM_NORMAL_HINTS` of the window when tiled, they are honored if not set
+    pub honor_size_hints: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -130,16 +140,8 @@ impl WindowHook {
     fn score_window<H: Handle>(&self, window: &Window<H>) -> u8 {
         // returns true if any of the items in the provided `Vec<&Option<String>>` is Some and matches the `&Regex`
         let matches_any = |re: &Regex, strs: Vec<&Option<String>>| {
-            strs.iter().any(|str| {
-                str.as_ref().is_some_and(|s| {
-                    // we match the class/title to the window rule by checking if replacing the text
-                    // with the regex makes the string empty. if the original string is already
-                    // empty, this will match it to every regex, so we need to check for that.
-                    // however, if the window rule is explicitly for empty strings, we still
-                    // want empty strings to match to it.
-                    re.replace(s, "").is_empty() && (!s.is_empty() || re.as_str().is_empty())
-                })
-            })
+            strs.iter()
+                .any(|str| str.as_ref().is_some_and(|s| matches_whole(re, s)))
         };
 
         let class_score = self.window_class.as_ref().map_or(0, |re| {
@@ -208,6 +210,9 @@ impl WindowHook {
             window.r#type = w_type;
         }
         window.hiding_strategy = self.hiding_strategy;
+        window.swallow = self.swallow;
+        window.urgency = self.urgency;
+        window.honor_size_hints = self.honor_size_hints;
     }
 }
 
@@ -238,10 +243,14 @@ pub struct Config {
     pub modkey: String,
     pub mousekey: Option<Modifier>,
     pub workspaces: Option<Vec<Workspace>>,
+    /// Replace `workspaces` while exactly the outputs of a profile are connected.
+    pub output_profiles: Vec<OutputProfile>,
     pub tags: Option<Vec<String>>,
     pub layouts: Vec<String>,
     pub layout_definitions: Vec<Layout>,
     pub layout_mode: LayoutMode,
+    /// Layouts switched to by the number of tiled windows, eg. `Monocle` for a single window.
+    pub window_count_layouts: Vec<WindowCountLayout>,
     pub insert_behavior: InsertBehavior,
     pub scratchpad: Option<Vec<ScratchPad>>,
     pub window_rules: Option<Vec<WindowHook>>,
@@ -258,6 +267,9 @@ pub struct Config {
     pub disable_cursor_reposition_on_resize: bool,
     pub focus_on_activation: FocusOnActivationBehaviour,
     pub window_hiding_strategy: WindowHidingStrategy,
+    pub swallow_terminals: bool,
+    /// Records every display event and command to this file, for `leftwm-replay`.
+    pub record_path: Option<PathBuf>,
     #[cfg(feature = "lefthk")]
     pub keybind: Vec<Keybind>,
     pub state_path: Option<PathBuf>,
@@ -392,6 +404,21 @@ fn absolute_path(path: &str) -> Option<PathBuf> {
     std::fs::canonicalize(exp_path.as_ref()).ok()
 }
 
+/// Reads a layout from the RON file at `source`, or else from `source` itself.
+fn parse_layout(source: &str) -> std::result::Result<Layout, String> {
+    let ron = Options::default()
+        .with_default_extension(Extensions::IMPLICIT_SOME | Extensions::UNWRAP_NEWTYPES);
+    match absolute_path(source) {
+        Some(path) => {
+            let contents = fs::read_to_string(&path)
+                .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+            ron.from_str(&contents)
+        }
+        None => ron.from_str(source),
+    }
+    .map_err(|err| format!("Could not parse layout: {err}"))
+}
+
 #[cfg(feature = "lefthk")]
 impl lefthk_core::config::Config for Config {
     fn mapped_bindings(&self) -> Vec<lefthk_core::config::Keybind> {
@@ -443,6 +470,10 @@ impl leftwm_core::Config for Config {
         self.workspaces.clone()
     }
 
+    fn output_profiles(&self) -> Vec<OutputProfile> {
+        self.output_profiles.clone()
+    }
+
     fn focus_behaviour(&self) -> FocusBehaviour {
         self.focus_behaviour
     }
@@ -482,6 +513,10 @@ impl leftwm_core::Config for Config {
         self.layout_mode
     }
 
+    fn window_count_layouts(&self) -> Vec<WindowCountLayout> {
+        self.window_count_layouts.clone()
+    }
+
     fn insert_behavior(&self) -> InsertBehavior {
         self.insert_behavior
     }
@@ -497,48 +532,59 @@ impl leftwm_core::Config for Config {
     fn command_handler<H: Handle, SERVER: DisplayServer<H>>(
         command: &str,
         manager: &mut Manager<H, Self, SERVER>,
-    ) -> bool {
-        let mut return_pipe = get_return_pipe();
-        if let Some((command, value)) = command.split_once(' ') {
-            match command {
-                "LoadTheme" => {
-                    if let Some(absolute) = absolute_path(value.trim()) {
-                        manager.config.theme_setting.load(absolute);
-                        write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
-                    } else {
-                        tracing::warn!("Path submitted does not exist: {}", value.trim());
-                        write_to_pipe(&mut return_pipe, "ERROR: Path submitted does not exist");
-                    }
-                    manager.load_theme_config()
-                }
-                "UnloadTheme" => {
-                    manager.config.theme_setting = ThemeConfig::default();
-                    write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
-                    manager.load_theme_config()
-                }
-                _ => {
-                    tracing::warn!("Command not recognized: {}", command);
-                    write_to_pipe(&mut return_pipe, "ERROR: Command not recognized");
-                    false
-                }
+    ) -> Result<bool, CommandError> {
+        let (command, value) = command.split_once(' ').unwrap_or((command, ""));
+        match command {
+            "LoadTheme" if value.trim().is_empty() => {
+                tracing::warn!("Missing parameter theme_path");
+                Err(CommandError::new(
+                    ErrorKind::InvalidArguments,
+                    "Missing parameter theme_path",
+                ))
             }
-        } else {
-            match command {
-                "LoadTheme" => {
-                    tracing::warn!("Missing parameter theme_path");
-                    write_to_pipe(&mut return_pipe, "ERROR: Missing parameter theme_path");
-                    false
-                }
-                "UnloadTheme" => {
-                    manager.config.theme_setting = ThemeConfig::default();
-                    write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
-                    manager.load_theme_config()
-                }
-                _ => {
-                    tracing::warn!("Command not recognized: {}", command);
-                    write_to_pipe(&mut return_pipe, "ERROR: Command not recognized");
-                    false
+            "LoadTheme" => {
+                let Some(absolute) = absolute_path(value.trim()) else {
+                    tracing::warn!("Path submitted does not exist: {}", value.trim());
+                    return Err(CommandError::new(
+                        ErrorKind::ExecutionFailed,
+                        "Path submitted does not exist",
+                    ));
+                };
+                manager.config.theme_setting.load(absolute);
+                Ok(manager.load_theme_config())
+            }
+            "UnloadTheme" => {
+                manager.config.theme_setting = ThemeConfig::default();
+                Ok(manager.load_theme_config())
+            }
+            "LoadLayout" | "RemoveLayout" if value.trim().is_empty() => {
+                tracing::warn!("Missing parameter for {}", command);
+                Err(CommandError::new(
+                    ErrorKind::InvalidArguments,
+                    format!("Missing parameter for {command}"),
+                ))
+            }
+            "LoadLayout" => {
+                let layout = parse_layout(value.trim()).map_err(|err| {
+                    tracing::warn!("{}", err);
+                    CommandError::new(ErrorKind::ExecutionFailed, err)
+                })?;
+                manager.state.layout_manager.load_layout(&layout);
+                Ok(true)
+            }
+            "RemoveLayout" => match manager.state.layout_manager.remove_layout(value.trim()) {
+                Ok(()) => Ok(true),
+                Err(err) => {
+                    tracing::warn!("{}", err);
+                    Err(CommandError::new(ErrorKind::ExecutionFailed, err))
                 }
+            },
+            _ => {
+                tracing::warn!("Command not recognized: {}", command);
+                Err(CommandError::new(
+                    ErrorKind::UnknownCommand,
+                    format!("Command not recognized: {command}"),
+                ))
             }
         }
     }
@@ -624,6 +670,13 @@ impl leftwm_core::Config for Config {
             .unwrap_or_else(|| "#FF0000".to_string())
     }
 
+    fn urgent_border_color(&self) -> String {
+        self.theme_setting
+            .urgent_border_color
+            .clone()
+            .unwrap_or_else(|| "#FFFF00".to_string())
+    }
+
     fn on_new_window_cmd(&self) -> Option<String> {
         self.theme_setting.on_new_window_cmd.clone()
     }
@@ -728,6 +781,21 @@ impl leftwm_core::Config for Config {
     fn window_hiding_strategy(&self) -> WindowHidingStrategy {
         self.window_hiding_strategy
     }
+
+    fn swallow_terminals(&self) -> bool {
+        self.swallow_terminals
+    }
+
+    fn record_path(&self) -> Option<PathBuf> {
+        self.record_path.clone()
+    }
+
+    fn as_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|err| {
+            tracing::warn!("Could not serialize config: {}", err);
+            serde_json::Value::Null
+        })
+    }
 }
 
 impl Config {
@@ -743,23 +811,6 @@ impl Config {
     }
 }
 
-fn get_return_pipe() -> Result<File, Box<dyn std::error::Error>> {
-    let file_name = ReturnPipe::pipe_name();
-    let file_path = BaseDirectories::with_prefix("leftwm");
-    let file_path = file_path
-        .find_runtime_file(file_name)
-        .ok_or("Unable to open return pipe")?;
-    Ok(OpenOptions::new().append(true).open(file_path)?)
-}
-
-fn write_to_pipe(return_pipe: &mut Result<File, Box<dyn Error>>, msg: &str) {
-    if let Ok(pipefile) = return_pipe
-        && let Err(e) = writeln!(pipefile, "{msg}")
-    {
-        tracing::warn!("Unable to connect to return pipe: {e}");
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -779,6 +830,20 @@ mod tests {
         assert!(ron_config.is_ok(), "Could not deserialize default config");
     }
 
+    #[test]
+    fn config_serializes_to_json_test() {
+        let config = Config::default();
+        let json = leftwm_core::Config::as_json(&config);
+        assert_eq!(json["tags"], serde_json::json!(config.tags));
+    }
+
+    #[test]
+    fn layouts_are_parsed_from_ron() {
+        let layout = parse_layout(r#"(name: "Wide", columns: (main: (count: 2)))"#).unwrap();
+        assert_eq!(layout.name, "Wide");
+        assert!(parse_layout("/does/not/exist.ron").is_err());
+    }
+
     #[test]
     fn create_valid_regex() {
         let serializable_regex = SerializableRegex::new(".*");
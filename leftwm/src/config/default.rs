@@ -223,10 +223,12 @@ impl Default for Config {
             // Using Backend's feature fallback
             backend: Backend::default(),
             workspaces: Some(vec![]),
+            output_profiles: vec![],
             tags: Some(tags),
             layouts: layouts.names(),
             layout_definitions: layouts.layouts,
             layout_mode: LayoutMode::Tag,
+            window_count_layouts: vec![],
             // TODO: add sane default for scratchpad config.
             // Currently default values are set in sane_dimension fn.
             scratchpad: Some(vec![scratchpad]),
@@ -240,6 +242,8 @@ impl Default for Config {
             single_window_border: true,
             insert_behavior: leftwm_core::config::InsertBehavior::Bottom,
             window_hiding_strategy: WindowHidingStrategy::default(),
+            swallow_terminals: false,
+            record_path: None,
             modkey: "Mod4".to_owned(),     // win key
             mousekey: Some("Mod4".into()), // win key
             #[cfg(feature = "lefthk")]
@@ -29,11 +29,57 @@ impl Keybind {
         &self,
         config: &Config,
     ) -> Result<lefthk_core::config::Keybind> {
+        self.check_value(config)?;
+
+        let command: String = if self.command == BaseCommand::Execute {
+            self.value.clone()
+        } else {
+            let mut head = "leftwm-command ".to_owned();
+            let mut command_parts: String = self.command.into();
+            if !self.value.is_empty() {
+                let args = if self.command == BaseCommand::GotoTag {
+                    format!(" {} {}", self.value, !config.disable_current_tag_swap)
+                } else {
+                    format!(" {}", self.value)
+                };
+                command_parts.push_str(&args);
+            }
+            _ = writeln!(head, "'{command_parts}'");
+            head
+        };
+        Ok(lefthk_core::config::Keybind {
+            command: lefthk_core::config::command::Execute::new(&command).normalize(),
+            modifier: self
+                .modifier
+                .as_ref()
+                .unwrap_or(&"None".into())
+                .clone()
+                .into(),
+            key: self.key.clone(),
+        })
+    }
+
+    /// Checks the value of the command is valid for it.
+    fn check_value(&self, config: &Config) -> Result<()> {
         let value_is_some = !self.value.is_empty();
         match &self.command {
-            BaseCommand::Execute | BaseCommand::LoadTheme => {
+            BaseCommand::Execute
+            | BaseCommand::LoadTheme
+            | BaseCommand::LoadLayout
+            | BaseCommand::RemoveLayout => {
                 ensure!(value_is_some, "value must not be empty");
             }
+            BaseCommand::AddTag
+            | BaseCommand::RemoveTag
+            | BaseCommand::RenameTag
+            | BaseCommand::MoveTag
+            | BaseCommand::ToggleTagView
+            | BaseCommand::ToggleWindowTag
+            | BaseCommand::MarkWindow
+            | BaseCommand::FocusMark
+            | BaseCommand::SwapWithMark
+            | BaseCommand::UnmarkWindow
+            | BaseCommand::SendMarkToTag => self.check_tag_or_mark_value()?,
             BaseCommand::ToggleScratchPad
             | BaseCommand::AttachScratchPad
             | BaseCommand::NextScratchPadWindow
@@ -60,15 +106,55 @@ impl Keybind {
             BaseCommand::SwapWindowTop if value_is_some => {
                 bool::from_str(&self.value).context("invalid boolean value for SwapWindowTop")?;
             }
+            BaseCommand::ShowDesktop if value_is_some => {
+                bool::from_str(&self.value).context("invalid boolean value for ShowDesktop")?;
+            }
+            BaseCommand::RestoreMinimized => {
+                ensure!(value_is_some, "value must be the handle of a window");
+            }
             BaseCommand::MoveToTag => {
                 usize::from_str(&self.value).context("invalid index value for SendWindowToTag")?;
             }
+            BaseCommand::SetLayout
+            | BaseCommand::ResizeSplit
+            | BaseCommand::SetColumnWidth
+            | BaseCommand::IncreaseMainWidth
+            | BaseCommand::DecreaseMainWidth
+            | BaseCommand::SetMarginMultiplier => self.check_layout_value(config)?,
+            BaseCommand::FocusNextTag | BaseCommand::FocusPreviousTag if value_is_some => {
+                ensure!(
+                    usize::from_str(&self.value).is_ok()
+                        || matches!(
+                            &self.value.as_str(),
+                            &"" | &"goto_empty"
+                                | &"ignore_empty"
+                                | &"goto_used"
+                                | &"ignore_used"
+                                | &"default"
+                        ),
+                    "Value should be empty, or one of 'default', 'goto_empty', 'ignore_empty', 'goto_used', 'ignore_used'"
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Checks the value of the commands changing the layout.
+    fn check_layout_value(&self, config: &Config) -> Result<()> {
+        match &self.command {
             BaseCommand::SetLayout => {
                 ensure!(
                     config.layouts.contains(&self.value),
                     "could not parse layout for command SetLayout"
                 );
             }
+            BaseCommand::ResizeSplit => {
+                i32::from_str(&self.value).context("invalid percentage for ResizeSplit")?;
+            }
+            BaseCommand::SetColumnWidth => {
+                i32::from_str(&self.value).context("invalid percentage for SetColumnWidth")?;
+            }
             BaseCommand::IncreaseMainWidth => {
                 i8::from_str(&self.value).context("invalid width value for IncreaseMainWidth")?;
             }
@@ -79,49 +165,64 @@ impl Keybind {
                 f32::from_str(&self.value)
                     .context("invalid margin multiplier for SetMarginMultiplier")?;
             }
-            BaseCommand::FocusNextTag | BaseCommand::FocusPreviousTag if value_is_some => {
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Checks the value of the commands changing tags or marks.
+    fn check_tag_or_mark_value(&self) -> Result<()> {
+        let value_is_some = !self.value.is_empty();
+        match &self.command {
+            BaseCommand::AddTag => {
+                ensure!(value_is_some, "value must not be empty");
+            }
+            BaseCommand::RemoveTag if value_is_some => {
+                usize::from_str(&self.value).context("invalid index value for RemoveTag")?;
+            }
+            BaseCommand::RenameTag => {
+                let (tag, _) = self
+                    .value
+                    .split_once(' ')
+                    .context("value should be a tag index followed by a label")?;
+                usize::from_str(tag).context("invalid index value for RenameTag")?;
+            }
+            BaseCommand::MoveTag => {
+                let (tag, position) = self
+                    .value
+                    .split_once(' ')
+                    .context("value should be a tag index followed by a position")?;
+                usize::from_str(tag).context("invalid index value for MoveTag")?;
+                usize::from_str(position).context("invalid position value for MoveTag")?;
+            }
+            BaseCommand::ToggleTagView => {
+                usize::from_str(&self.value).context("invalid index value for ToggleTagView")?;
+            }
+            BaseCommand::ToggleWindowTag => {
+                usize::from_str(&self.value).context("invalid index value for ToggleWindowTag")?;
+            }
+            BaseCommand::MarkWindow | BaseCommand::FocusMark | BaseCommand::SwapWithMark => {
                 ensure!(
-                    usize::from_str(&self.value).is_ok()
-                        || matches!(
-                            &self.value.as_str(),
-                            &"" | &"goto_empty"
-                                | &"ignore_empty"
-                                | &"goto_used"
-                                | &"ignore_used"
-                                | &"default"
-                        ),
-                    "Value should be empty, or one of 'default', 'goto_empty', 'ignore_empty', 'goto_used', 'ignore_used'"
+                    value_is_some && !self.value.contains(char::is_whitespace),
+                    "Value should be a mark name without whitespace"
+                );
+            }
+            BaseCommand::UnmarkWindow if value_is_some => {
+                ensure!(
+                    !self.value.contains(char::is_whitespace),
+                    "Value should be empty or a mark name without whitespace"
                 );
             }
+            BaseCommand::SendMarkToTag => {
+                let (_, tag) = self
+                    .value
+                    .split_once(' ')
+                    .context("value should be a mark name followed by a tag index")?;
+                usize::from_str(tag).context("invalid index value for SendMarkToTag")?;
+            }
             _ => {}
         }
-
-        let command: String = if self.command == BaseCommand::Execute {
-            self.value.clone()
-        } else {
-            let mut head = "leftwm-command ".to_owned();
-            let mut command_parts: String = self.command.into();
-            if !self.value.is_empty() {
-                let args = if self.command == BaseCommand::GotoTag {
-                    format!(" {} {}", self.value, !config.disable_current_tag_swap)
-                } else {
-                    format!(" {}", self.value)
-                };
-                command_parts.push_str(&args);
-            }
-            _ = writeln!(head, "'{command_parts}'");
-            head
-        };
-        Ok(lefthk_core::config::Keybind {
-            command: lefthk_core::config::command::Execute::new(&command).normalize(),
-            modifier: self
-                .modifier
-                .as_ref()
-                .unwrap_or(&"None".into())
-                .clone()
-                .into(),
-            key: self.key.clone(),
-        })
+        Ok(())
     }
 }
 
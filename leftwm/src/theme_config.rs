@@ -20,6 +20,8 @@ pub struct ThemeConfig {
     pub default_border_color: Option<String>,
     pub floating_border_color: Option<String>,
     pub focused_border_color: Option<String>,
+    /// Border of windows asking for attention.
+    pub urgent_border_color: Option<String>,
     pub background_color: Option<String>,
     #[serde(rename = "on_new_window")]
     pub on_new_window_cmd: Option<String>,
@@ -50,6 +52,7 @@ impl Default for ThemeConfig {
             default_border_color: Some("#000000".to_owned()),
             floating_border_color: Some("#000000".to_owned()),
             focused_border_color: Some("#FF0000".to_owned()),
+            urgent_border_color: Some("#FFFF00".to_owned()),
             background_color: Some("#333333".to_owned()),
             on_new_window_cmd: None,
         }
@@ -117,6 +120,7 @@ mod tests {
     default_border_color: Some("#222222"),
     floating_border_color: Some("#005500"),
     focused_border_color: Some("#FFB53A"),
+    urgent_border_color: Some("#FFFF00"),
     background_color: Some("#333333"),
     on_new_window: Some("echo Hello World"),
 
@@ -145,6 +149,7 @@ mod tests {
                 default_border_color: Some("#222222".to_string()),
                 floating_border_color: Some("#005500".to_string()),
                 focused_border_color: Some("#FFB53A".to_string()),
+                urgent_border_color: Some("#FFFF00".to_string()),
                 background_color: Some("#333333".to_owned()),
                 on_new_window_cmd: Some("echo Hello World".to_string()),
             }